@@ -22,8 +22,17 @@ fn main() {
     dbg!(exe.mz_header());
     dbg!(exe.pe_header());
 
+    if let Ok(Some(rich)) = exe.rich_header() {
+        eprintln!("rich_header.key                  = 0x{:08x} (checksum valid: {})", rich.key, rich.is_checksum_valid());
+        for (i, entry) in rich.entries.iter().enumerate() {
+            eprintln!("rich_header.entries[{: >2}]         = {:?} ({})", i, entry, entry.toolchain().unwrap_or("unknown toolchain"));
+        }
+        eprintln!();
+    }
+
+    let strings = exe.read_string_table().unwrap_or_default();
     for (i, section) in exe.pe_section_headers().iter().enumerate() {
-        eprintln!("sections[{}].name                = {:?}", i, section.name);
+        eprintln!("sections[{}].name                = {:?}", i, strings.section_name(section));
         eprintln!("sections[{}].characteristics     = {:?}", i, section.characteristics);
         eprintln!("sections[{}].virtual_address     = base + 0x{:08x} .. 0x{:08x}", i, section.virtual_address.to_u32(), section.virtual_address.to_u32() + section.virtual_size);
         match section.pointer_to_raw_data {
@@ -119,7 +128,18 @@ fn main() {
             },
             2 => {}, // resource
             3 => {}, // exception
-            4 => {}, // security
+            4 => { // security
+                match exe.authenticode_signatures() {
+                    Err(err) => eprintln!("    signatures = {:?}", err),
+                    Ok(signatures) => for (i, signature) in signatures.iter().flat_map(|s| s.iter_all()).enumerate() {
+                        eprintln!("    signature[{}].digest_algorithm   = {:?}", i, signature.digest_algorithm);
+                        eprintln!("    signature[{}].signer             = {:?}", i, signature.signer_certificate().map(|c| c.subject.to_string()));
+                        for (k, ts) in signature.signers.iter().flat_map(|s| s.timestamps.iter()).enumerate() {
+                            eprintln!("    signature[{}].timestamp[{}]       = {:?} ({:?})", i, k, ts.time, ts.kind);
+                        }
+                    },
+                }
+            },
             5 => {}, // basereloc
             6 => {}, // debug
             7 => {}, // architecture
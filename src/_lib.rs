@@ -1,8 +1,16 @@
 #[macro_use] #[path = "macros/_macros.rs"] mod macros;
 
-pub mod mz;
+#[path = "ar/_ar.rs"]               pub mod ar;
+#[path = "authenticode/_authenticode.rs"] pub mod authenticode;
+#[path = "clr/_clr.rs"]             pub mod clr;
+#[path = "coff/_coff.rs"]           pub mod coff;
+#[path = "def/_def.rs"]             pub mod def;
 #[path = "error/_error.rs"]         pub mod error;
+#[path = "exe/_exe.rs"]             pub mod exe;
 #[path = "io/_io.rs"]               pub mod io;
+#[path = "le/_le.rs"]               pub mod le;
+#[path = "mz/_mz.rs"]               pub mod mz;
+#[path = "ne/_ne.rs"]               pub mod ne;
 #[path = "pe/_pe.rs"]               pub mod pe;
 
 mod from_memory;                    #[allow(unused_imports)] pub use from_memory::*;
@@ -0,0 +1,34 @@
+//! **Ar**chives (`.lib` static and import libraries, `.a` files) in the `!<arch>\n` format.
+//!
+//! Archives are a flat sequence of [`MemberHeader`]-prefixed members.  MSVC-style archives start with up to two linker members
+//! (symbol indicies) and a longnames member, followed by the actual COFF objects (readable with [`coff::Reader`]) or short import objects ([`coff::ImportObject`].)
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Reader`]                | Reads and caches the special members and [`Member`] list, and member data on demand.
+//! | [`MemberReader`]          | impl [`ReadAt`](maulingmonkey_io_adapters::ReadAt) for a single [`Member`]'s data.
+//! | [`Member`]                | A regular archive member, with its name resolved through [`LongNames`].
+//! | [`MemberHeader`]          | The 60-byte textual header preceding every member.
+//! | [`FirstLinkerMember`]     | The first `/` member: a big-endian, unsorted symbol index.
+//! | [`SecondLinkerMember`]    | The second `/` member: a little-endian, sorted symbol index (MSVC only.)
+//! | [`LinkerSymbol`]          | A symbol name and the offset of the [`Member`] defining it.
+//! | [`LongNames`]             | The `//` member: names of members too long for [`MemberHeader::name`].
+//! | [`Writer`]                | Builds an archive from members and the public symbols they define.
+//! | [`ImportLibrary`]         | Generates an import library for a DLL from [`pe::Exports`], a [`def::ModuleDefinition`], or a list of [`ImportLibraryExport`]s.
+//!
+//! ## References
+//! *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#archive-library-file-format>
+//! *   <https://en.wikipedia.org/wiki/Ar_(Unix)>
+
+mod import_library;                 pub use import_library::*;
+mod linker_member;                  pub use linker_member::*;
+mod long_names;                     pub use long_names::*;
+mod member;                         pub use member::*;
+mod member_header;                  pub use member_header::*;
+mod reader;                         pub use reader::*;
+mod writer;                         pub use writer::*;
+
+#[cfg(doc)] use crate::*;
+
+/// The 8-byte signature every archive starts with
+pub const SIGNATURE : &[u8; 8] = b"!<arch>\n";
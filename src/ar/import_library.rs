@@ -0,0 +1,255 @@
+use crate::*;
+
+use maulingmonkey_io_adapters::ReadAt;
+
+use bytemuck::*;
+
+use std::io::{self, Write};
+
+
+
+/// ⨯ { [dll_name](Self::dll_name), [machine](Self::machine), [exports](Self::exports) }<br>
+/// An import library (`.lib`) to generate for a DLL<br>
+/// <br>
+///
+/// The written archive matches what `lib.exe /def` and `llvm-dlltool` produce: an import descriptor, a null import descriptor, a null thunk,
+/// and one [`coff::ImportObject`] per export.
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_format_exe::*;
+/// let dll = pe::Reader::open("thirdparty.dll").unwrap();
+/// let lib = ar::ImportLibrary::from_pe(&dll).unwrap();
+/// lib.write(&mut std::fs::File::create("thirdparty.lib").unwrap()).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ImportLibrary {
+    /// The name of the DLL to import from (e.g. `"KERNEL32.dll"`)
+    pub dll_name:   String,
+    pub machine:    pe::Machine,
+    pub exports:    Vec<ImportLibraryExport>,
+}
+
+/// ⨯ { [name](Self::name), [symbol_name](Self::symbol_name), [ordinal](Self::ordinal), [noname](Self::noname), [import_type](Self::import_type) }<br>
+/// A single export of an [`ImportLibrary`]<br>
+/// <br>
+#[derive(Clone, Debug)]
+pub struct ImportLibraryExport {
+    /// The name exported by the DLL
+    pub name:           String,
+    /// The public symbol to define, or [`None`] to derive it from [`name`](Self::name) (prefixing `_` for [`pe::Machine::I386`] C names.)
+    /// Set this for decorated `__stdcall` imports like `_Sleep@4`.
+    pub symbol_name:    Option<String>,
+    /// The ordinal, used as a hint for named imports
+    pub ordinal:        Option<u16>,
+    /// Import by [`ordinal`](Self::ordinal) instead of by [`name`](Self::name)
+    pub noname:         bool,
+    pub import_type:    coff::ImportType,
+}
+
+impl ImportLibrary {
+    /// Describe the import library of a DLL from its [`pe::Exports`] (see [`def::ModuleDefinition::from_pe`].)
+    ///
+    /// Exports are imported as [`DATA`](coff::ImportType::DATA) if they point into a non-executable section, otherwise as [`CODE`](coff::ImportType::CODE).
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the image has no exports
+    /// *   [`io::Error`] forwarded from [`pe::Reader::read_exports`]
+    pub fn from_pe<R: ReadAt>(reader: &pe::Reader<R>) -> io::Result<Self> {
+        let def = def::ModuleDefinition::from_pe(reader)?;
+        Self::from_def(&def, reader.pe_header().file_header.machine)
+    }
+
+    /// Describe the import library of a DLL from a `.def` file.  [`PRIVATE`](def::Export::private) exports are skipped.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the `.def` file doesn't name the DLL
+    /// *   [`io::ErrorKind::InvalidInput`] if a [`NONAME`](def::Export::noname) export has no ordinal
+    pub fn from_def(def: &def::ModuleDefinition, machine: pe::Machine) -> io::Result<Self> {
+        let dll_name = def.name.clone().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "def::ModuleDefinition has no LIBRARY name to import from"))?;
+        let exports = def.exports.iter().filter(|e| !e.private).map(|e| {
+            if e.noname && e.ordinal.is_none() { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("NONAME export {:?} has no ordinal", e.name))) }
+            Ok(ImportLibraryExport {
+                name:           e.name.clone(),
+                symbol_name:    None,
+                ordinal:        e.ordinal,
+                noname:         e.noname,
+                import_type:    if e.data { coff::ImportType::DATA } else if e.constant { coff::ImportType::CONST } else { coff::ImportType::CODE },
+            })
+        }).collect::<io::Result<_>>()?;
+        Ok(Self { dll_name, machine, exports })
+    }
+
+    /// Build the [`coff::ImportObject`] of an export.
+    pub fn import_object(&self, export: &ImportLibraryExport) -> coff::ImportObject {
+        let symbol_name = export.symbol_name.clone().unwrap_or_else(|| {
+            let decorated = export.name.starts_with('?') || export.name.starts_with('@');
+            if self.machine == pe::Machine::I386 && !decorated { format!("_{}", export.name) } else { export.name.clone() }
+        });
+        let name = match (export.noname, export.ordinal) {
+            (true, Some(ordinal))   => coff::ImportName::Ordinal(ordinal),
+            _                       => coff::ImportName::Name { name: export.name.clone(), hint: export.ordinal.unwrap_or(0) },
+        };
+        coff::ImportObject::new(self.machine, &self.dll_name[..], symbol_name, name, export.import_type)
+    }
+
+    /// Write the import library as an archive.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if [`machine`](Self::machine) isn't I386, AMD64, ARMNT, or ARM64
+    /// *   [`io::Error`] forwarded from [`ar::Writer::write`]
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let (addr32nb, ptr_size) = match self.machine {
+            pe::Machine::I386   => (0x0007, 4),
+            pe::Machine::AMD64  => (0x0003, 8),
+            pe::Machine::ARMNT  => (0x0002, 4),
+            pe::Machine::ARM64  => (0x0002, 8),
+            _                   => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("unable to generate import library for {:?}", self.machine))),
+        };
+        let library = self.dll_name.rsplit_once('.').map_or(&self.dll_name[..], |(stem, _)| stem);
+        let import_descriptor   = format!("__IMPORT_DESCRIPTOR_{}", library);
+        let null_descriptor     = "__NULL_IMPORT_DESCRIPTOR".to_string();
+        let null_thunk          = format!("\x7f{}_NULL_THUNK_DATA", library);
+
+        let rw = pe::SectionCharacteristics::CNT_INITIALIZED_DATA | pe::SectionCharacteristics::MEM_READ | pe::SectionCharacteristics::MEM_WRITE;
+        let ptr_align = if ptr_size == 8 { pe::SectionCharacteristics::ALIGN_8BYTES } else { pe::SectionCharacteristics::ALIGN_4BYTES };
+        let mut dll_name = self.dll_name.as_bytes().to_vec();
+        dll_name.push(0);
+        if dll_name.len() & 1 != 0 { dll_name.push(0) }
+
+        let mut lib = ar::Writer::new();
+        lib.add_member(&self.dll_name[..], object(self.machine, &[
+            Section { name: b".idata$2", data: vec![0; 20], characteristics: rw | pe::SectionCharacteristics::ALIGN_4BYTES, relocations: &[(12, 2, addr32nb), (0, 3, addr32nb), (16, 4, addr32nb)] },
+            Section { name: b".idata$6", data: dll_name, characteristics: rw | pe::SectionCharacteristics::ALIGN_2BYTES, relocations: &[] },
+        ], &[
+            (&import_descriptor[..],    1, coff::StorageClass::EXTERNAL),
+            (".idata$2",                1, coff::StorageClass::SECTION),
+            (".idata$6",                2, coff::StorageClass::STATIC),
+            (".idata$4",                0, coff::StorageClass::SECTION),
+            (".idata$5",                0, coff::StorageClass::SECTION),
+            (&null_descriptor[..],      0, coff::StorageClass::EXTERNAL),
+            (&null_thunk[..],           0, coff::StorageClass::EXTERNAL),
+        ]), vec![import_descriptor.clone()]);
+        lib.add_member(&self.dll_name[..], object(self.machine, &[
+            Section { name: b".idata$3", data: vec![0; 20], characteristics: rw | pe::SectionCharacteristics::ALIGN_4BYTES, relocations: &[] },
+        ], &[
+            (&null_descriptor[..],      1, coff::StorageClass::EXTERNAL),
+        ]), vec![null_descriptor]);
+        lib.add_member(&self.dll_name[..], object(self.machine, &[
+            Section { name: b".idata$5", data: vec![0; ptr_size], characteristics: rw | ptr_align, relocations: &[] },
+            Section { name: b".idata$4", data: vec![0; ptr_size], characteristics: rw | ptr_align, relocations: &[] },
+        ], &[
+            (&null_thunk[..],           1, coff::StorageClass::EXTERNAL),
+        ]), vec![null_thunk]);
+
+        for export in self.exports.iter() {
+            let import = self.import_object(export);
+            let mut symbols = vec![format!("__imp_{}", import.symbol_name)];
+            if export.import_type == coff::ImportType::CODE { symbols.push(import.symbol_name.clone()) }
+            lib.add_member(&self.dll_name[..], import.to_bytes(), symbols);
+        }
+
+        lib.write(w)
+    }
+}
+
+struct Section<'a> {
+    name:               &'static [u8; 8],
+    data:               Vec<u8>,
+    characteristics:    pe::SectionCharacteristics,
+    /// (offset, symbol index, type)
+    relocations:        &'a [(u32, u32, u16)],
+}
+
+/// Build a minimal COFF object of `sections` and `symbols` (name, 1-based section number, storage class)
+fn object(machine: pe::Machine, sections: &[Section], symbols: &[(&str, i16, coff::StorageClass)]) -> Vec<u8> {
+    let headers_size = 20 + 40 * sections.len();
+    let mut raw = Vec::new();
+    let mut section_headers = Vec::new();
+    for section in sections {
+        let data_ptr = headers_size + raw.len();
+        raw.extend_from_slice(&section.data[..]);
+        let relocs_ptr = headers_size + raw.len();
+        for (offset, symbol, kind) in section.relocations.iter() {
+            raw.extend_from_slice(&offset.to_le_bytes());
+            raw.extend_from_slice(&symbol.to_le_bytes());
+            raw.extend_from_slice(&kind.to_le_bytes());
+        }
+        section_headers.extend_from_slice(section.name);
+        section_headers.extend_from_slice(&0u32.to_le_bytes()); // virtual_size
+        section_headers.extend_from_slice(&0u32.to_le_bytes()); // virtual_address
+        section_headers.extend_from_slice(&(section.data.len() as u32).to_le_bytes());
+        section_headers.extend_from_slice(&(data_ptr as u32).to_le_bytes());
+        section_headers.extend_from_slice(&(if section.relocations.is_empty() { 0 } else { relocs_ptr as u32 }).to_le_bytes());
+        section_headers.extend_from_slice(&0u32.to_le_bytes()); // pointer_to_linenumbers
+        section_headers.extend_from_slice(&(section.relocations.len() as u16).to_le_bytes());
+        section_headers.extend_from_slice(&0u16.to_le_bytes()); // number_of_linenumbers
+        section_headers.extend_from_slice(&section.characteristics.bits().to_le_bytes());
+    }
+
+    let mut strings = vec![0u8; 4];
+    let mut symbol_table = Vec::new();
+    for (name, section, class) in symbols.iter() {
+        if name.len() <= 8 {
+            let mut short = [0u8; 8];
+            short[..name.len()].copy_from_slice(name.as_bytes());
+            symbol_table.extend_from_slice(&short);
+        } else {
+            symbol_table.extend_from_slice(&0u32.to_le_bytes());
+            symbol_table.extend_from_slice(&(strings.len() as u32).to_le_bytes());
+            strings.extend_from_slice(name.as_bytes());
+            strings.push(0);
+        }
+        symbol_table.extend_from_slice(&0u32.to_le_bytes()); // value
+        symbol_table.extend_from_slice(&section.to_le_bytes());
+        symbol_table.extend_from_slice(&0u16.to_le_bytes()); // type
+        symbol_table.push(class.to_u8());
+        symbol_table.push(0); // naux
+    }
+    let strings_len = strings.len() as u32;
+    strings[..4].copy_from_slice(&strings_len.to_le_bytes());
+
+    let mut obj = Vec::new();
+    obj.extend_from_slice(bytes_of(&machine));
+    obj.extend_from_slice(&(sections.len() as u16).to_le_bytes());
+    obj.extend_from_slice(&0u32.to_le_bytes()); // time_date
+    obj.extend_from_slice(&((headers_size + raw.len()) as u32).to_le_bytes());
+    obj.extend_from_slice(&(symbols.len() as u32).to_le_bytes());
+    obj.extend_from_slice(&0u16.to_le_bytes()); // optional_header_size
+    obj.extend_from_slice(&0u16.to_le_bytes()); // characteristics
+    obj.extend_from_slice(&section_headers[..]);
+    obj.extend_from_slice(&raw[..]);
+    obj.extend_from_slice(&symbol_table[..]);
+    obj.extend_from_slice(&strings[..]);
+    obj
+}
+
+
+
+#[test] fn roundtrip() {
+    let lib = ImportLibrary { dll_name: "example_library.dll".into(), machine: pe::Machine::I386, exports: vec![
+        ImportLibraryExport { name: "foo".into(),   symbol_name: None,                  ordinal: Some(1), noname: false, import_type: coff::ImportType::CODE },
+        ImportLibraryExport { name: "Bar".into(),   symbol_name: Some("_Bar@8".into()), ordinal: None,    noname: false, import_type: coff::ImportType::CODE },
+        ImportLibraryExport { name: "baz".into(),   symbol_name: None,                  ordinal: Some(3), noname: true,  import_type: coff::ImportType::DATA },
+    ]};
+    let path = std::env::temp_dir().join(format!("maulingmonkey-format-exe-import-library-{}.lib", std::process::id()));
+    lib.write(&mut std::fs::File::create(&path).unwrap()).unwrap();
+    let archive = ar::Reader::open(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(archive.members().len(), 6);
+    assert!(archive.members().iter().all(|m| m.name == "example_library.dll"));
+    assert_eq!(archive.first_linker_member().unwrap().symbols.len(), 3 + 2 + 2 + 1);
+
+    let descriptor = archive.find_symbol("__IMPORT_DESCRIPTOR_example_library").unwrap();
+    let symbols = archive.read_coff(descriptor).unwrap().read_symbol_table().unwrap();
+    assert_eq!(symbols.symbols.iter().map(|s| &s.name[..]).collect::<Vec<_>>(), [
+        "__IMPORT_DESCRIPTOR_example_library", ".idata$2", ".idata$6", ".idata$4", ".idata$5", "__NULL_IMPORT_DESCRIPTOR", "\x7fexample_library_NULL_THUNK_DATA",
+    ]);
+
+    let bar = archive.read_import_object(archive.find_symbol("__imp__Bar@8").unwrap()).unwrap();
+    assert_eq!(bar.import_name(), coff::ImportName::Name { name: "Bar".into(), hint: 0 });
+    let baz = archive.read_import_object(archive.find_symbol("__imp__baz").unwrap()).unwrap();
+    assert_eq!((baz.import_name(), baz.header.import_type()), (coff::ImportName::Ordinal(3), coff::ImportType::DATA));
+    assert!(archive.find_symbol("_baz").is_none());
+}
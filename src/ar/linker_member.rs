@@ -0,0 +1,108 @@
+#[cfg(doc)] use crate::*;
+
+use std::io;
+
+
+
+/// ⨯ { [name](Self::name), [member_offset](Self::member_offset) }<br>
+/// A public symbol listed by a [`ar::FirstLinkerMember`] or [`ar::SecondLinkerMember`]<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkerSymbol {
+    pub name:           String,
+    /// File offset of the [`ar::MemberHeader`] of the member defining this symbol (see [`ar::Reader::member_at`])
+    pub member_offset:  u32,
+}
+
+/// ⨯ { [symbols](Self::symbols) }<br>
+/// The first `/` archive member: an index of public symbols, in big-endian, in archive order<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#first-linker-member>
+#[derive(Clone, Debug, Default)]
+pub struct FirstLinkerMember {
+    pub symbols:    Vec<LinkerSymbol>,
+}
+
+/// ⨯ { [member_offsets](Self::member_offsets), [symbols](Self::symbols) }<br>
+/// The second `/` archive member: an index of public symbols, in little-endian, sorted by name<br>
+/// <br>
+///
+/// Only MSVC-style archives have this member.  GNU `ar` only writes a [`ar::FirstLinkerMember`].
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#second-linker-member>
+#[derive(Clone, Debug, Default)]
+pub struct SecondLinkerMember {
+    /// File offsets of every [`ar::MemberHeader`], in archive order
+    pub member_offsets: Vec<u32>,
+    /// Every symbol, sorted by [`name`](LinkerSymbol::name), with member indicies resolved to [`member_offsets`](Self::member_offsets)
+    pub symbols:        Vec<LinkerSymbol>,
+}
+
+impl FirstLinkerMember {
+    /// Parse the data of the first linker member.
+    pub fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut r = data;
+        let n = take_u32(&mut r, u32::from_be_bytes)? as usize;
+        let offsets = (0 .. n).map(|_| take_u32(&mut r, u32::from_be_bytes)).collect::<io::Result<Vec<_>>>()?;
+        let symbols = offsets.into_iter().map(|member_offset| Ok(LinkerSymbol { name: take_name(&mut r)?, member_offset })).collect::<io::Result<_>>()?;
+        Ok(Self { symbols })
+    }
+}
+
+impl SecondLinkerMember {
+    /// Parse the data of the second linker member.
+    pub fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut r = data;
+        let m = take_u32(&mut r, u32::from_le_bytes)? as usize;
+        let member_offsets = (0 .. m).map(|_| take_u32(&mut r, u32::from_le_bytes)).collect::<io::Result<Vec<_>>>()?;
+        let n = take_u32(&mut r, u32::from_le_bytes)? as usize;
+        if r.len() < 2 * n { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "ar::SecondLinkerMember indicies extend past end of member")) }
+        let (indicies, mut r) = r.split_at(2 * n);
+        let symbols = indicies.chunks_exact(2).map(|i| {
+            let index = u16::from_le_bytes([i[0], i[1]]);
+            let member_offset = *usize::from(index).checked_sub(1).and_then(|i| member_offsets.get(i))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("ar::SecondLinkerMember member index {} out of bounds", index)))?;
+            Ok(LinkerSymbol { name: take_name(&mut r)?, member_offset })
+        }).collect::<io::Result<_>>()?;
+        Ok(Self { member_offsets, symbols })
+    }
+
+    /// Find a symbol by name, using a binary search of the sorted [`symbols`](Self::symbols).
+    pub fn find(&self, name: &str) -> Option<&LinkerSymbol> {
+        self.symbols.binary_search_by(|s| s.name.as_bytes().cmp(name.as_bytes())).ok().map(|i| &self.symbols[i])
+    }
+}
+
+fn take_u32(r: &mut &[u8], from_bytes: fn([u8; 4]) -> u32) -> io::Result<u32> {
+    if r.len() < 4 { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "ar linker member truncated")) }
+    let v = from_bytes([r[0], r[1], r[2], r[3]]);
+    *r = &r[4..];
+    Ok(v)
+}
+
+fn take_name(r: &mut &[u8]) -> io::Result<String> {
+    let nul = r.iter().position(|b| *b == 0).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "ar linker member symbol name unterminated"))?;
+    let name = String::from_utf8_lossy(&r[..nul]).into_owned();
+    *r = &r[nul+1..];
+    Ok(name)
+}
+
+
+
+#[test] fn parse() {
+    let first = FirstLinkerMember::parse(b"\0\0\0\x02\0\0\0\x80\0\0\x01\x00_foo\0_bar\0").unwrap();
+    assert_eq!(first.symbols, vec![
+        LinkerSymbol { name: "_foo".into(), member_offset: 0x80 },
+        LinkerSymbol { name: "_bar".into(), member_offset: 0x100 },
+    ]);
+
+    let second = SecondLinkerMember::parse(b"\x02\0\0\0\x80\0\0\0\x00\x01\0\0\x02\0\0\0\x02\0\x01\0_bar\0_foo\0").unwrap();
+    assert_eq!(second.member_offsets, vec![0x80, 0x100]);
+    assert_eq!(second.find("_foo"), Some(&LinkerSymbol { name: "_foo".into(), member_offset: 0x80 }));
+    assert_eq!(second.find("_bar").map(|s| s.member_offset), Some(0x100));
+    assert_eq!(second.find("_baz"), None);
+    assert!(SecondLinkerMember::parse(b"\x01\0\0\0\x80\0\0\0\x01\0\0\0\x02\0_foo\0").is_err());
+}
@@ -0,0 +1,31 @@
+#[cfg(doc)] use crate::*;
+
+
+
+/// The `//` archive member: names of [`ar::Member`]s too long to fit in [`ar::MemberHeader::name`]<br>
+/// <br>
+///
+/// Members with long names are named `"/123"`, where `123` is a decimal offset into this table.
+/// MSVC terminates names with `\0`, GNU `ar` with `/\n` - both are accepted.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#longnames-member>
+#[derive(Clone, Debug, Default)]
+pub struct LongNames {
+    data: Vec<u8>,
+}
+
+impl LongNames {
+    /// Create a longnames table from the raw data of the `//` member.
+    pub fn from_bytes(data: Vec<u8>) -> Self { Self { data } }
+
+    /// The raw data of the `//` member.
+    pub fn as_bytes(&self) -> &[u8] { &self.data[..] }
+
+    /// The name at `offset`, without its terminator, or [`None`] if `offset` is out of bounds.
+    pub fn get(&self, offset: u32) -> Option<&[u8]> {
+        let s = self.data.get(offset as usize ..)?;
+        let s = &s[..s.iter().position(|b| *b == 0 || *b == b'\n').unwrap_or(s.len())];
+        Some(s.strip_suffix(b"/").unwrap_or(s))
+    }
+}
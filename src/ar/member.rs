@@ -0,0 +1,25 @@
+use crate::*;
+
+use std::ops::Range;
+
+
+
+/// ⨯ { [offset](Self::offset), [header](Self::header), [name](Self::name) }<br>
+/// A regular archive member: typically a COFF object or a short import object<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Member {
+    /// File offset of the [`ar::MemberHeader`], as referenced by [`ar::LinkerSymbol::member_offset`]
+    pub offset: u32,
+    pub header: ar::MemberHeader,
+    /// The member name, with any `/` terminator stripped and `/123` resolved through [`ar::LongNames`]
+    pub name:   String,
+}
+
+impl Member {
+    /// The file offsets of this member's data, following the [`ar::MemberHeader`]
+    pub fn data_range(&self) -> Range<u64> {
+        let start = u64::from(self.offset) + ar::MemberHeader::SIZE as u64;
+        start .. start + u64::from(self.header.size)
+    }
+}
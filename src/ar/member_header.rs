@@ -0,0 +1,59 @@
+#[cfg(doc)] use crate::*;
+
+use std::io;
+
+
+
+/// ⨯ { [name](Self::name), [date](Self::date), [user_id](Self::user_id), [group_id](Self::group_id), [mode](Self::mode), [size](Self::size) }<br>
+/// `IMAGE_ARCHIVE_MEMBER_HEADER`: the textual header preceding every archive member<br>
+/// <br>
+///
+/// Every field is stored as space-padded ASCII.  Blank or unparseable [`date`](Self::date), [`user_id`](Self::user_id),
+/// [`group_id`](Self::group_id), and [`mode`](Self::mode) fields read as [`None`] - MSVC leaves them blank for special members.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#archive-member-headers>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemberHeader {
+    /// The raw name field, trailing spaces trimmed: `"/"`, `"//"`, `"/123"` (a [`ar::LongNames`] offset), or `"name/"`.
+    pub name:       String,
+    /// Seconds since the Unix epoch
+    pub date:       Option<u32>,
+    pub user_id:    Option<u32>,
+    pub group_id:   Option<u32>,
+    /// Octal file mode
+    pub mode:       Option<u32>,
+    /// Size of the member's data in bytes, excluding this header and any trailing padding byte
+    pub size:       u32,
+}
+
+impl MemberHeader {
+    /// Size of a single `IMAGE_ARCHIVE_MEMBER_HEADER` in bytes
+    pub const SIZE : usize = 60;
+
+    /// The 2-byte `` `\n `` terminator of every header
+    pub const END : &'static [u8; 2] = b"`\n";
+
+    pub(crate) fn from_record(r: &[u8; Self::SIZE]) -> io::Result<Self> {
+        if &r[58..60] != Self::END { return Err(io::Error::new(io::ErrorKind::InvalidData, "ar::MemberHeader::end != \"`\\n\"")) }
+        let size = field(&r[48..58]);
+        Ok(Self {
+            name:       String::from_utf8_lossy(&r[..16]).trim_end_matches(' ').into(),
+            date:       field(&r[16..28]).parse().ok(),
+            user_id:    field(&r[28..34]).parse().ok(),
+            group_id:   field(&r[34..40]).parse().ok(),
+            mode:       u32::from_str_radix(field(&r[40..48]), 8).ok(),
+            size:       size.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("ar::MemberHeader::size {:?} isn't a decimal number", size)))?,
+        })
+    }
+}
+
+fn field(f: &[u8]) -> &str { std::str::from_utf8(f).unwrap_or("").trim_end_matches(' ') }
+
+
+
+#[test] fn from_record() {
+    let h = MemberHeader::from_record(b"foo.obj/        1700000000              100666  1234      `\n").unwrap();
+    assert_eq!(h, MemberHeader { name: "foo.obj/".into(), date: Some(1700000000), user_id: None, group_id: None, mode: Some(0o100666), size: 1234 });
+    assert!(MemberHeader::from_record(b"foo.obj/        1700000000              100666  1234      \n\n").is_err());
+}
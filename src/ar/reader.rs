@@ -0,0 +1,187 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, SeeklessFile};
+
+use std::convert::*;
+use std::fs::File;
+use std::path::PathBuf;
+
+
+
+/// Wraps a [`ReadAt`] for ease of reading an archive (`.lib`) by caching the linker members, [`ar::LongNames`], and [`ar::Member`] list.<br>
+/// <br>
+///
+/// Member data is read on demand: see [`read_member_data`](Self::read_member_data) and [`read_coff`](Self::read_coff).
+pub struct Reader<R> {
+    src:                        Src,
+    reader:                     R,
+    first_linker_member:        Option<ar::FirstLinkerMember>,
+    second_linker_member:       Option<ar::SecondLinkerMember>,
+    long_names:                 Option<ar::LongNames>,
+    members:                    Vec<ar::Member>,
+}
+
+impl Reader<SeeklessFile> {
+    /// Open an archive and parse the special members and member headers.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path);
+        let src = Src::PathBuf(path);
+        Reader::read_src(SeeklessFile::from(src.anno(file, "error opening ar::Reader")?), src)
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    /// Read an archive and parse the special members and member headers.  Prefer [`Reader::open`] for better error messages.
+    pub fn read(reader: R) -> io::Result<Self> { Self::read_src(reader, Src::Unknown) }
+
+    /// Read the data of a [`ar::Member`].
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the member was truncated
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_member_data(&self, member: &ar::Member) -> io::Result<Vec<u8>> {
+        let range = member.data_range();
+        self.src.anno(self.reader.read_exact_vec_at((range.end - range.start) as usize, range.start), "error reading ar::Member data")
+    }
+
+    /// A [`ReadAt`] limited to the data of a [`ar::Member`].
+    pub fn member_reader(&self, member: &ar::Member) -> ar::MemberReader<'_, R> {
+        ar::MemberReader { reader: &self.reader, range: member.data_range() }
+    }
+
+    /// Parse a [`ar::Member`] as a COFF object.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the member isn't a COFF object (e.g. a short import object)
+    /// *   [`io::Error`] forwarded from [`coff::Reader::read`]
+    pub fn read_coff(&self, member: &ar::Member) -> io::Result<coff::Reader<ar::MemberReader<'_, R>>> {
+        coff::Reader::read_src(self.member_reader(member), Src::ArMember(Box::new(self.src.clone()), member.name.clone()))
+    }
+
+    /// Parse a [`ar::Member`] of an import library as a short import object.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the member isn't a short import object (e.g. a COFF object)
+    /// *   [`io::Error`] forwarded from [`ar::Reader::read_member_data`]
+    pub fn read_import_object(&self, member: &ar::Member) -> io::Result<coff::ImportObject> {
+        let data = self.read_member_data(member)?;
+        let src = Src::ArMember(Box::new(self.src.clone()), member.name.clone());
+        src.anno(coff::ImportObject::parse(&data[..]), "error parsing coff::ImportObject")
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
+        let mut signature = [0u8; 8];
+        src.anno(reader.read_exact_at(&mut signature[..], 0), "error reading ar::SIGNATURE")?;
+        if &signature != ar::SIGNATURE {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "ar::SIGNATURE != \"!<arch>\\n\"")), "error reading ar::SIGNATURE");
+        }
+
+        let mut first_linker_member     = None;
+        let mut second_linker_member    = None;
+        let mut long_names              = None;
+        let mut members                 = Vec::new();
+
+        let mut offset = ar::SIGNATURE.len() as u64;
+        loop {
+            let mut raw = [0u8; ar::MemberHeader::SIZE];
+            match src.anno(reader.read_up_to_at(&mut raw[..], offset), "error reading ar::MemberHeader")? {
+                0 => break,
+                ar::MemberHeader::SIZE => {},
+                _ => return src.anno(Err(io::ErrorKind::UnexpectedEof.into()), "error reading ar::MemberHeader"),
+            }
+            let header = src.anno(ar::MemberHeader::from_record(&raw), "error reading ar::MemberHeader")?;
+            let member_offset = src.anno(u32::try_from(offset).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "ar::Member offset exceeds 4 GiB")), "error reading ar::MemberHeader")?;
+            let member = ar::Member { offset: member_offset, name: String::new(), header };
+            offset = member.data_range().end;
+            offset += offset & 1; // members are 2-byte aligned
+
+            if member.header.name == "/" && first_linker_member.is_none() && members.is_empty() {
+                let data = Self::read_special(&reader, &src, &member)?;
+                first_linker_member = Some(src.anno(ar::FirstLinkerMember::parse(&data[..]), "error parsing ar::FirstLinkerMember")?);
+            } else if member.header.name == "/" && second_linker_member.is_none() && members.is_empty() {
+                let data = Self::read_special(&reader, &src, &member)?;
+                second_linker_member = Some(src.anno(ar::SecondLinkerMember::parse(&data[..]), "error parsing ar::SecondLinkerMember")?);
+            } else if member.header.name == "//" {
+                long_names = Some(ar::LongNames::from_bytes(Self::read_special(&reader, &src, &member)?));
+            } else {
+                let name = match member.header.name.strip_prefix('/').filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) {
+                    Some(n) => {
+                        let name = n.parse().ok().and_then(|n| long_names.as_ref()?.get(n));
+                        let name = src.anno(name.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("ar::MemberHeader::name {:?} not found in ar::LongNames", member.header.name))), "error reading ar::Member name")?;
+                        String::from_utf8_lossy(name).into_owned()
+                    },
+                    None => member.header.name.strip_suffix('/').unwrap_or(&member.header.name).into(),
+                };
+                members.push(ar::Member { name, ..member });
+            }
+        }
+
+        Ok(Self { src, reader, first_linker_member, second_linker_member, long_names, members })
+    }
+
+    fn read_special(reader: &R, src: &Src, member: &ar::Member) -> io::Result<Vec<u8>> {
+        src.anno(reader.read_exact_vec_at(member.header.size as usize, member.data_range().start), "error reading ar::Member data") // size is untrusted: don't preallocate
+    }
+}
+
+impl<R> Reader<R> {
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn first_linker_member(&self) -> Option<&ar::FirstLinkerMember> { self.first_linker_member.as_ref() }
+    #[allow(missing_docs)] pub fn second_linker_member(&self) -> Option<&ar::SecondLinkerMember> { self.second_linker_member.as_ref() }
+    #[allow(missing_docs)] pub fn long_names(&self) -> Option<&ar::LongNames> { self.long_names.as_ref() }
+    #[allow(missing_docs)] pub fn members(&self) -> &[ar::Member] { &self.members[..] }
+
+    /// Find the [`ar::Member`] whose header is at `offset`, as referenced by [`ar::LinkerSymbol::member_offset`]
+    pub fn member_at(&self, offset: u32) -> Option<&ar::Member> {
+        self.members.binary_search_by_key(&offset, |m| m.offset).ok().map(|i| &self.members[i])
+    }
+
+    /// Find the [`ar::Member`] defining the public symbol `name`, via the linker members
+    pub fn find_symbol(&self, name: &str) -> Option<&ar::Member> {
+        let symbol = match (&self.second_linker_member, &self.first_linker_member) {
+            (Some(second), _)       => second.find(name),
+            (None, Some(first))     => first.symbols.iter().find(|s| s.name == name),
+            (None, None)            => None,
+        }?;
+        self.member_at(symbol.member_offset)
+    }
+}
+
+
+
+/// impl [`ReadAt`] for the data of a single [`ar::Member`], as returned by [`ar::Reader::member_reader`]<br>
+/// <br>
+pub struct MemberReader<'r, R> {
+    reader: &'r R,
+    range:  std::ops::Range<u64>,
+}
+
+impl<'r, R> Clone for MemberReader<'r, R> { // always cloneable even if R isn't
+    fn clone(&self) -> Self {
+        Self { reader: self.reader, range: self.range.clone() }
+    }
+}
+
+impl<'r, R: ReadAt> ReadAt for MemberReader<'r, R> {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let remaining = (self.range.end - self.range.start).saturating_sub(offset);
+        let n = buf.len().min(usize::try_from(remaining).unwrap_or(!0));
+        if n == 0 { return Ok(0) }
+        self.reader.read_at(&mut buf[..n], self.range.start + offset)
+    }
+}
+
+
+
+#[test] fn read_truncated() {
+    let mut lib = ar::Writer::new();
+    lib.add_member("foo.obj", vec![0; 4], vec!["_foo".into()]);
+    let mut data = Vec::new();
+    lib.write(&mut data).unwrap();
+    assert_eq!(Reader::read(MemoryReadAt(data.clone())).unwrap().members().len(), 1);
+
+    data[8 + 48 .. 8 + 58].copy_from_slice(b"4000000000"); // first linker member size
+    assert_eq!(Reader::read(MemoryReadAt(data)).map(|_| ()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
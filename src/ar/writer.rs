@@ -0,0 +1,120 @@
+use crate::*;
+
+use std::convert::*;
+use std::io::{self, Write};
+
+
+
+/// Builds an MSVC-style archive (`.lib`): both linker members, a longnames member, and every added member<br>
+/// <br>
+///
+/// Output is deterministic: [`ar::MemberHeader::date`]s are written as 0.
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_format_exe::*;
+/// let mut lib = ar::Writer::new();
+/// lib.add_member("foo.obj", vec![/* COFF object */], vec!["_foo".into()]);
+/// let mut data = Vec::new();
+/// lib.write(&mut data).unwrap();
+/// assert!(data.starts_with(ar::SIGNATURE));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Writer {
+    members: Vec<(String, Vec<u8>, Vec<String>)>,
+}
+
+impl Writer {
+    /// Create an empty archive.
+    pub fn new() -> Self { Self::default() }
+
+    /// Add a member named `name` (without any `/` terminator) containing `data`, defining the public `symbols` listed by the linker members.
+    pub fn add_member(&mut self, name: impl Into<String>, data: Vec<u8>, symbols: Vec<String>) -> &mut Self {
+        self.members.push((name.into(), data, symbols));
+        self
+    }
+
+    /// Write the archive.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the archive would exceed 4 GiB, or has more than [`u16::MAX`] members
+    /// *   [`io::Error`] forwarded from [`Write::write_all`]
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "ar::Writer: archive exceeds 4 GiB or u16::MAX members");
+        if self.members.len() > usize::from(u16::MAX) { return Err(too_big()) }
+
+        let mut long_names = Vec::new();
+        let header_names = self.members.iter().map(|(name, _, _)| {
+            if name.len() < 16 {
+                format!("{}/", name)
+            } else {
+                let offset = long_names.len();
+                long_names.extend_from_slice(name.as_bytes());
+                long_names.push(0);
+                format!("/{}", offset)
+            }
+        }).collect::<Vec<_>>();
+
+        let symbols = self.members.iter().enumerate().flat_map(|(i, (_, _, symbols))| symbols.iter().map(move |s| (i, s))).collect::<Vec<_>>();
+        let names_size = symbols.iter().map(|(_, s)| s.len() + 1).sum::<usize>();
+        let first_size  = 4 + 4 * symbols.len() + names_size;
+        let second_size = 4 + 4 * self.members.len() + 4 + 2 * symbols.len() + names_size;
+
+        let mut offset = ar::SIGNATURE.len() + member_size(first_size) + member_size(second_size) + member_size(long_names.len());
+        let mut member_offsets = Vec::new();
+        for (_, data, _) in self.members.iter() {
+            member_offsets.push(u32::try_from(offset).map_err(|_| too_big())?);
+            offset += member_size(data.len());
+        }
+        u32::try_from(offset).map_err(|_| too_big())?;
+
+        let mut first = Vec::with_capacity(first_size);
+        first.extend_from_slice(&(symbols.len() as u32).to_be_bytes());
+        for (i, _) in symbols.iter() { first.extend_from_slice(&member_offsets[*i].to_be_bytes()) }
+        for (_, s) in symbols.iter() { first.extend_from_slice(s.as_bytes()); first.push(0) }
+
+        let mut sorted = symbols.clone();
+        sorted.sort_by(|a, b| a.1.as_bytes().cmp(b.1.as_bytes()));
+        let mut second = Vec::with_capacity(second_size);
+        second.extend_from_slice(&(self.members.len() as u32).to_le_bytes());
+        for o in member_offsets.iter() { second.extend_from_slice(&o.to_le_bytes()) }
+        second.extend_from_slice(&(sorted.len() as u32).to_le_bytes());
+        for (i, _) in sorted.iter() { second.extend_from_slice(&u16::try_from(*i + 1).map_err(|_| too_big())?.to_le_bytes()) } // 1-based
+        for (_, s) in sorted.iter() { second.extend_from_slice(s.as_bytes()); second.push(0) }
+
+        w.write_all(ar::SIGNATURE)?;
+        write_member(w, "/", &first[..])?;
+        write_member(w, "/", &second[..])?;
+        write_member(w, "//", &long_names[..])?;
+        for (name, (_, data, _)) in header_names.iter().zip(self.members.iter()) {
+            write_member(w, name, &data[..])?;
+        }
+        Ok(())
+    }
+}
+
+fn member_size(data: usize) -> usize { ar::MemberHeader::SIZE + data + (data & 1) }
+
+fn write_member(w: &mut impl Write, name: &str, data: &[u8]) -> io::Result<()> {
+    let mode = if name.starts_with('/') && name.len() <= 2 { "0" } else { "644" };
+    writeln!(w, "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`", name, 0, 0, 0, mode, data.len())?;
+    w.write_all(data)?;
+    if data.len() & 1 != 0 { w.write_all(b"\n")? }
+    Ok(())
+}
+
+
+
+#[test] fn member_limit() {
+    let mut lib = Writer::new();
+    for i in 0 .. u16::MAX - 1 { lib.add_member(format!("{}.obj", i), Vec::new(), Vec::new()); }
+    lib.add_member("last.obj", Vec::new(), vec!["_last".into()]);
+    let mut data = Vec::new();
+    lib.write(&mut data).unwrap(); // u16::MAX members: the last symbol's 1-based index is exactly u16::MAX
+    let second = 8 + 60 + 4 + 4 + 5 + 1 + 60; // signature, first linker member (1 offset, "_last\0"), second linker member header
+    let indices = second + 4 + 4 * usize::from(u16::MAX) + 4;
+    assert_eq!(data[indices .. indices + 2], u16::MAX.to_le_bytes());
+
+    lib.add_member("overflow.obj", Vec::new(), vec!["_overflow".into()]);
+    assert_eq!(lib.write(&mut Vec::new()).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
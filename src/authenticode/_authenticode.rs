@@ -0,0 +1,28 @@
+//! Decoding of [Authenticode](https://learn.microsoft.com/en-us/windows-hardware/drivers/install/authenticode) signatures: "who signed this and when"
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Signature`]         | A PKCS#7 `SignedData` blob from the [`pe`] certificate table: image digest, certificates, signers, nested signatures.
+//! | [`SignerInfo`]        | Who signed (by issuer + serial number), program name, and [`Timestamp`]s.
+//! | [`Certificate`]       | The subject, issuer, serial number, and validity period of an embedded X.509 certificate.
+//! | [`Timestamp`]         | A legacy countersignature or RFC 3161 timestamp token.
+//! | [`Name`]              | An X.509 distinguished name such as `"CN=Contoso Ltd, O=Contoso Ltd, C=US"`.
+//! | [`ObjectIdentifier`]  | An ASN.1 OID such as `1.2.840.113549.1.7.2`.
+//!
+//! Signatures are *decoded*, not *verified* - no cryptography is performed.
+//!
+//! ## References
+//! *   [Windows Authenticode Portable Executable Signature Format](https://download.microsoft.com/download/9/c/5/9c5b2167-8017-4bae-9fde-d599bac8184a/Authenticode_PE.docx)
+//! *   [RFC 2315: PKCS #7](https://www.rfc-editor.org/rfc/rfc2315)
+//! *   [RFC 3161: Time-Stamp Protocol](https://www.rfc-editor.org/rfc/rfc3161)
+//! *   [RFC 5280: X.509 Certificates](https://www.rfc-editor.org/rfc/rfc5280)
+
+#[cfg(doc)] use crate::*;
+
+pub(crate) mod der;
+mod certificate;                    pub use certificate::*;
+mod name;                           pub use name::*;
+mod oid;                            pub use oid::*;
+mod signature;                      pub use signature::*;
+mod signer_info;                    pub use signer_info::*;
+mod time;                           pub use time::*;
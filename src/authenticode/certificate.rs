@@ -0,0 +1,82 @@
+use super::*;
+use super::der::*;
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+
+
+
+/// ⨯ {
+///     [serial_number](Self::serial_number),
+///     [issuer](Self::issuer),
+///     [subject](Self::subject),
+///     [not_before](Self::not_before),
+///     [not_after](Self::not_after)
+/// }<br>
+/// The identifying parts of an X.509 certificate embedded in a [`Signature`].<br>
+/// <br>
+///
+/// Public keys, extensions, and the certificate's own signature are not decoded - this crate does not *verify* anything.
+///
+/// ## References
+/// *   [RFC 5280 §4.1](https://www.rfc-editor.org/rfc/rfc5280#section-4.1)
+#[derive(Clone, Debug, Default)]
+pub struct Certificate {
+    pub serial_number:  SerialNumber,
+    pub issuer:         Name,
+    pub subject:        Name,
+    pub not_before:     Time,
+    pub not_after:      Time,
+}
+
+/// ≈ [`Vec<u8>`] - A big-endian certificate serial number, unique per [`Certificate::issuer`]<br>
+/// <br>
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct SerialNumber(pub Vec<u8>);
+
+impl Certificate {
+    /// `true` if this certificate was issued by `issuer` with serial number `serial_number` (how [`SignerInfo`]s reference their certificate)
+    pub fn is(&self, issuer: &Name, serial_number: &SerialNumber) -> bool {
+        self.issuer == *issuer && self.serial_number == *serial_number
+    }
+
+    /// `true` if this certificate's issuer is its own subject
+    pub fn is_self_issued(&self) -> bool {
+        self.issuer == self.subject
+    }
+
+    pub(crate) fn from_der(der: &mut Der) -> io::Result<Self> {
+        let mut cert = der.read_sequence("Certificate")?;
+        let mut tbs = cert.read_sequence("TBSCertificate")?;
+        let _version        = tbs.read_optional(context(0))?;
+        let serial_number   = SerialNumber::from_der(&mut tbs)?;
+        let _signature      = tbs.read_algorithm("TBSCertificate.signature")?;
+        let issuer          = Name::from_der(&mut tbs)?;
+        let mut validity    = tbs.read_sequence("Validity")?;
+        let not_before      = Time::from_tlv(validity.read_any()?)?;
+        let not_after       = Time::from_tlv(validity.read_any()?)?;
+        let subject         = Name::from_der(&mut tbs)?;
+        Ok(Self { serial_number, issuer, subject, not_before, not_after })
+    }
+}
+
+impl SerialNumber {
+    pub(crate) fn from_der(der: &mut Der) -> io::Result<Self> {
+        let n = der.read_integer("CertificateSerialNumber")?;
+        Ok(Self(n.to_vec()))
+    }
+}
+
+impl Display for SerialNumber {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        // DER may add a leading 0x00 to keep the INTEGER positive - skip it like signtool/certutil do
+        let n = match &self.0[..] { [0, rest @ ..] if !rest.is_empty() => rest, n => n };
+        write!(fmt, "{}", name::hex(n))
+    }
+}
+
+impl Debug for SerialNumber {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "SerialNumber({})", self)
+    }
+}
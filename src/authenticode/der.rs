@@ -0,0 +1,181 @@
+//! Minimal [DER](https://en.wikipedia.org/wiki/X.690#DER_encoding) reader - just enough to walk PKCS#7 and X.509 structures.
+//!
+//! Only low-tag-number identifiers are supported.  BER indefinite lengths are tolerated, as some older signing tools emit them.
+
+use super::*;
+
+use std::io;
+
+
+
+pub(crate) const INTEGER            : u8 = 0x02;
+pub(crate) const OCTET_STRING       : u8 = 0x04;
+pub(crate) const OBJECT_IDENTIFIER  : u8 = 0x06;
+pub(crate) const UTF8_STRING        : u8 = 0x0C;
+pub(crate) const PRINTABLE_STRING   : u8 = 0x13;
+pub(crate) const T61_STRING         : u8 = 0x14;
+pub(crate) const IA5_STRING         : u8 = 0x16;
+pub(crate) const UTC_TIME           : u8 = 0x17;
+pub(crate) const GENERALIZED_TIME   : u8 = 0x18;
+pub(crate) const UNIVERSAL_STRING   : u8 = 0x1C;
+pub(crate) const BMP_STRING         : u8 = 0x1E;
+pub(crate) const SEQUENCE           : u8 = 0x30;
+pub(crate) const SET                : u8 = 0x31;
+
+/// `[n] IMPLICIT` / `[n] EXPLICIT` constructed context-specific tag
+pub(crate) const fn context(n: u8) -> u8 { 0xA0 | n }
+
+/// `[n] IMPLICIT` primitive context-specific tag
+pub(crate) const fn context_primitive(n: u8) -> u8 { 0x80 | n }
+
+pub(crate) fn invalid(what: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, what.into())
+}
+
+
+
+/// A single decoded **t**ag / **l**ength / **v**alue
+#[derive(Clone, Copy)]
+pub(crate) struct Tlv<'a> {
+    pub tag:    u8,
+    pub value:  &'a [u8],
+    /// The entire encoding, including the tag and length
+    pub raw:    &'a [u8],
+}
+
+/// A cursor over a run of DER-encoded values (e.g. the contents of a `SEQUENCE`)
+#[derive(Clone, Copy)]
+pub(crate) struct Der<'a>(&'a [u8]);
+
+impl<'a> Der<'a> {
+    pub fn new(data: &'a [u8]) -> Self { Self(data) }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    pub fn peek_tag(&self) -> Option<u8> { self.0.first().copied() }
+
+    pub fn read_any(&mut self) -> io::Result<Tlv<'a>> {
+        let (tlv, rest) = split_tlv(self.0, 0)?;
+        self.0 = rest;
+        Ok(tlv)
+    }
+
+    pub fn read(&mut self, tag: u8, what: &str) -> io::Result<&'a [u8]> {
+        match self.peek_tag() {
+            Some(t) if t == tag => Ok(self.read_any()?.value),
+            Some(t) => Err(invalid(format!("authenticode: expected {} (tag 0x{:02x}), found tag 0x{:02x}", what, tag, t))),
+            None    => Err(invalid(format!("authenticode: expected {} (tag 0x{:02x}), found end of data", what, tag))),
+        }
+    }
+
+    pub fn read_optional(&mut self, tag: u8) -> io::Result<Option<&'a [u8]>> {
+        if self.peek_tag() == Some(tag) {
+            Ok(Some(self.read_any()?.value))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn read_sequence(&mut self, what: &str) -> io::Result<Der<'a>> { Ok(Der(self.read(SEQUENCE, what)?)) }
+    pub fn read_set     (&mut self, what: &str) -> io::Result<Der<'a>> { Ok(Der(self.read(SET,      what)?)) }
+    pub fn read_integer (&mut self, what: &str) -> io::Result<&'a [u8]> { self.read(INTEGER, what) }
+    pub fn read_octets  (&mut self, what: &str) -> io::Result<&'a [u8]> { self.read(OCTET_STRING, what) }
+
+    pub fn read_oid(&mut self, what: &str) -> io::Result<ObjectIdentifier> {
+        ObjectIdentifier::from_der_value(self.read(OBJECT_IDENTIFIER, what)?)
+    }
+
+    /// Read an `AlgorithmIdentifier ::= SEQUENCE { algorithm OBJECT IDENTIFIER, parameters ANY OPTIONAL }`, discarding the parameters
+    pub fn read_algorithm(&mut self, what: &str) -> io::Result<ObjectIdentifier> {
+        self.read_sequence(what)?.read_oid(what)
+    }
+
+    /// Iterate over the remaining values
+    pub fn iter(self) -> impl Iterator<Item = io::Result<Tlv<'a>>> {
+        let mut der = self;
+        std::iter::from_fn(move || if der.is_empty() { None } else {
+            let r = der.read_any();
+            if r.is_err() { der = Der(&[]) }
+            Some(r)
+        })
+    }
+}
+
+/// How deeply BER indefinite-length values may nest before being rejected, bounding [`split_tlv`]'s recursion
+const MAX_INDEFINITE_DEPTH : usize = 64;
+
+fn split_tlv(data: &[u8], depth: usize) -> io::Result<(Tlv<'_>, &[u8])> {
+    let eof = || invalid("authenticode: unexpected end of DER data");
+    let tag = *data.first().ok_or_else(eof)?;
+    if tag & 0x1F == 0x1F { return Err(invalid("authenticode: high-tag-number DER identifiers are not supported")) }
+    let len0 = *data.get(1).ok_or_else(eof)?;
+
+    let (header, len) = match len0 {
+        0x00 ..= 0x7F => (2, usize::from(len0)),
+        0x80 => { // BER indefinite length: contents run until an end-of-contents (00 00) marker
+            if tag & 0x20 == 0 { return Err(invalid("authenticode: indefinite length on primitive DER value")) }
+            if depth >= MAX_INDEFINITE_DEPTH { return Err(invalid("authenticode: indefinite length values nested too deeply")) }
+            let mut rest = &data[2..];
+            loop {
+                if rest.starts_with(&[0, 0]) { break }
+                rest = split_tlv(rest, depth + 1)?.1;
+            }
+            let len = data.len() - 2 - rest.len();
+            let value = &data[2..2+len];
+            let raw = &data[..2+len+2];
+            return Ok((Tlv { tag, value, raw }, &rest[2..]));
+        },
+        0x81 ..= 0x84 => {
+            let n = usize::from(len0 & 0x7F);
+            let bytes = data.get(2..2+n).ok_or_else(eof)?;
+            (2+n, bytes.iter().fold(0usize, |len, b| (len << 8) | usize::from(*b)))
+        },
+        _ => return Err(invalid("authenticode: DER length too large")),
+    };
+
+    let end = header.checked_add(len).filter(|end| *end <= data.len()).ok_or_else(eof)?;
+    Ok((Tlv { tag, value: &data[header..end], raw: &data[..end] }, &data[end..]))
+}
+
+/// Decode the various `DirectoryString` / `Time` adjacent string types into a [`String`]
+pub(crate) fn decode_string(tlv: Tlv) -> io::Result<String> {
+    match tlv.tag {
+        UTF8_STRING | PRINTABLE_STRING | IA5_STRING => Ok(String::from_utf8_lossy(tlv.value).into_owned()),
+        T61_STRING  => Ok(tlv.value.iter().map(|b| char::from(*b)).collect()), // close enough: treat as latin-1
+        BMP_STRING  => {
+            let u16s = tlv.value.chunks(2).map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]));
+            Ok(std::char::decode_utf16(u16s).map(|c| c.unwrap_or(std::char::REPLACEMENT_CHARACTER)).collect())
+        },
+        UNIVERSAL_STRING => {
+            Ok(tlv.value.chunks(4).map(|c| {
+                let mut b = [0u8; 4];
+                b[..c.len()].copy_from_slice(c);
+                std::char::from_u32(u32::from_be_bytes(b)).unwrap_or(std::char::REPLACEMENT_CHARACTER)
+            }).collect())
+        },
+        other => Err(invalid(format!("authenticode: expected a string, found tag 0x{:02x}", other))),
+    }
+}
+
+
+
+#[test] fn lengths() {
+    let mut der = Der::new(&[0x04, 0x02, 0xAA, 0xBB, 0x30, 0x81, 0x01, 0x05, 0x30, 0x80, 0x05, 0x00, 0x00, 0x00]);
+    assert_eq!(der.read_octets("short").unwrap(), &[0xAA, 0xBB]);
+    assert_eq!(der.read_sequence("long").unwrap().0, &[0x05]);
+    let mut indefinite = der.read_sequence("indefinite").unwrap();
+    assert!(der.is_empty());
+    assert_eq!(indefinite.read(0x05, "NULL").unwrap(), &[]);
+    assert!(indefinite.is_empty());
+
+    assert!(Der::new(&[0x04, 0x03, 0xAA]).read_any().is_err());
+    assert!(Der::new(&[0x04]).read_any().is_err());
+}
+
+#[test] fn indefinite_depth() {
+    let nested = |depth: usize| [&[0x30, 0x80][..]].repeat(depth).concat().into_iter().chain([0, 0].repeat(depth)).collect::<Vec<u8>>();
+    assert!(Der::new(&nested(MAX_INDEFINITE_DEPTH)).read_any().is_ok());
+    assert_eq!(Der::new(&nested(MAX_INDEFINITE_DEPTH + 1)).read_any().map(|_| ()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(Der::new(&[0x30, 0x80].repeat(500_000)).read_any().map(|_| ()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert_eq!(crate::authenticode::Signature::from_der(&[0x30, 0x80].repeat(500_000)).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
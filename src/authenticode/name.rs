@@ -0,0 +1,68 @@
+use super::*;
+use super::der::*;
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+
+
+
+/// ≈ `"CN=Contoso Ltd, O=Contoso Ltd, L=Redmond, C=US"`<br>
+/// An X.509 distinguished name, as used for certificate subjects and issuers.<br>
+/// <br>
+///
+/// Attributes are kept in encoded order (most significant first, e.g. `C` before `CN`), flattening multi-valued RDNs.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Name {
+    pub attributes: Vec<(ObjectIdentifier, String)>,
+}
+
+impl Name {
+    /// The first value of an attribute by [`ObjectIdentifier::name`] (e.g. `"CN"`, `"O"`)
+    pub fn get(&self, attribute: &str) -> Option<&str> {
+        self.attributes.iter().find(|(oid, _)| oid.name() == Some(attribute)).map(|(_, v)| &v[..])
+    }
+
+    /// The `CN` attribute, typically the publisher or certificate authority name
+    pub fn common_name(&self) -> Option<&str> { self.get("CN") }
+
+    pub(crate) fn from_der(der: &mut Der) -> io::Result<Self> {
+        let mut attributes = Vec::new();
+        for rdn in der.read_sequence("Name")?.iter() {
+            let rdn = rdn?;
+            if rdn.tag != SET { return Err(invalid("authenticode: expected RelativeDistinguishedName SET")) }
+            for atv in Der::new(rdn.value).iter() {
+                let atv = atv?;
+                if atv.tag != SEQUENCE { return Err(invalid("authenticode: expected AttributeTypeAndValue SEQUENCE")) }
+                let mut atv = Der::new(atv.value);
+                let oid = atv.read_oid("AttributeType")?;
+                let value = atv.read_any()?;
+                let value = decode_string(value).unwrap_or_else(|_| format!("#{}", hex(value.raw)));
+                attributes.push((oid, value));
+            }
+        }
+        Ok(Self { attributes })
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        for (i, (oid, value)) in self.attributes.iter().enumerate() {
+            if i != 0 { write!(fmt, ", ")?; }
+            match oid.name() {
+                Some(name)  => write!(fmt, "{}={}", name, value)?,
+                None        => write!(fmt, "{}={}", oid, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Name {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "Name({:?})", self.to_string())
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
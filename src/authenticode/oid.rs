@@ -0,0 +1,143 @@
+use super::der::invalid;
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+
+
+
+/// ≈ `1.2.840.113549.1.7.2`<br>
+/// An [ASN.1 object identifier](https://en.wikipedia.org/wiki/Object_identifier), identifying algorithms, attributes, content types, etc.<br>
+/// <br>
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectIdentifier(Vec<u64>);
+
+impl ObjectIdentifier {
+    /// The individual arcs of this identifier (e.g. `[1, 2, 840, 113549, 1, 7, 2]`)
+    pub fn arcs(&self) -> &[u64] { &self.0[..] }
+
+    /// A short, human readable name for well known identifiers (e.g. `"sha256"`, `"CN"`)
+    pub fn name(&self) -> Option<&'static str> {
+        KNOWN.iter().find(|(arcs, _)| *arcs == self.arcs()).map(|(_, name)| *name)
+    }
+
+    pub(crate) fn from_der_value(value: &[u8]) -> io::Result<Self> {
+        let mut arcs = Vec::new();
+        let mut arc = 0u64;
+        for (i, b) in value.iter().copied().enumerate() {
+            if arc > (u64::MAX >> 7) { return Err(invalid("authenticode: object identifier arc too large")) }
+            arc = (arc << 7) | u64::from(b & 0x7F);
+            if b & 0x80 != 0 {
+                if i+1 == value.len() { return Err(invalid("authenticode: truncated object identifier")) }
+                continue;
+            }
+            if arcs.is_empty() {
+                let first = (arc / 40).min(2);
+                arcs.push(first);
+                arcs.push(arc - 40 * first);
+            } else {
+                arcs.push(arc);
+            }
+            arc = 0;
+        }
+        if arcs.is_empty() { return Err(invalid("authenticode: empty object identifier")) }
+        Ok(Self(arcs))
+    }
+}
+
+impl PartialEq<[u64]> for ObjectIdentifier {
+    fn eq(&self, other: &[u64]) -> bool { self.arcs() == other }
+}
+
+impl Display for ObjectIdentifier {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        for (i, arc) in self.0.iter().enumerate() {
+            if i != 0 { write!(fmt, ".")?; }
+            write!(fmt, "{}", arc)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for ObjectIdentifier {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name)  => write!(fmt, "{} ({})", name, self),
+            None        => write!(fmt, "{}", self),
+        }
+    }
+}
+
+
+
+pub(crate) const SIGNED_DATA                : &[u64] = &[1, 2, 840, 113549, 1, 7, 2];
+pub(crate) const MESSAGE_DIGEST             : &[u64] = &[1, 2, 840, 113549, 1, 9, 4];
+pub(crate) const SIGNING_TIME               : &[u64] = &[1, 2, 840, 113549, 1, 9, 5];
+pub(crate) const COUNTERSIGNATURE           : &[u64] = &[1, 2, 840, 113549, 1, 9, 6];
+pub(crate) const TST_INFO                   : &[u64] = &[1, 2, 840, 113549, 1, 9, 16, 1, 4];
+pub(crate) const SPC_INDIRECT_DATA          : &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 4];
+pub(crate) const SPC_SP_OPUS_INFO           : &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 1, 12];
+pub(crate) const SPC_NESTED_SIGNATURE       : &[u64] = &[1, 3, 6, 1, 4, 1, 311, 2, 4, 1];
+pub(crate) const MS_RFC3161_TIMESTAMP       : &[u64] = &[1, 3, 6, 1, 4, 1, 311, 3, 3, 1];
+
+const KNOWN : &[(&[u64], &str)] = &[
+    // digest algorithms
+    (&[1, 2, 840, 113549, 2, 5],                "md5"),
+    (&[1, 3, 14, 3, 2, 26],                     "sha1"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 1],         "sha256"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 2],         "sha384"),
+    (&[2, 16, 840, 1, 101, 3, 4, 2, 3],         "sha512"),
+
+    // signature algorithms
+    (&[1, 2, 840, 113549, 1, 1, 1],             "rsaEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 4],             "md5WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 5],             "sha1WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 11],            "sha256WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 12],            "sha384WithRSAEncryption"),
+    (&[1, 2, 840, 113549, 1, 1, 13],            "sha512WithRSAEncryption"),
+    (&[1, 2, 840, 10045, 2, 1],                 "ecPublicKey"),
+    (&[1, 2, 840, 10045, 4, 3, 2],              "ecdsa-with-SHA256"),
+    (&[1, 2, 840, 10045, 4, 3, 3],              "ecdsa-with-SHA384"),
+
+    // name attributes
+    (&[2, 5, 4, 3],                             "CN"),
+    (&[2, 5, 4, 5],                             "serialNumber"),
+    (&[2, 5, 4, 6],                             "C"),
+    (&[2, 5, 4, 7],                             "L"),
+    (&[2, 5, 4, 8],                             "ST"),
+    (&[2, 5, 4, 9],                             "street"),
+    (&[2, 5, 4, 10],                            "O"),
+    (&[2, 5, 4, 11],                            "OU"),
+    (&[2, 5, 4, 15],                            "businessCategory"),
+    (&[2, 5, 4, 17],                            "postalCode"),
+    (&[1, 2, 840, 113549, 1, 9, 1],             "emailAddress"),
+    (&[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 1],     "jurisdictionL"),
+    (&[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 2],     "jurisdictionST"),
+    (&[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 3],     "jurisdictionC"),
+
+    // content types / attributes
+    (SIGNED_DATA,                               "signedData"),
+    (MESSAGE_DIGEST,                            "messageDigest"),
+    (SIGNING_TIME,                              "signingTime"),
+    (COUNTERSIGNATURE,                          "countersignature"),
+    (TST_INFO,                                  "id-ct-TSTInfo"),
+    (SPC_INDIRECT_DATA,                         "SPC_INDIRECT_DATA_OBJID"),
+    (&[1, 3, 6, 1, 4, 1, 311, 2, 1, 15],        "SPC_PE_IMAGE_DATA_OBJID"),
+    (SPC_SP_OPUS_INFO,                          "SPC_SP_OPUS_INFO_OBJID"),
+    (SPC_NESTED_SIGNATURE,                      "SPC_NESTED_SIGNATURE_OBJID"),
+    (MS_RFC3161_TIMESTAMP,                      "SPC_RFC3161_OBJID"),
+];
+
+
+
+#[test] fn display() {
+    let oid = ObjectIdentifier::from_der_value(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02]).unwrap();
+    assert_eq!(oid.to_string(), "1.2.840.113549.1.7.2");
+    assert_eq!(oid.name(), Some("signedData"));
+    assert!(oid == *SIGNED_DATA);
+
+    let oid = ObjectIdentifier::from_der_value(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]).unwrap();
+    assert_eq!(format!("{:?}", oid), "sha256 (2.16.840.1.101.3.4.2.1)");
+
+    assert!(ObjectIdentifier::from_der_value(&[]).is_err());
+    assert!(ObjectIdentifier::from_der_value(&[0x2A, 0x86]).is_err());
+}
@@ -0,0 +1,161 @@
+use super::*;
+use super::der::*;
+
+use std::io;
+
+
+
+/// ⨯ {
+///     [digest_algorithm](Self::digest_algorithm),
+///     [image_digest](Self::image_digest),
+///     [certificates](Self::certificates),
+///     [signers](Self::signers),
+///     [nested](Self::nested)
+/// }<br>
+/// A decoded Authenticode signature: a PKCS#7 `SignedData` wrapping a `SpcIndirectDataContent`.<br>
+/// <br>
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_format_exe::*;
+/// let pe = pe::Reader::open("signed.exe").unwrap();
+/// for signature in pe.authenticode_signatures().unwrap() {
+///     let signer = signature.signer_certificate().map(|c| c.subject.to_string());
+///     let when = signature.signer().and_then(|s| s.timestamps.first()).and_then(|ts| ts.time);
+///     println!("{:?} signed with {:?} @ {:?}", signer, signature.digest_algorithm, when);
+/// }
+/// ```
+///
+/// ## References
+/// *   [Windows Authenticode Portable Executable Signature Format](https://download.microsoft.com/download/9/c/5/9c5b2167-8017-4bae-9fde-d599bac8184a/Authenticode_PE.docx)
+/// *   [RFC 2315: PKCS #7: Cryptographic Message Syntax](https://www.rfc-editor.org/rfc/rfc2315)
+#[derive(Clone, Debug, Default)]
+pub struct Signature {
+    /// Algorithm used to compute [`image_digest`](Self::image_digest) (e.g. `sha256`)
+    pub digest_algorithm:   ObjectIdentifier,
+    /// The expected Authenticode hash of the image (`SpcIndirectDataContent::messageDigest`)
+    pub image_digest:       Vec<u8>,
+    /// All certificates bundled with the signature (signer, intermediates, and often legacy timestamping certificates), in encoded order
+    pub certificates:       Vec<Certificate>,
+    /// Typically exactly one [`SignerInfo`]
+    pub signers:            Vec<SignerInfo>,
+    /// Additional signatures nested via `SPC_NESTED_SIGNATURE_OBJID` (`1.3.6.1.4.1.311.2.4.1`) - e.g. a SHA-256 signature alongside a legacy SHA-1 one
+    pub nested:             Vec<Signature>,
+}
+
+impl Signature {
+    /// Decode a DER encoded PKCS#7 `ContentInfo` containing Authenticode `SignedData`
+    /// (e.g. the data following a [`pe::WinCertificate`](crate::pe::WinCertificate) of type [`PKCS_SIGNED_DATA`](crate::pe::CertificateType::PKCS_SIGNED_DATA).)
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if `data` isn't (well formed) Authenticode `SignedData`
+    pub fn from_der(data: &[u8]) -> io::Result<Self> { Self::from_der_depth(data, 0) }
+
+    /// [`from_der`](Self::from_der), `depth` [`SignerInfo`]s deep
+    pub(crate) fn from_der_depth(data: &[u8], depth: usize) -> io::Result<Self> {
+        let sd = SignedData::from_content_info(data, depth)?;
+        if sd.content_type != *SPC_INDIRECT_DATA {
+            return Err(invalid(format!("authenticode: SignedData content type is {}, expected SPC_INDIRECT_DATA_OBJID", sd.content_type)));
+        }
+
+        // SpcIndirectDataContent ::= SEQUENCE { data SpcAttributeTypeAndOptionalValue, messageDigest DigestInfo }
+        let mut content = Der::new(sd.content.unwrap_or(&[])).read_sequence("SpcIndirectDataContent")?;
+        let _data = content.read_sequence("SpcAttributeTypeAndOptionalValue")?;
+        let mut digest_info = content.read_sequence("DigestInfo")?;
+        let digest_algorithm = digest_info.read_algorithm("DigestInfo.digestAlgorithm")?;
+        let image_digest = digest_info.read_octets("DigestInfo.digest")?.to_vec();
+
+        Ok(Self {
+            digest_algorithm,
+            image_digest,
+            certificates:   sd.certificates,
+            signers:        sd.signer_infos,
+            nested:         sd.nested,
+        })
+    }
+
+    /// The first (typically only) [`SignerInfo`]
+    pub fn signer(&self) -> Option<&SignerInfo> {
+        self.signers.first()
+    }
+
+    /// The [`Certificate`] of the first [`SignerInfo`] - its [`subject`](Certificate::subject) is "who signed this"
+    pub fn signer_certificate(&self) -> Option<&Certificate> {
+        let signer = self.signer()?;
+        self.certificates.iter().find(|c| c.is(&signer.issuer, &signer.serial_number))
+    }
+
+    /// The chain of [`Certificate`]s from [`signer_certificate`](Self::signer_certificate) towards the root, as far as the bundled certificates allow
+    pub fn certificate_chain(&self) -> Vec<&Certificate> {
+        let mut chain = Vec::new();
+        let mut next = self.signer_certificate();
+        while let Some(cert) = next {
+            chain.push(cert);
+            if cert.is_self_issued() || chain.len() > self.certificates.len() { break }
+            next = self.certificates.iter().find(|c| c.subject == cert.issuer);
+        }
+        chain
+    }
+
+    /// This signature followed by all [`nested`](Self::nested) signatures, recursively
+    pub fn iter_all(&self) -> impl Iterator<Item = &Signature> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let next = stack.pop()?;
+            stack.extend(next.nested.iter().rev());
+            Some(next)
+        })
+    }
+}
+
+
+
+/// The parts of a PKCS#7 / CMS `SignedData` shared by Authenticode signatures and RFC 3161 timestamp tokens
+pub(crate) struct SignedData<'a> {
+    pub content_type:   ObjectIdentifier,
+    /// The `[0] EXPLICIT` content, still DER encoded
+    pub content:        Option<&'a [u8]>,
+    pub certificates:   Vec<Certificate>,
+    pub signer_infos:   Vec<SignerInfo>,
+    pub nested:         Vec<Signature>,
+}
+
+impl<'a> SignedData<'a> {
+    /// Decode `ContentInfo ::= SEQUENCE { contentType OBJECT IDENTIFIER, content [0] EXPLICIT SignedData }`, `depth` [`SignerInfo`]s deep
+    pub fn from_content_info(data: &'a [u8], depth: usize) -> io::Result<Self> {
+        let mut ci = Der::new(data).read_sequence("ContentInfo")?;
+        let content_type = ci.read_oid("ContentInfo.contentType")?;
+        if content_type != *SIGNED_DATA {
+            return Err(invalid(format!("authenticode: ContentInfo content type is {}, expected signedData", content_type)));
+        }
+        let mut sd = Der::new(ci.read(context(0), "ContentInfo.content")?).read_sequence("SignedData")?;
+
+        let _version            = sd.read_integer("SignedData.version")?;
+        let _digest_algorithms  = sd.read_set("SignedData.digestAlgorithms")?;
+        let mut eci             = sd.read_sequence("SignedData.contentInfo")?;
+        let content_type        = eci.read_oid("SignedData.contentInfo.contentType")?;
+        let content             = eci.read_optional(context(0))?;
+
+        let mut certificates = Vec::new();
+        if let Some(certs) = sd.read_optional(context(0))? {
+            let mut certs = Der::new(certs);
+            while !certs.is_empty() {
+                if certs.peek_tag() == Some(SEQUENCE) {
+                    certificates.push(Certificate::from_der(&mut certs)?);
+                } else {
+                    let _other_certificate_format = certs.read_any()?; // e.g. [0] extendedCertificate, [1] attributeCertificate
+                }
+            }
+        }
+        let _crls = sd.read_optional(context(1))?;
+
+        let mut signer_infos = Vec::new();
+        let mut nested = Vec::new();
+        let mut sis = sd.read_set("SignedData.signerInfos")?;
+        while !sis.is_empty() {
+            signer_infos.push(SignerInfo::from_der(&mut sis, &mut nested, depth)?);
+        }
+
+        Ok(Self { content_type, content, certificates, signer_infos, nested })
+    }
+}
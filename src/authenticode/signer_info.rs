@@ -0,0 +1,235 @@
+use super::*;
+use super::der::*;
+
+use std::io;
+
+
+
+/// ⨯ {
+///     [issuer](Self::issuer),
+///     [serial_number](Self::serial_number),
+///     [digest_algorithm](Self::digest_algorithm),
+///     [signing_time](Self::signing_time),
+///     [program_name](Self::program_name),
+///     [timestamps](Self::timestamps),
+///     ...
+/// }<br>
+/// A single PKCS#7 `SignerInfo` of a [`Signature`]: *who* signed, and (via [`timestamps`](Self::timestamps)) *when*.<br>
+/// <br>
+///
+/// The signer's [`Certificate`] is identified by [`issuer`](Self::issuer) + [`serial_number`](Self::serial_number),
+/// see [`Signature::signer_certificate`].
+///
+/// ## References
+/// *   [RFC 2315 §9.2](https://www.rfc-editor.org/rfc/rfc2315#section-9.2)
+#[derive(Clone, Debug, Default)]
+pub struct SignerInfo {
+    /// Issuer of the signing [`Certificate`]
+    pub issuer:                 Name,
+    /// Serial number of the signing [`Certificate`] (empty if identified by subject key identifier instead)
+    pub serial_number:          SerialNumber,
+    /// Digest algorithm used over the authenticated attributes (e.g. `sha256`)
+    pub digest_algorithm:       ObjectIdentifier,
+    /// Signature algorithm (e.g. `rsaEncryption`)
+    pub signature_algorithm:    ObjectIdentifier,
+    /// The *signer's* claimed signing time (`signingTime` authenticated attribute.)  Rarely present and untrusted - prefer [`timestamps`](Self::timestamps).
+    pub signing_time:           Option<Time>,
+    /// `SpcSpOpusInfo::programName` - the description shown in UAC prompts
+    pub program_name:           Option<String>,
+    /// `SpcSpOpusInfo::moreInfo` - typically the publisher's URL
+    pub more_info_url:          Option<String>,
+    /// Countersignatures and RFC 3161 timestamps proving when this was signed
+    pub timestamps:             Vec<Timestamp>,
+}
+
+/// ⨯ {
+///     [kind](Self::kind),
+///     [time](Self::time),
+///     [issuer](Self::issuer),
+///     [serial_number](Self::serial_number),
+///     [certificates](Self::certificates)
+/// }<br>
+/// A timestamp attached to a [`SignerInfo`] by a timestamping authority.<br>
+/// <br>
+#[derive(Clone, Debug)]
+pub struct Timestamp {
+    /// How the timestamp was attached: a legacy countersignature, or an RFC 3161 token
+    pub kind:                   TimestampKind,
+    /// When the timestamping authority saw the signature
+    pub time:                   Option<Time>,
+    /// Issuer of the timestamping authority's [`Certificate`]
+    pub issuer:                 Name,
+    /// Serial number of the timestamping authority's [`Certificate`]
+    pub serial_number:          SerialNumber,
+    /// Certificates embedded in an [`TimestampKind::Rfc3161`] token.
+    /// [`TimestampKind::Countersignature`]s store theirs in the outer [`Signature::certificates`] instead.
+    pub certificates:           Vec<Certificate>,
+}
+
+/// ∑ [Countersignature](Self::Countersignature) | [Rfc3161](Self::Rfc3161)<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampKind {
+    /// Legacy Authenticode timestamp: a PKCS#9 `countersignature` (`1.2.840.113549.1.9.6`) unauthenticated attribute
+    Countersignature,
+    /// [RFC 3161](https://www.rfc-editor.org/rfc/rfc3161) timestamp token (`1.3.6.1.4.1.311.3.3.1` unauthenticated attribute)
+    Rfc3161,
+}
+
+impl Timestamp {
+    /// Find the timestamping authority's [`Certificate`] in [`certificates`](Self::certificates), falling back on `signature`'s.
+    pub fn certificate<'a>(&'a self, signature: &'a Signature) -> Option<&'a Certificate> {
+        self.certificates.iter().chain(signature.certificates.iter()).find(|c| c.is(&self.issuer, &self.serial_number))
+    }
+}
+
+/// How deeply [`SignerInfo`]s may nest (via countersignatures, timestamps, and nested signatures) before being rejected
+const MAX_SIGNER_INFO_DEPTH : usize = 16;
+
+impl SignerInfo {
+    /// Parse a `SignerInfo` nested within `depth` others, appending any nested Authenticode signatures to `nested`
+    pub(crate) fn from_der(der: &mut Der, nested: &mut Vec<Signature>, depth: usize) -> io::Result<Self> {
+        if depth >= MAX_SIGNER_INFO_DEPTH { return Err(invalid("authenticode: SignerInfo nested too deeply")) }
+        let mut si = der.read_sequence("SignerInfo")?;
+        let _version = si.read_integer("SignerInfo.version")?;
+
+        let mut s = SignerInfo::default();
+        if si.peek_tag() == Some(context_primitive(0)) {
+            let _subject_key_identifier = si.read_any()?;
+        } else {
+            let mut ias = si.read_sequence("IssuerAndSerialNumber")?;
+            s.issuer        = Name::from_der(&mut ias)?;
+            s.serial_number = SerialNumber::from_der(&mut ias)?;
+        }
+        s.digest_algorithm = si.read_algorithm("SignerInfo.digestAlgorithm")?;
+
+        if let Some(authenticated) = si.read_optional(context(0))? {
+            for_each_attribute(authenticated, |oid, value| {
+                if oid == SIGNING_TIME {
+                    s.signing_time = Some(Time::from_tlv(value)?);
+                } else if oid == SPC_SP_OPUS_INFO {
+                    let mut opus = Der::new(value.raw).read_sequence("SpcSpOpusInfo")?;
+                    if let Some(name) = opus.read_optional(context(0))? {
+                        s.program_name = Some(spc_string(Der::new(name).read_any()?)?);
+                    }
+                    if let Some(link) = opus.read_optional(context(1))? {
+                        let link = Der::new(link).read_any()?;
+                        if link.tag == context_primitive(0) { // url [0] IMPLICIT IA5String
+                            s.more_info_url = Some(String::from_utf8_lossy(link.value).into_owned());
+                        }
+                    }
+                }
+                Ok(())
+            })?;
+        }
+
+        s.signature_algorithm = si.read_algorithm("SignerInfo.digestEncryptionAlgorithm")?;
+        let _encrypted_digest = si.read_octets("SignerInfo.encryptedDigest")?;
+
+        if let Some(unauthenticated) = si.read_optional(context(1))? {
+            for_each_attribute(unauthenticated, |oid, value| {
+                if oid == COUNTERSIGNATURE {
+                    let counter = SignerInfo::from_der(&mut Der::new(value.raw), &mut Vec::new(), depth + 1)?;
+                    s.timestamps.push(Timestamp {
+                        kind:           TimestampKind::Countersignature,
+                        time:           counter.signing_time,
+                        issuer:         counter.issuer,
+                        serial_number:  counter.serial_number,
+                        certificates:   Vec::new(),
+                    });
+                } else if oid == MS_RFC3161_TIMESTAMP {
+                    s.timestamps.push(rfc3161_timestamp(value.raw, depth + 1)?);
+                } else if oid == SPC_NESTED_SIGNATURE {
+                    nested.push(Signature::from_der_depth(value.raw, depth + 1)?);
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(s)
+    }
+}
+
+/// Walk `SET OF Attribute`, where `Attribute ::= SEQUENCE { type OBJECT IDENTIFIER, values SET OF ANY }`
+fn for_each_attribute<'a>(attributes: &'a [u8], mut f: impl FnMut(&ObjectIdentifier, Tlv<'a>) -> io::Result<()>) -> io::Result<()> {
+    for attribute in Der::new(attributes).iter() {
+        let attribute = attribute?;
+        if attribute.tag != SEQUENCE { return Err(invalid("authenticode: expected Attribute SEQUENCE")) }
+        let mut attribute = Der::new(attribute.value);
+        let oid = attribute.read_oid("Attribute.type")?;
+        for value in attribute.read_set("Attribute.values")?.iter() {
+            f(&oid, value?)?;
+        }
+    }
+    Ok(())
+}
+
+/// `SpcString ::= CHOICE { unicode [0] IMPLICIT BMPString, ascii [1] IMPLICIT IA5String }`
+fn spc_string(tlv: Tlv) -> io::Result<String> {
+    match tlv.tag {
+        t if t == context_primitive(0) => decode_string(Tlv { tag: BMP_STRING, ..tlv }),
+        t if t == context_primitive(1) => decode_string(Tlv { tag: IA5_STRING, ..tlv }),
+        other => Err(invalid(format!("authenticode: expected SpcString, found tag 0x{:02x}", other))),
+    }
+}
+
+fn rfc3161_timestamp(content_info: &[u8], depth: usize) -> io::Result<Timestamp> {
+    let sd = SignedData::from_content_info(content_info, depth)?;
+    if sd.content_type != *TST_INFO { return Err(invalid("authenticode: RFC 3161 timestamp does not contain TSTInfo")) }
+
+    // eContent [0] EXPLICIT OCTET STRING containing TSTInfo
+    let tst_info = Der::new(sd.content.unwrap_or(&[])).read_octets("TSTInfo eContent")?;
+    let mut tst_info = Der::new(tst_info).read_sequence("TSTInfo")?;
+    let _version            = tst_info.read_integer("TSTInfo.version")?;
+    let _policy             = tst_info.read_oid("TSTInfo.policy")?;
+    let _message_imprint    = tst_info.read_sequence("TSTInfo.messageImprint")?;
+    let _serial_number      = tst_info.read_integer("TSTInfo.serialNumber")?;
+    let time                = Time::from_tlv(tst_info.read_any()?)?;
+
+    let signer = sd.signer_infos.into_iter().next().unwrap_or_default();
+    Ok(Timestamp {
+        kind:           TimestampKind::Rfc3161,
+        time:           Some(time),
+        issuer:         signer.issuer,
+        serial_number:  signer.serial_number,
+        certificates:   sd.certificates,
+    })
+}
+
+
+
+#[cfg(test)] fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let len = content.len();
+    let mut out = vec![tag];
+    if len < 0x80 { out.push(len as u8) } else { out.extend_from_slice(&[0x84, (len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8]) }
+    out.extend_from_slice(content);
+    out
+}
+
+/// A minimal `SignerInfo`, optionally countersigned by `counter`
+#[cfg(test)] fn signer_info(counter: Option<&[u8]>) -> Vec<u8> {
+    let sha256 = tlv(SEQUENCE, &tlv(OBJECT_IDENTIFIER, &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01]));
+    let mut si = tlv(INTEGER, &[3]);
+    si.extend(tlv(context_primitive(0), &[0xAA]));                   // subjectKeyIdentifier
+    si.extend_from_slice(&sha256);                                  // digestAlgorithm
+    si.extend_from_slice(&sha256);                                  // digestEncryptionAlgorithm (close enough)
+    si.extend(tlv(OCTET_STRING, &[0xBB]));                          // encryptedDigest
+    if let Some(counter) = counter {
+        let mut attribute = tlv(OBJECT_IDENTIFIER, &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x06]); // countersignature
+        attribute.extend(tlv(SET, counter));
+        si.extend(tlv(context(1), &tlv(SEQUENCE, &attribute)));
+    }
+    tlv(SEQUENCE, &si)
+}
+
+#[test] fn nesting_depth() {
+    let chain = |depth: usize| (1 .. depth).fold(signer_info(None), |inner, _| signer_info(Some(&inner[..])));
+    let si = SignerInfo::from_der(&mut Der::new(&chain(MAX_SIGNER_INFO_DEPTH)), &mut Vec::new(), 0).unwrap();
+    assert_eq!(si.timestamps.len(), 1);
+    assert_eq!(si.timestamps[0].kind, TimestampKind::Countersignature);
+
+    let err = SignerInfo::from_der(&mut Der::new(&chain(MAX_SIGNER_INFO_DEPTH + 1)), &mut Vec::new(), 0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let err = SignerInfo::from_der(&mut Der::new(&chain(1_000)), &mut Vec::new(), 0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,84 @@
+use super::der::{self, *};
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io;
+
+
+
+/// ⨯ { [year](Self::year), [month](Self::month), [day](Self::day), [hour](Self::hour), [minute](Self::minute), [second](Self::second) }<br>
+/// A decoded ASN.1 `UTCTime` or `GeneralizedTime`, always in UTC.<br>
+/// <br>
+///
+/// Unlike [`pe::TimeDate`](crate::pe::TimeDate), certificate validity periods routinely extend past 2106 (or before 1970), so these are kept as calendar fields.
+/// Fields are ordered such that the derived [`Ord`] is chronological.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub year:   u16,
+    /// `1 ..= 12`
+    pub month:  u8,
+    /// `1 ..= 31`
+    pub day:    u8,
+    /// `0 ..= 23`
+    pub hour:   u8,
+    /// `0 ..= 59`
+    pub minute: u8,
+    /// `0 ..= 60` (leap seconds)
+    pub second: u8,
+}
+
+impl Time {
+    pub(crate) fn from_tlv(tlv: Tlv) -> io::Result<Self> {
+        let s = tlv.value;
+        let digits = |range: std::ops::Range<usize>| -> io::Result<u16> {
+            let d = s.get(range).filter(|d| d.iter().all(u8::is_ascii_digit)).ok_or_else(|| invalid("authenticode: malformed time"))?;
+            Ok(d.iter().fold(0u16, |n, d| n * 10 + u16::from(d - b'0')))
+        };
+
+        let (year, rest) = match tlv.tag {
+            der::UTC_TIME => {
+                let yy = digits(0..2)?;
+                (if yy >= 50 { 1900 + yy } else { 2000 + yy }, 2) // RFC 5280 §4.1.2.5.1
+            },
+            der::GENERALIZED_TIME => (digits(0..4)?, 4),
+            other => return Err(invalid(format!("authenticode: expected UTCTime or GeneralizedTime, found tag 0x{:02x}", other))),
+        };
+
+        let month   = digits(rest   .. rest+2)? as u8;
+        let day     = digits(rest+2 .. rest+4)? as u8;
+        let hour    = digits(rest+4 .. rest+6)? as u8;
+        let minute  = digits(rest+6 .. rest+8)? as u8;
+        let second  = if s.get(rest+8).is_some_and(u8::is_ascii_digit) { digits(rest+8 .. rest+10)? as u8 } else { 0 };
+        // Fractional seconds and the trailing 'Z' are ignored.  Non-UTC offsets aren't permitted by DER.
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+            return Err(invalid("authenticode: time out of range"));
+        }
+
+        Ok(Self { year, month, day, hour, minute, second })
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{: >4}-{:02}-{:02} {: >2}:{:02}:{:02} UTC", self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
+
+impl Debug for Time {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "Time({})", self)
+    }
+}
+
+
+
+#[test] fn parse() {
+    let t = |tag, s: &str| Time::from_tlv(Tlv { tag, value: s.as_bytes(), raw: &[] });
+    assert_eq!(t(UTC_TIME,          "210315123456Z"     ).unwrap().to_string(), "2021-03-15 12:34:56 UTC");
+    assert_eq!(t(UTC_TIME,          "991231235959Z"     ).unwrap().to_string(), "1999-12-31 23:59:59 UTC");
+    assert_eq!(t(GENERALIZED_TIME,  "21060207062816Z"   ).unwrap().to_string(), "2106-02-07  6:28:16 UTC");
+    assert_eq!(t(GENERALIZED_TIME,  "20210315123456.789Z").unwrap().second, 56);
+    assert!(t(UTC_TIME, "211315123456Z").is_err());
+    assert!(t(UTC_TIME, "2103").is_err());
+    assert!(t(OCTET_STRING, "210315123456Z").is_err());
+}
@@ -0,0 +1,39 @@
+//! **C**ommon **L**anguage **R**untime (.NET) images: the CLR header, metadata root, metadata streams, and metadata tables.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Header`]            | `IMAGE_COR20_HEADER`, referenced by [`pe::DataDirectories::com_descriptor`].
+//! | [`Flags`]             | [`Header::flags`]: `COMIMAGE_FLAGS_*` such as [`ILONLY`](Flags::ILONLY).
+//! | [`ImageKind`]         | Whether an image is native, managed (IL only), or mixed-mode.
+//! | [`Metadata`]          | The metadata blob referenced by [`Header::metadata`], with its [`MetadataRoot`].
+//! | [`MetadataRoot`]      | The `BSJB` metadata root: runtime version string and [`StreamHeader`]s.
+//! | [`StreamHeader`]      | The location and name of a metadata stream (`#~`, `#Strings`, `#US`, `#Blob`, `#GUID`.)
+//! | [`Tables`]            | The decoded `#~` stream, with typed rows such as [`TypeDef`], [`AssemblyRef`], and [`ManifestResource`].
+//! | [`TableId`]           | Identifies a metadata table, such as [`TYPE_DEF`](TableId::TYPE_DEF).
+//! | [`Token`]             | Identifies a row of a metadata table.
+//! | [`EmbeddedResource`]  | A managed resource embedded in the image, iterated by [`EmbeddedResources`].
+//! | [`ReadyToRun`]        | A [`ReadyToRunHeader`] (referenced by [`Header::managed_native_header`]) and its [`ReadyToRunSection`]s.
+//! | [`ReadyToRunFlags`]   | [`ReadyToRunHeader::flags`]: `READYTORUN_FLAG_*` such as [`COMPONENT`](ReadyToRunFlags::COMPONENT).
+//! | [`ReadyToRunSectionType`] | [`ReadyToRunSection::section_type`]: such as [`COMPILER_IDENTIFIER`](ReadyToRunSectionType::COMPILER_IDENTIFIER).
+//! | [`ReadyToRunImportSection`] | A group of lazily bound cells of precompiled code.
+//! | [`ResourceVisibility`]| Whether a [`ManifestResource`] is public or private.
+//! | [`Version`]           | An assembly version (`major.minor.build.revision`.)
+//!
+//! ## References
+//! *   [ECMA-335](https://www.ecma-international.org/publications-and-standards/standards/ecma-335/) §II.22 (Metadata logical format), §II.24 (Metadata physical layout), and §II.25.3.3 (CLI header)
+//! *   `IMAGE_COR20_HEADER` in `winnt.h` / `corhdr.h`
+//! *   [ReadyToRun File Format](https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md)
+
+mod flags;                          pub use flags::*;
+mod header;                         pub use header::*;
+mod image_kind;                     pub use image_kind::*;
+mod metadata;                       pub use metadata::*;
+mod ready_to_run_flags;             pub use ready_to_run_flags::*;
+mod ready_to_run_header;            pub use ready_to_run_header::*;
+mod ready_to_run_section_type;      pub use ready_to_run_section_type::*;
+mod resources;                      pub use resources::*;
+mod rows;                           pub use rows::*;
+mod table_id;                       pub use table_id::*;
+mod tables;                         pub use tables::*;
+
+#[cfg(doc)] use crate::*;
@@ -0,0 +1,31 @@
+#[cfg(doc)] use crate::*;
+
+from_memory_flags! {
+    /// [ILONLY](Self::ILONLY) |
+    /// [REQUIRED_32BIT](Self::REQUIRED_32BIT) |
+    /// [STRONGNAMESIGNED](Self::STRONGNAMESIGNED) |
+    /// ...<br>
+    /// [clr::Header::flags]: runtime requirements of a managed image<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://learn.microsoft.com/en-us/dotnet/framework/unmanaged-api/metadata/corhdr-h>
+    /// *   `COMIMAGE_FLAGS_*` in `corhdr.h`
+    #[repr(transparent)]
+    pub struct Flags : u32 {
+        /// The image contains only IL code - no native code (not mixed-mode C++/CLI)
+        const ILONLY                = 0x00000001;
+        /// The image can only be loaded into a 32-bit process
+        const REQUIRED_32BIT        = 0x00000002;
+        /// Obsolete
+        const IL_LIBRARY            = 0x00000004;
+        /// The image has a strong name signature ([`clr::Header::strong_name_signature`])
+        const STRONGNAMESIGNED      = 0x00000008;
+        /// [`clr::Header::entry_point`] is a native RVA rather than a managed method token
+        const NATIVE_ENTRYPOINT     = 0x00000010;
+        /// The runtime and JIT should track debug data
+        const TRACKDEBUGDATA        = 0x00010000;
+        /// The image prefers running as 32-bit, even on 64-bit platforms (`AnyCPU 32-bit preferred`) - only meaningful with [`REQUIRED_32BIT`](Self::REQUIRED_32BIT)
+        const PREFERRED_32BIT       = 0x00020000;
+    }
+}
@@ -0,0 +1,62 @@
+use crate::*;
+use pe::DataDirectory;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [major_runtime_version](Self::major_runtime_version),
+    ///     [minor_runtime_version](Self::minor_runtime_version),
+    ///     [metadata](Self::metadata),
+    ///     [flags](Self::flags),
+    ///     [entry_point](Self::entry_point),
+    ///     [resources](Self::resources),
+    ///     [strong_name_signature](Self::strong_name_signature),
+    ///     ...
+    /// }<br>
+    /// `IMAGE_COR20_HEADER`: the CLI header of a managed image, referenced by [`pe::DataDirectories::com_descriptor`]<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   [ECMA-335](https://www.ecma-international.org/publications-and-standards/standards/ecma-335/) §II.25.3.3 (CLI header)
+    /// *   `IMAGE_COR20_HEADER` in `winnt.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Header {
+        /// Size of this header in bytes (72)
+        pub cb:                             u32,
+        /// Minimum runtime version required: 2 for all .NET versions to date
+        pub major_runtime_version:          u16,
+        pub minor_runtime_version:          u16,
+        /// The metadata: see [`pe::Reader::read_clr_metadata`]
+        pub metadata:                       DataDirectory,
+        pub flags:                          clr::Flags,
+        /// A `MethodDef` or `File` metadata token, or a native [`pe::RVA`] if [`flags`](Self::flags) contains [`NATIVE_ENTRYPOINT`](clr::Flags::NATIVE_ENTRYPOINT)
+        pub entry_point:                    u32,
+        /// Managed resources, addressed by the `ManifestResource` table
+        pub resources:                      DataDirectory,
+        /// The strong name signature hash
+        pub strong_name_signature:          DataDirectory,
+        /// Always 0
+        pub code_manager_table:             DataDirectory,
+        /// An array of fixups for mixed-mode images exporting managed methods to native code
+        pub vtable_fixups:                  DataDirectory,
+        /// Always 0
+        pub export_address_table_jumps:     DataDirectory,
+        /// 0, or a ReadyToRun (or obsolete NGEN) header: see [`pe::Reader::read_ready_to_run`]
+        pub managed_native_header:          DataDirectory,
+    }
+}
+
+impl Header {
+    /// The kind of image this header describes: [`Managed`](clr::ImageKind::Managed) if [`ILONLY`](clr::Flags::ILONLY), otherwise [`Mixed`](clr::ImageKind::Mixed).
+    pub fn image_kind(&self) -> clr::ImageKind {
+        if self.flags.contains(clr::Flags::ILONLY) { clr::ImageKind::Managed } else { clr::ImageKind::Mixed }
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(72, std::mem::size_of::<<Header as FromMemory>::Raw>());
+}
@@ -0,0 +1,18 @@
+#[cfg(doc)] use crate::*;
+
+
+
+/// ∑ [Native](Self::Native) | [Managed](Self::Managed) | [Mixed](Self::Mixed)<br>
+/// Whether an image contains native code, managed code, or both<br>
+/// <br>
+///
+/// See [`pe::Reader::clr_image_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImageKind {
+    /// No [`clr::Header`]: an ordinary native image
+    Native,
+    /// A [`clr::Header`] with [`ILONLY`](clr::Flags::ILONLY): a pure .NET assembly (C#, VB.NET, `/clr:pure` C++/CLI, etc.)
+    Managed,
+    /// A [`clr::Header`] without [`ILONLY`](clr::Flags::ILONLY): a mixed-mode assembly containing native code (typically `/clr` C++/CLI)
+    Mixed,
+}
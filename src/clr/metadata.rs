@@ -0,0 +1,148 @@
+use crate::*;
+
+use std::borrow::Cow;
+use std::io;
+
+
+
+/// ⨯ { [root](Self::root), [data](Self::data) }<br>
+/// The metadata blob of a managed image, referenced by [`clr::Header::metadata`](crate::clr::Header::metadata)<br>
+/// <br>
+///
+/// ## References
+/// *   [ECMA-335](https://www.ecma-international.org/publications-and-standards/standards/ecma-335/) §II.24.2 (Metadata root)
+#[derive(Clone, Debug, Default)]
+pub struct Metadata {
+    pub root:   MetadataRoot,
+    /// The entire metadata blob, which [`StreamHeader::offset`]s are relative to
+    pub data:   Vec<u8>,
+}
+
+/// ⨯ { [major_version](Self::major_version), [minor_version](Self::minor_version), [version](Self::version), [flags](Self::flags), [streams](Self::streams) }<br>
+/// The metadata root, starting with the `BSJB` signature<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetadataRoot {
+    /// 1
+    pub major_version:  u16,
+    /// 1
+    pub minor_version:  u16,
+    /// The runtime version the image was built against, e.g. `"v4.0.30319"`
+    pub version:        String,
+    /// Reserved, 0
+    pub flags:          u16,
+    pub streams:        Vec<StreamHeader>,
+}
+
+/// ⨯ { [offset](Self::offset), [size](Self::size), [name](Self::name) }<br>
+/// The location and name of a metadata stream: `#~` (tables), `#Strings`, `#US` (user strings), `#Blob`, or `#GUID`<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamHeader {
+    /// Offset of the stream relative to the start of the [`MetadataRoot`]
+    pub offset: u32,
+    pub size:   u32,
+    pub name:   String,
+}
+
+impl MetadataRoot {
+    /// `"BSJB"` as a little-endian [`u32`]
+    pub const SIGNATURE : u32 = 0x424A5342;
+
+    /// Parse the metadata root from the start of the metadata blob.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the signature isn't `BSJB`
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the root or stream headers are truncated
+    pub fn parse(data: &[u8]) -> io::Result<Self> {
+        let eof = || io::Error::new(io::ErrorKind::UnexpectedEof, "clr::MetadataRoot truncated");
+        let u16_at = |o: usize| data.get(o .. o + 2).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or_else(eof);
+        let u32_at = |o: usize| data.get(o .. o + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok_or_else(eof);
+
+        if u32_at(0)? != Self::SIGNATURE { return Err(io::Error::new(io::ErrorKind::InvalidData, "clr::MetadataRoot::signature != \"BSJB\"")) }
+        let major_version   = u16_at(4)?;
+        let minor_version   = u16_at(6)?;
+        let length          = u32_at(12)? as usize;
+        let version         = data.get(16 .. 16 + length).ok_or_else(eof)?;
+        let version         = String::from_utf8_lossy(&version[..version.iter().position(|b| *b == 0).unwrap_or(version.len())]).into_owned();
+        let mut o           = 16 + length;
+        let flags           = u16_at(o)?;
+        let nstreams        = u16_at(o + 2)?;
+        o += 4;
+
+        let mut streams = Vec::with_capacity(nstreams.into());
+        for _ in 0 .. nstreams {
+            let offset  = u32_at(o)?;
+            let size    = u32_at(o + 4)?;
+            let name    = data.get(o + 8 ..).ok_or_else(eof)?;
+            let nul     = name.iter().position(|b| *b == 0).ok_or_else(eof)?;
+            streams.push(StreamHeader { offset, size, name: String::from_utf8_lossy(&name[..nul]).into_owned() });
+            o += 8 + (nul + 4) / 4 * 4; // name is padded to a 4-byte boundary
+        }
+
+        Ok(Self { major_version, minor_version, version, flags, streams })
+    }
+
+    /// Find a stream by name (e.g. `"#Strings"`)
+    pub fn stream(&self, name: &str) -> Option<&StreamHeader> {
+        self.streams.iter().find(|s| s.name == name)
+    }
+}
+
+impl Metadata {
+    /// Parse the metadata blob.  See [`MetadataRoot::parse`] for errors.
+    pub fn parse(data: Vec<u8>) -> io::Result<Self> {
+        let root = MetadataRoot::parse(&data[..])?;
+        Ok(Self { root, data })
+    }
+
+    /// The data of a stream by name (e.g. `"#Strings"`), or [`None`] if missing or out of bounds
+    pub fn stream_data(&self, name: &str) -> Option<&[u8]> {
+        let stream = self.root.stream(name)?;
+        self.data.get(stream.offset as usize .. (stream.offset as usize).checked_add(stream.size as usize)?)
+    }
+
+    /// Decode the `#~` tables stream.  See [`clr::Tables::parse`] for errors.
+    pub fn tables(&self) -> io::Result<clr::Tables> { clr::Tables::parse(self) }
+
+    /// A nul-terminated string from the `#Strings` heap, or [`None`] if out of bounds
+    pub fn string(&self, index: u32) -> Option<Cow<'_, str>> {
+        let s = self.stream_data("#Strings")?.get(index as usize ..)?;
+        Some(String::from_utf8_lossy(&s[..s.iter().position(|b| *b == 0)?]))
+    }
+
+    /// A length-prefixed blob from the `#Blob` heap, or [`None`] if out of bounds
+    pub fn blob(&self, index: u32) -> Option<&[u8]> {
+        let b = self.stream_data("#Blob")?.get(index as usize ..)?;
+        let (len, prefix) : (usize, usize) = match *b.first()? {
+            b0 if b0 & 0x80 == 0x00 => (usize::from(b0), 1),
+            b0 if b0 & 0xC0 == 0x80 => (usize::from(b0 & 0x3F) << 8 | usize::from(*b.get(1)?), 2),
+            b0 if b0 & 0xE0 == 0xC0 => (usize::from(b0 & 0x1F) << 24 | usize::from(*b.get(1)?) << 16 | usize::from(*b.get(2)?) << 8 | usize::from(*b.get(3)?), 4),
+            _                       => return None,
+        };
+        b.get(prefix .. prefix.checked_add(len)?)
+    }
+
+    /// A GUID from the `#GUID` heap (1-based), or [`None`] for index 0 or if out of bounds
+    pub fn guid(&self, index: u32) -> Option<[u8; 16]> {
+        let start : usize = (index as usize).checked_sub(1)? * 16;
+        let mut guid = [0u8; 16];
+        guid.copy_from_slice(self.stream_data("#GUID")?.get(start .. start + 16)?);
+        Some(guid)
+    }
+}
+
+
+
+#[test] fn parse() {
+    let mut data = b"BSJB\x01\x00\x01\x00\x00\x00\x00\x00\x0C\x00\x00\x00v4.0.30319\x00\x00\x00\x00\x02\x00".to_vec();
+    data.extend_from_slice(b"\x6C\x00\x00\x00\x10\x00\x00\x00#~\x00\x00");
+    data.extend_from_slice(b"\x7C\x00\x00\x00\x20\x00\x00\x00#Strings\x00\x00\x00\x00");
+    let root = MetadataRoot::parse(&data[..]).unwrap();
+    assert_eq!(root.version, "v4.0.30319");
+    assert_eq!(root.streams, vec![
+        StreamHeader { offset: 0x6C, size: 0x10, name: "#~".into() },
+        StreamHeader { offset: 0x7C, size: 0x20, name: "#Strings".into() },
+    ]);
+    assert_eq!(root.stream("#Strings").map(|s| s.size), Some(0x20));
+}
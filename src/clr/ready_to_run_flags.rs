@@ -0,0 +1,33 @@
+#[cfg(doc)] use crate::*;
+
+from_memory_flags! {
+    /// [PLATFORM_NEUTRAL_SOURCE](Self::PLATFORM_NEUTRAL_SOURCE) |
+    /// [PARTIAL](Self::PARTIAL) |
+    /// [COMPONENT](Self::COMPONENT) |
+    /// ...<br>
+    /// [clr::ReadyToRunHeader::flags]: how a ReadyToRun image was compiled<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md>
+    /// *   `READYTORUN_FLAG_*` in `readytorun.h`
+    #[repr(transparent)]
+    pub struct ReadyToRunFlags : u32 {
+        /// The original IL image was platform neutral
+        const PLATFORM_NEUTRAL_SOURCE       = 0x00000001;
+        /// Type validation was skipped at compile time
+        const SKIP_TYPE_VALIDATION          = 0x00000002;
+        /// Only some methods were precompiled
+        const PARTIAL                       = 0x00000004;
+        /// PInvoke stubs are compiled into the image, and not shareable
+        const NONSHARED_PINVOKE_STUBS       = 0x00000008;
+        /// The image contains the IL bodies of its methods
+        const EMBEDDED_MSIL                 = 0x00000010;
+        /// The image is a component assembly of a composite image
+        const COMPONENT                     = 0x00000020;
+        /// The image's version bubble spans multiple modules
+        const MULTIMODULE_VERSION_BUBBLE    = 0x00000040;
+        /// The image contains code for methods outside its version bubble
+        const UNRELATED_R2R_CODE            = 0x00000080;
+    }
+}
@@ -0,0 +1,105 @@
+use crate::*;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [signature](Self::signature),
+    ///     [major_version](Self::major_version),
+    ///     [minor_version](Self::minor_version),
+    ///     [flags](Self::flags),
+    ///     [nsections](Self::nsections)
+    /// }<br>
+    /// `READYTORUN_HEADER`: the header of precompiled native code in a managed image, referenced by [`clr::Header::managed_native_header`]<br>
+    /// <br>
+    ///
+    /// Followed by [`nsections`](Self::nsections) [`clr::ReadyToRunSection`]s.  See [`pe::Reader::read_ready_to_run`].
+    ///
+    /// ## References
+    /// *   <https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md>
+    /// *   `READYTORUN_HEADER` in `readytorun.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ReadyToRunHeader {
+        /// [`ReadyToRunHeader::SIGNATURE`]
+        pub signature:      u32,
+        /// Incremented on breaking format changes: 5 for .NET 5, 6+ for later runtimes
+        pub major_version:  u16,
+        pub minor_version:  u16,
+        pub flags:          clr::ReadyToRunFlags,
+        pub nsections:      u32,
+    }
+}
+
+from_memory_struct! {
+    /// { [section_type](Self::section_type), [section](Self::section) }<br>
+    /// `READYTORUN_SECTION`: an entry of the section table following [`clr::ReadyToRunHeader`]<br>
+    /// <br>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ReadyToRunSection {
+        pub section_type:   clr::ReadyToRunSectionType,
+        pub section:        pe::DataDirectory,
+    }
+}
+
+from_memory_struct! {
+    /// {
+    ///     [section](Self::section),
+    ///     [flags](Self::flags),
+    ///     [import_type](Self::import_type),
+    ///     [entry_size](Self::entry_size),
+    ///     [signatures](Self::signatures),
+    ///     [auxiliary_data](Self::auxiliary_data)
+    /// }<br>
+    /// `READYTORUN_IMPORT_SECTION`: a group of lazily bound cells, listed by [`IMPORT_SECTIONS`](clr::ReadyToRunSectionType::IMPORT_SECTIONS)<br>
+    /// <br>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ReadyToRunImportSection {
+        /// The import cells
+        pub section:        pe::DataDirectory,
+        /// `READYTORUN_IMPORT_SECTION_FLAGS_*`: `0x01` eager, `0x02` PCode, `0x04` PGO instrumentation
+        pub flags:          u16,
+        /// `READYTORUN_IMPORT_SECTION_TYPE_*`: 0 unknown, 1 stub dispatch, 2 string handle, 3 IL body fixups
+        pub import_type:    u8,
+        /// Size of each cell in bytes, or 0
+        pub entry_size:     u8,
+        /// An array of [`u32`] signature RVAs, one per cell, or 0
+        pub signatures:     pe::RVA,
+        /// Auxiliary data, or 0
+        pub auxiliary_data: pe::RVA,
+    }
+}
+
+/// ⨯ { [header](Self::header), [sections](Self::sections) }<br>
+/// A [`clr::ReadyToRunHeader`] and its section table<br>
+/// <br>
+#[derive(Clone, Debug, Default)]
+pub struct ReadyToRun {
+    pub header:     ReadyToRunHeader,
+    pub sections:   Vec<ReadyToRunSection>,
+}
+
+impl ReadyToRunHeader {
+    /// `"RTR"` as a little-endian [`u32`]
+    pub const SIGNATURE : u32 = 0x00525452;
+}
+
+impl ReadyToRun {
+    /// Find a section by type
+    pub fn section(&self, section_type: clr::ReadyToRunSectionType) -> Option<pe::DataDirectory> {
+        self.sections.iter().find(|s| s.section_type == section_type).map(|s| s.section)
+    }
+
+    /// The native entry points of non-generic methods, as a `NativeArray` indexed by `MethodDef` row
+    pub fn method_entry_points(&self) -> Option<pe::DataDirectory> { self.section(clr::ReadyToRunSectionType::METHOD_DEF_ENTRY_POINTS) }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(16, std::mem::size_of::<<ReadyToRunHeader        as FromMemory>::Raw>());
+    assert_eq!(12, std::mem::size_of::<<ReadyToRunSection       as FromMemory>::Raw>());
+    assert_eq!(20, std::mem::size_of::<<ReadyToRunImportSection as FromMemory>::Raw>());
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑
+/// [COMPILER_IDENTIFIER](Self::COMPILER_IDENTIFIER) |
+/// [IMPORT_SECTIONS](Self::IMPORT_SECTIONS) |
+/// [RUNTIME_FUNCTIONS](Self::RUNTIME_FUNCTIONS) |
+/// [METHOD_DEF_ENTRY_POINTS](Self::METHOD_DEF_ENTRY_POINTS) |
+/// ...<br>
+/// [clr::ReadyToRunSection::section_type]: what a ReadyToRun section contains<br>
+/// <br>
+///
+/// ## References
+/// *   <https://github.com/dotnet/runtime/blob/main/docs/design/coreclr/botr/readytorun-format.md>
+/// *   `ReadyToRunSectionType` in `readytorun.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct ReadyToRunSectionType(u32le);
+
+impl ReadyToRunSectionType {
+    pub const fn new(value: u32) -> Self { Self(u32le::new(value)) }
+    pub fn to_u32(self) -> u32 { self.0.to_le() }
+
+    #[doc = "A `\\0`-terminated string identifying the compiler, e.g. `\"Crossgen2 8.0.0\"`"   ] pub const COMPILER_IDENTIFIER           : ReadyToRunSectionType = ReadyToRunSectionType::new(100);
+    #[doc = "An array of [`clr::ReadyToRunImportSection`]s"                                     ] pub const IMPORT_SECTIONS               : ReadyToRunSectionType = ReadyToRunSectionType::new(101);
+    #[doc = "`RUNTIME_FUNCTION` unwind entries of the compiled code"                            ] pub const RUNTIME_FUNCTIONS             : ReadyToRunSectionType = ReadyToRunSectionType::new(102);
+    #[doc = "A `NativeArray` of entry points of non-generic methods, indexed by `MethodDef` row"] pub const METHOD_DEF_ENTRY_POINTS       : ReadyToRunSectionType = ReadyToRunSectionType::new(103);
+    #[doc = "Exception handling clauses"                                                        ] pub const EXCEPTION_INFO                : ReadyToRunSectionType = ReadyToRunSectionType::new(104);
+    #[doc = "Debug info (bounds and variables)"                                                 ] pub const DEBUG_INFO                    : ReadyToRunSectionType = ReadyToRunSectionType::new(105);
+    #[doc = "Thunks for delay loaded method calls"                                              ] pub const DELAY_LOAD_METHOD_CALL_THUNKS : ReadyToRunSectionType = ReadyToRunSectionType::new(106);
+    #[doc = "A `NativeHashtable` of types defined by this image"                                ] pub const AVAILABLE_TYPES               : ReadyToRunSectionType = ReadyToRunSectionType::new(108);
+    #[doc = "A `NativeHashtable` of entry points of generic instantiations"                     ] pub const INSTANCE_METHOD_ENTRY_POINTS  : ReadyToRunSectionType = ReadyToRunSectionType::new(109);
+    #[doc = "Cross-module inlining information (v2.1+)"                                         ] pub const INLINING_INFO                 : ReadyToRunSectionType = ReadyToRunSectionType::new(110);
+    #[doc = "Profile data (v2.2+)"                                                              ] pub const PROFILE_DATA_INFO             : ReadyToRunSectionType = ReadyToRunSectionType::new(111);
+    #[doc = "Metadata of the version bubble's modules (v2.3+)"                                  ] pub const MANIFEST_METADATA             : ReadyToRunSectionType = ReadyToRunSectionType::new(112);
+    #[doc = "Which custom attributes are present (v3.1+)"                                       ] pub const ATTRIBUTE_PRESENCE            : ReadyToRunSectionType = ReadyToRunSectionType::new(113);
+    #[doc = "Inlining information (v4.1+)"                                                      ] pub const INLINING_INFO2                : ReadyToRunSectionType = ReadyToRunSectionType::new(114);
+    #[doc = "Component assemblies of a composite image (v4.1+)"                                 ] pub const COMPONENT_ASSEMBLIES          : ReadyToRunSectionType = ReadyToRunSectionType::new(115);
+    #[doc = "The composite image owning a component assembly (v4.1+)"                           ] pub const OWNER_COMPOSITE_EXECUTABLE    : ReadyToRunSectionType = ReadyToRunSectionType::new(116);
+    #[doc = "PGO instrumentation data (v5.2+)"                                                  ] pub const PGO_INSTRUMENTATION_DATA      : ReadyToRunSectionType = ReadyToRunSectionType::new(117);
+    #[doc = "MVIDs of the manifest assemblies (v5.3+)"                                          ] pub const MANIFEST_ASSEMBLY_MVIDS       : ReadyToRunSectionType = ReadyToRunSectionType::new(118);
+    #[doc = "Cross-module inlining information (v6.2+)"                                         ] pub const CROSS_MODULE_INLINE_INFO      : ReadyToRunSectionType = ReadyToRunSectionType::new(119);
+    #[doc = "Map of hot/cold method splits (v8.0+)"                                             ] pub const HOT_COLD_MAP                  : ReadyToRunSectionType = ReadyToRunSectionType::new(120);
+    #[doc = "Which methods are generic (v9.0+)"                                                 ] pub const METHOD_IS_GENERIC_MAP         : ReadyToRunSectionType = ReadyToRunSectionType::new(121);
+    #[doc = "Enclosing types of nested types (v9.0+)"                                           ] pub const ENCLOSING_TYPE_MAP            : ReadyToRunSectionType = ReadyToRunSectionType::new(122);
+    #[doc = "Generic parameter info of types (v9.0+)"                                           ] pub const TYPE_GENERIC_INFO_MAP         : ReadyToRunSectionType = ReadyToRunSectionType::new(123);
+}
+
+impl Debug for ReadyToRunSectionType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::COMPILER_IDENTIFIER           => "COMPILER_IDENTIFIER",
+            Self::IMPORT_SECTIONS               => "IMPORT_SECTIONS",
+            Self::RUNTIME_FUNCTIONS             => "RUNTIME_FUNCTIONS",
+            Self::METHOD_DEF_ENTRY_POINTS       => "METHOD_DEF_ENTRY_POINTS",
+            Self::EXCEPTION_INFO                => "EXCEPTION_INFO",
+            Self::DEBUG_INFO                    => "DEBUG_INFO",
+            Self::DELAY_LOAD_METHOD_CALL_THUNKS => "DELAY_LOAD_METHOD_CALL_THUNKS",
+            Self::AVAILABLE_TYPES               => "AVAILABLE_TYPES",
+            Self::INSTANCE_METHOD_ENTRY_POINTS  => "INSTANCE_METHOD_ENTRY_POINTS",
+            Self::INLINING_INFO                 => "INLINING_INFO",
+            Self::PROFILE_DATA_INFO             => "PROFILE_DATA_INFO",
+            Self::MANIFEST_METADATA             => "MANIFEST_METADATA",
+            Self::ATTRIBUTE_PRESENCE            => "ATTRIBUTE_PRESENCE",
+            Self::INLINING_INFO2                => "INLINING_INFO2",
+            Self::COMPONENT_ASSEMBLIES          => "COMPONENT_ASSEMBLIES",
+            Self::OWNER_COMPOSITE_EXECUTABLE    => "OWNER_COMPOSITE_EXECUTABLE",
+            Self::PGO_INSTRUMENTATION_DATA      => "PGO_INSTRUMENTATION_DATA",
+            Self::MANIFEST_ASSEMBLY_MVIDS       => "MANIFEST_ASSEMBLY_MVIDS",
+            Self::CROSS_MODULE_INLINE_INFO      => "CROSS_MODULE_INLINE_INFO",
+            Self::HOT_COLD_MAP                  => "HOT_COLD_MAP",
+            Self::METHOD_IS_GENERIC_MAP         => "METHOD_IS_GENERIC_MAP",
+            Self::ENCLOSING_TYPE_MAP            => "ENCLOSING_TYPE_MAP",
+            Self::TYPE_GENERIC_INFO_MAP         => "TYPE_GENERIC_INFO_MAP",
+            other                               => return write!(fmt, "ReadyToRunSectionType({})", other.to_u32()),
+        };
+        write!(fmt, "ReadyToRunSectionType::{}", name)
+    }
+}
+
+impl FromMemory for ReadyToRunSectionType {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
@@ -0,0 +1,86 @@
+use crate::*;
+
+use std::io;
+use std::slice;
+
+
+
+/// ∑ [Public](Self::Public) | [Private](Self::Private)<br>
+/// The visibility of a [`clr::ManifestResource`] to other assemblies<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceVisibility {
+    /// `mrPublic`: visible to other assemblies
+    Public,
+    /// `mrPrivate`: only visible to the declaring assembly
+    Private,
+}
+
+/// ⨯ { [name](Self::name), [visibility](Self::visibility), [data](Self::data) }<br>
+/// A managed resource embedded in an assembly, such as a `.resources` blob or an embedded file<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedResource {
+    /// The manifest resource name, e.g. `"MyPlugin.Properties.Resources.resources"`
+    pub name:       String,
+    pub visibility: ResourceVisibility,
+    pub data:       Vec<u8>,
+}
+
+/// An iterator over the [`clr::EmbeddedResource`]s of an assembly.  See [`pe::Reader::read_clr_resources`].
+///
+/// [`clr::ManifestResource`]s stored in other files or assemblies (with a non-null [`implementation`](clr::ManifestResource::implementation)) are skipped.
+#[derive(Clone)]
+pub struct EmbeddedResources<'t> {
+    data:   Vec<u8>,
+    rows:   slice::Iter<'t, clr::ManifestResource>,
+}
+
+impl clr::ManifestResource {
+    /// Decode [`flags`](Self::flags)' visibility, treating unknown values as [`Private`](ResourceVisibility::Private)
+    pub fn visibility(&self) -> ResourceVisibility {
+        if self.flags & 0x7 == 0x1 { ResourceVisibility::Public } else { ResourceVisibility::Private }
+    }
+}
+
+impl<'t> EmbeddedResources<'t> {
+    /// Iterate the embedded [`clr::ManifestResource`]s of `tables`, given the contents of the [`clr::Header::resources`] directory.
+    pub fn new(resources: Vec<u8>, tables: &'t clr::Tables) -> Self {
+        Self { data: resources, rows: tables.manifest_resources().iter() }
+    }
+}
+
+impl Iterator for EmbeddedResources<'_> {
+    type Item = io::Result<EmbeddedResource>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.by_ref().find(|row| row.implementation.is_null())?;
+        let start = row.offset as usize;
+        let data = self.data.get(start .. start.saturating_add(4)).and_then(|len| {
+            let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+            self.data.get(start + 4 .. (start + 4).checked_add(len)?)
+        });
+        Some(match data {
+            Some(data)  => Ok(EmbeddedResource { name: row.name.clone(), visibility: row.visibility(), data: data.to_vec() }),
+            None        => Err(io::Error::new(io::ErrorKind::InvalidData, format!("clr::EmbeddedResource {:?} extends beyond clr::Header::resources", row.name))),
+        })
+    }
+}
+
+
+
+#[test] fn embedded_resources() {
+    let mut tables = clr::Tables::default();
+    tables.manifest_resources = vec![
+        clr::ManifestResource { offset: 0, flags: 1, name: "A.resources".into(), implementation: clr::Token::default() },
+        clr::ManifestResource { offset: 0, flags: 1, name: "External.txt".into(), implementation: clr::Token::from_table_row(clr::TableId::FILE, 1) },
+        clr::ManifestResource { offset: 8, flags: 2, name: "b.txt".into(), implementation: clr::Token::default() },
+        clr::ManifestResource { offset: 16, flags: 2, name: "truncated".into(), implementation: clr::Token::default() },
+    ];
+    let data = b"\x03\x00\x00\x00abc\0\x02\x00\x00\x00hi\0\0\xFF\x00\x00\x00".to_vec();
+    let mut resources = EmbeddedResources::new(data, &tables);
+    assert_eq!(resources.next().unwrap().unwrap(), EmbeddedResource { name: "A.resources".into(), visibility: ResourceVisibility::Public, data: b"abc".to_vec() });
+    assert_eq!(resources.next().unwrap().unwrap(), EmbeddedResource { name: "b.txt".into(), visibility: ResourceVisibility::Private, data: b"hi".to_vec() });
+    assert_eq!(resources.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert!(resources.next().is_none());
+}
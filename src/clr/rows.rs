@@ -0,0 +1,171 @@
+use crate::*;
+
+use std::fmt::{self, Display, Formatter};
+
+
+
+/// ⨯ { [generation](Self::generation), [name](Self::name), [mvid](Self::mvid) }<br>
+/// A row of the `Module` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Module {
+    pub generation: u16,
+    pub name:       String,
+    /// Module version id: a GUID distinguishing between builds of the same module
+    pub mvid:       Option<[u8; 16]>,
+}
+
+/// ⨯ { [resolution_scope](Self::resolution_scope), [name](Self::name), [namespace](Self::namespace) }<br>
+/// A row of the `TypeRef` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeRef {
+    /// A [`Module`](clr::TableId::MODULE), [`ModuleRef`](clr::TableId::MODULE_REF), [`AssemblyRef`](clr::TableId::ASSEMBLY_REF), or [`TypeRef`](clr::TableId::TYPE_REF) (for nested types)
+    pub resolution_scope:   clr::Token,
+    pub name:               String,
+    pub namespace:          String,
+}
+
+/// ⨯ { [flags](Self::flags), [name](Self::name), [namespace](Self::namespace), [extends](Self::extends), [field_list](Self::field_list), [method_list](Self::method_list) }<br>
+/// A row of the `TypeDef` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeDef {
+    /// `TypeAttributes`
+    pub flags:          u32,
+    pub name:           String,
+    pub namespace:      String,
+    /// A [`TypeDef`](clr::TableId::TYPE_DEF), [`TypeRef`](clr::TableId::TYPE_REF), or [`TypeSpec`](clr::TableId::TYPE_SPEC), or null for interfaces and `<Module>`
+    pub extends:        clr::Token,
+    /// First row of this type's run of the `Field` table
+    pub field_list:     u32,
+    /// First row of this type's run of the `MethodDef` table
+    pub method_list:    u32,
+}
+
+/// ⨯ { [flags](Self::flags), [name](Self::name), [signature](Self::signature) }<br>
+/// A row of the `Field` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Field {
+    /// `FieldAttributes`
+    pub flags:      u16,
+    pub name:       String,
+    /// `#Blob` index (see [`clr::Metadata::blob`])
+    pub signature:  u32,
+}
+
+/// ⨯ { [rva](Self::rva), [impl_flags](Self::impl_flags), [flags](Self::flags), [name](Self::name), [signature](Self::signature), [param_list](Self::param_list) }<br>
+/// A row of the `MethodDef` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MethodDef {
+    /// The method body, or 0 for abstract, runtime, and P/Invoke methods
+    pub rva:        pe::RVA,
+    /// `MethodImplAttributes`
+    pub impl_flags: u16,
+    /// `MethodAttributes`
+    pub flags:      u16,
+    pub name:       String,
+    /// `#Blob` index (see [`clr::Metadata::blob`])
+    pub signature:  u32,
+    /// First row of this method's run of the `Param` table
+    pub param_list: u32,
+}
+
+/// ⨯ { [class](Self::class), [name](Self::name), [signature](Self::signature) }<br>
+/// A row of the `MemberRef` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemberRef {
+    /// A [`TypeDef`](clr::TableId::TYPE_DEF), [`TypeRef`](clr::TableId::TYPE_REF), [`ModuleRef`](clr::TableId::MODULE_REF), [`MethodDef`](clr::TableId::METHOD_DEF), or [`TypeSpec`](clr::TableId::TYPE_SPEC)
+    pub class:      clr::Token,
+    pub name:       String,
+    /// `#Blob` index (see [`clr::Metadata::blob`])
+    pub signature:  u32,
+}
+
+/// ⨯ { [parent](Self::parent), [constructor](Self::constructor), [value](Self::value) }<br>
+/// A row of the `CustomAttribute` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CustomAttribute {
+    /// The row the attribute is applied to
+    pub parent:         clr::Token,
+    /// A [`MethodDef`](clr::TableId::METHOD_DEF) or [`MemberRef`](clr::TableId::MEMBER_REF)
+    pub constructor:    clr::Token,
+    /// `#Blob` index (see [`clr::Metadata::blob`])
+    pub value:          u32,
+}
+
+/// ⨯ { [major](Self::major), [minor](Self::minor), [build](Self::build), [revision](Self::revision) }<br>
+/// An assembly version, displayed as `major.minor.build.revision`<br>
+/// <br>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major:      u16,
+    pub minor:      u16,
+    pub build:      u16,
+    pub revision:   u16,
+}
+
+impl Display for Version {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{}.{}.{}.{}", self.major, self.minor, self.build, self.revision)
+    }
+}
+
+/// ⨯ { [hash_alg_id](Self::hash_alg_id), [version](Self::version), [flags](Self::flags), [public_key](Self::public_key), [name](Self::name), [culture](Self::culture) }<br>
+/// The single row of the `Assembly` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Assembly {
+    /// `AssemblyHashAlgorithm`, e.g. `0x8004` (SHA1)
+    pub hash_alg_id:    u32,
+    pub version:        Version,
+    /// `AssemblyFlags`
+    pub flags:          u32,
+    /// Empty if not strong name signed
+    pub public_key:     Vec<u8>,
+    pub name:           String,
+    /// Empty for culture neutral assemblies
+    pub culture:        String,
+}
+
+/// ⨯ { [version](Self::version), [flags](Self::flags), [public_key_or_token](Self::public_key_or_token), [name](Self::name), [culture](Self::culture), [hash_value](Self::hash_value) }<br>
+/// A row of the `AssemblyRef` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssemblyRef {
+    pub version:                Version,
+    /// `AssemblyFlags`: `0x0001` if [`public_key_or_token`](Self::public_key_or_token) is a full public key
+    pub flags:                  u32,
+    pub public_key_or_token:    Vec<u8>,
+    pub name:                   String,
+    pub culture:                String,
+    pub hash_value:             Vec<u8>,
+}
+
+/// ⨯ { [offset](Self::offset), [flags](Self::flags), [name](Self::name), [implementation](Self::implementation) }<br>
+/// A row of the `ManifestResource` table<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestResource {
+    /// Offset within [`clr::Header::resources`] (if [`implementation`](Self::implementation) is null)
+    pub offset:         u32,
+    /// `ManifestResourceAttributes`: `0x0001` public, `0x0002` private
+    pub flags:          u32,
+    pub name:           String,
+    /// Null if the resource is embedded in this image, otherwise a [`File`](clr::TableId::FILE) or [`AssemblyRef`](clr::TableId::ASSEMBLY_REF)
+    pub implementation: clr::Token,
+}
+
+impl TypeDef {
+    /// `true` if visibility is `Public` or `NestedPublic`
+    pub fn is_public(&self) -> bool { matches!(self.flags & 0x7, 1 | 2) }
+
+    /// `"Namespace.Name"`, or just `"Name"` if there's no namespace
+    pub fn full_name(&self) -> String {
+        if self.namespace.is_empty() { self.name.clone() } else { format!("{}.{}", self.namespace, self.name) }
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+
+
+/// [`u8`]: identifies a metadata table, such as [`TYPE_DEF`](Self::TYPE_DEF) or [`ASSEMBLY_REF`](Self::ASSEMBLY_REF)<br>
+/// <br>
+///
+/// ## References
+/// *   [ECMA-335](https://www.ecma-international.org/publications-and-standards/standards/ecma-335/) §II.22 (Metadata logical format: tables)
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TableId(u8);
+
+impl TableId {
+    pub const fn new(value: u8) -> Self { Self(value) }
+    pub const fn to_u8(self) -> u8 { self.0 }
+
+    pub const MODULE                    : TableId = TableId(0x00);
+    pub const TYPE_REF                  : TableId = TableId(0x01);
+    pub const TYPE_DEF                  : TableId = TableId(0x02);
+    pub const FIELD_PTR                 : TableId = TableId(0x03);
+    pub const FIELD                     : TableId = TableId(0x04);
+    pub const METHOD_PTR                : TableId = TableId(0x05);
+    pub const METHOD_DEF                : TableId = TableId(0x06);
+    pub const PARAM_PTR                 : TableId = TableId(0x07);
+    pub const PARAM                     : TableId = TableId(0x08);
+    pub const INTERFACE_IMPL            : TableId = TableId(0x09);
+    pub const MEMBER_REF                : TableId = TableId(0x0A);
+    pub const CONSTANT                  : TableId = TableId(0x0B);
+    pub const CUSTOM_ATTRIBUTE          : TableId = TableId(0x0C);
+    pub const FIELD_MARSHAL             : TableId = TableId(0x0D);
+    pub const DECL_SECURITY             : TableId = TableId(0x0E);
+    pub const CLASS_LAYOUT              : TableId = TableId(0x0F);
+    pub const FIELD_LAYOUT              : TableId = TableId(0x10);
+    pub const STAND_ALONE_SIG           : TableId = TableId(0x11);
+    pub const EVENT_MAP                 : TableId = TableId(0x12);
+    pub const EVENT_PTR                 : TableId = TableId(0x13);
+    pub const EVENT                     : TableId = TableId(0x14);
+    pub const PROPERTY_MAP              : TableId = TableId(0x15);
+    pub const PROPERTY_PTR              : TableId = TableId(0x16);
+    pub const PROPERTY                  : TableId = TableId(0x17);
+    pub const METHOD_SEMANTICS          : TableId = TableId(0x18);
+    pub const METHOD_IMPL               : TableId = TableId(0x19);
+    pub const MODULE_REF                : TableId = TableId(0x1A);
+    pub const TYPE_SPEC                 : TableId = TableId(0x1B);
+    pub const IMPL_MAP                  : TableId = TableId(0x1C);
+    pub const FIELD_RVA                 : TableId = TableId(0x1D);
+    pub const ENC_LOG                   : TableId = TableId(0x1E);
+    pub const ENC_MAP                   : TableId = TableId(0x1F);
+    pub const ASSEMBLY                  : TableId = TableId(0x20);
+    pub const ASSEMBLY_PROCESSOR        : TableId = TableId(0x21);
+    pub const ASSEMBLY_OS               : TableId = TableId(0x22);
+    pub const ASSEMBLY_REF              : TableId = TableId(0x23);
+    pub const ASSEMBLY_REF_PROCESSOR    : TableId = TableId(0x24);
+    pub const ASSEMBLY_REF_OS           : TableId = TableId(0x25);
+    pub const FILE                      : TableId = TableId(0x26);
+    pub const EXPORTED_TYPE             : TableId = TableId(0x27);
+    pub const MANIFEST_RESOURCE         : TableId = TableId(0x28);
+    pub const NESTED_CLASS              : TableId = TableId(0x29);
+    pub const GENERIC_PARAM             : TableId = TableId(0x2A);
+    pub const METHOD_SPEC               : TableId = TableId(0x2B);
+    pub const GENERIC_PARAM_CONSTRAINT  : TableId = TableId(0x2C);
+
+    /// The name of this table as used by ECMA-335, e.g. `"TypeDef"`
+    pub fn name(self) -> Option<&'static str> {
+        NAMES.get(usize::from(self.0)).copied()
+    }
+}
+
+const NAMES : &[&str] = &[
+    "Module", "TypeRef", "TypeDef", "FieldPtr", "Field", "MethodPtr", "MethodDef", "ParamPtr",
+    "Param", "InterfaceImpl", "MemberRef", "Constant", "CustomAttribute", "FieldMarshal", "DeclSecurity", "ClassLayout",
+    "FieldLayout", "StandAloneSig", "EventMap", "EventPtr", "Event", "PropertyMap", "PropertyPtr", "Property",
+    "MethodSemantics", "MethodImpl", "ModuleRef", "TypeSpec", "ImplMap", "FieldRVA", "ENCLog", "ENCMap",
+    "Assembly", "AssemblyProcessor", "AssemblyOS", "AssemblyRef", "AssemblyRefProcessor", "AssemblyRefOS", "File", "ExportedType",
+    "ManifestResource", "NestedClass", "GenericParam", "MethodSpec", "GenericParamConstraint",
+];
+
+impl Debug for TableId {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name)  => write!(fmt, "TableId({})", name),
+            None        => write!(fmt, "TableId(0x{:02X})", self.0),
+        }
+    }
+}
+
+/// [`u32`]: a metadata token, identifying a row of a metadata table (e.g. `0x02000001` is the first [`TYPE_DEF`](TableId::TYPE_DEF))<br>
+/// <br>
+///
+/// Coded indicies within the tables are decoded into tokens.  Row 0 is the null token of a table.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token(u32);
+
+impl Token {
+    pub const fn new(value: u32) -> Self { Self(value) }
+    pub const fn to_u32(self) -> u32 { self.0 }
+
+    /// Create a token from a table and 1-based row number
+    pub const fn from_table_row(table: TableId, row: u32) -> Self { Self((table.0 as u32) << 24 | (row & 0x00FF_FFFF)) }
+
+    #[allow(missing_docs)] pub const fn table(self) -> TableId { TableId((self.0 >> 24) as u8) }
+    /// 1-based row number, or 0 for a null reference
+    pub const fn row(self) -> u32 { self.0 & 0x00FF_FFFF }
+    #[allow(missing_docs)] pub const fn is_null(self) -> bool { self.row() == 0 }
+}
+
+impl Debug for Token {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.table().name() {
+            Some(name)  => write!(fmt, "Token({}, {})", name, self.row()),
+            None        => write!(fmt, "Token(0x{:08X})", self.0),
+        }
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "0x{:08X}", self.0) }
+}
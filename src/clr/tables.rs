@@ -0,0 +1,342 @@
+use crate::*;
+use clr::TableId as T;
+
+use std::io;
+
+
+
+/// The decoded `#~` (or uncompressed `#-`) metadata tables stream<br>
+/// <br>
+///
+/// Every table is decoded into raw column values (see [`raw_row`](Self::raw_row)), and the commonly needed tables are
+/// additionally decoded into typed rows with heap references resolved (see [`type_defs`](Self::type_defs), [`assembly_refs`](Self::assembly_refs), etc.)
+///
+/// ## References
+/// *   [ECMA-335](https://www.ecma-international.org/publications-and-standards/standards/ecma-335/) §II.24.2.6 (`#~` stream) and §II.22 (tables)
+#[derive(Clone, Debug, Default)]
+pub struct Tables {
+    /// 2
+    pub major_version:  u8,
+    /// 0
+    pub minor_version:  u8,
+    /// Bit flags: `0x01` for 4-byte `#Strings` indicies, `0x02` for `#GUID`, `0x04` for `#Blob`
+    pub heap_sizes:     u8,
+    /// Bit mask of which [`clr::TableId`]s are present
+    pub valid:          u64,
+    /// Bit mask of which [`clr::TableId`]s are sorted
+    pub sorted:         u64,
+    tables:             Vec<Table>,
+
+    pub(crate) modules:             Vec<clr::Module>,
+    pub(crate) type_refs:           Vec<clr::TypeRef>,
+    pub(crate) type_defs:           Vec<clr::TypeDef>,
+    pub(crate) fields:              Vec<clr::Field>,
+    pub(crate) method_defs:         Vec<clr::MethodDef>,
+    pub(crate) member_refs:         Vec<clr::MemberRef>,
+    pub(crate) custom_attributes:   Vec<clr::CustomAttribute>,
+    pub(crate) assemblies:          Vec<clr::Assembly>,
+    pub(crate) assembly_refs:       Vec<clr::AssemblyRef>,
+    pub(crate) manifest_resources:  Vec<clr::ManifestResource>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Table {
+    columns:    usize,
+    values:     Vec<u32>,
+}
+
+impl Tables {
+    /// Decode the `#~` (or `#-`) stream of `metadata`.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if there's no tables stream, or it contains tables unknown to ECMA-335
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the stream is truncated
+    pub fn parse(metadata: &clr::Metadata) -> io::Result<Self> {
+        let data = metadata.stream_data("#~").or_else(|| metadata.stream_data("#-")).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "clr::Metadata has no #~ tables stream"))?;
+        let mut r = Cursor { data, offset: 0 };
+        r.offset = 4; // reserved
+        let major_version   = r.u8()?;
+        let minor_version   = r.u8()?;
+        let heap_sizes      = r.u8()?;
+        r.offset += 1; // reserved
+        let valid           = u64::from(r.u32()?) | u64::from(r.u32()?) << 32;
+        let sorted          = u64::from(r.u32()?) | u64::from(r.u32()?) << 32;
+
+        let mut rows = [0u32; 64];
+        for (id, rows) in rows.iter_mut().enumerate() {
+            if valid & (1 << id) == 0 { continue }
+            if id >= SCHEMA.len() { return Err(io::Error::new(io::ErrorKind::InvalidData, format!("clr::Tables: unknown table 0x{:02X}", id))) }
+            *rows = r.u32()?;
+        }
+        if heap_sizes & 0x40 != 0 { r.offset += 4 } // extra data
+
+        let width = |column: &Column| -> usize {
+            match column {
+                Column::U16     => 2,
+                Column::U32     => 4,
+                Column::Str     => if heap_sizes & 0x01 != 0 { 4 } else { 2 },
+                Column::Guid    => if heap_sizes & 0x02 != 0 { 4 } else { 2 },
+                Column::Blob    => if heap_sizes & 0x04 != 0 { 4 } else { 2 },
+                Column::Index(t) => if rows[usize::from(t.to_u8())] < 0x10000 { 2 } else { 4 },
+                Column::Coded(c) => {
+                    let max = c.tables.iter().flatten().map(|t| rows[usize::from(t.to_u8())]).max().unwrap_or(0);
+                    if max < 1 << (16 - c.bits) { 2 } else { 4 }
+                },
+            }
+        };
+
+        let mut tables = Vec::with_capacity(SCHEMA.len());
+        for (id, schema) in SCHEMA.iter().enumerate() {
+            let row_width = schema.iter().map(width).sum::<usize>() as u64;
+            let remaining = r.data.len().saturating_sub(r.offset) as u64;
+            if u64::from(rows[id]) * row_width > remaining { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("clr::Tables: table 0x{:02X} rows extend past end of stream", id))) }
+            let mut values = Vec::with_capacity(rows[id] as usize * schema.len());
+            for _ in 0 .. rows[id] {
+                for column in schema.iter() {
+                    let raw = if width(column) == 2 { u32::from(r.u16()?) } else { r.u32()? };
+                    values.push(match column {
+                        Column::Coded(c) => {
+                            let tag = raw & ((1 << c.bits) - 1);
+                            match c.tables.get(tag as usize).copied().flatten() {
+                                Some(table) => clr::Token::from_table_row(table, raw >> c.bits).to_u32(),
+                                None        => 0,
+                            }
+                        },
+                        _ => raw,
+                    });
+                }
+            }
+            tables.push(Table { columns: schema.len(), values });
+        }
+
+        let mut tables = Self { major_version, minor_version, heap_sizes, valid, sorted, tables, ..Self::default() };
+        tables.decode_rows(metadata)?;
+        Ok(tables)
+    }
+
+    /// The number of rows in a table (0 if absent)
+    pub fn row_count(&self, table: clr::TableId) -> u32 {
+        self.tables.get(usize::from(table.to_u8())).map_or(0, |t| (t.values.len() / t.columns.max(1)) as u32)
+    }
+
+    /// The raw column values of a row (1-based), in ECMA-335 column order.
+    ///
+    /// *   Constants and flags are zero-extended to [`u32`]
+    /// *   Heap and simple table indicies are left as-is
+    /// *   Coded indicies are decoded into [`clr::Token`]s (see [`clr::Token::new`])
+    pub fn raw_row(&self, table: clr::TableId, row: u32) -> Option<&[u32]> {
+        let t = self.tables.get(usize::from(table.to_u8()))?;
+        let start = (row as usize).checked_sub(1)? * t.columns;
+        t.values.get(start .. start + t.columns)
+    }
+
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn modules(&self) -> &[clr::Module] { &self.modules[..] }
+    #[allow(missing_docs)] pub fn type_refs(&self) -> &[clr::TypeRef] { &self.type_refs[..] }
+    #[allow(missing_docs)] pub fn type_defs(&self) -> &[clr::TypeDef] { &self.type_defs[..] }
+    #[allow(missing_docs)] pub fn fields(&self) -> &[clr::Field] { &self.fields[..] }
+    #[allow(missing_docs)] pub fn method_defs(&self) -> &[clr::MethodDef] { &self.method_defs[..] }
+    #[allow(missing_docs)] pub fn member_refs(&self) -> &[clr::MemberRef] { &self.member_refs[..] }
+    #[allow(missing_docs)] pub fn custom_attributes(&self) -> &[clr::CustomAttribute] { &self.custom_attributes[..] }
+    #[allow(missing_docs)] pub fn assemblies(&self) -> &[clr::Assembly] { &self.assemblies[..] }
+    #[allow(missing_docs)] pub fn assembly_refs(&self) -> &[clr::AssemblyRef] { &self.assembly_refs[..] }
+    #[allow(missing_docs)] pub fn manifest_resources(&self) -> &[clr::ManifestResource] { &self.manifest_resources[..] }
+
+    /// The single [`clr::Assembly`] row, or [`None`] for a netmodule
+    pub fn assembly(&self) -> Option<&clr::Assembly> { self.assemblies.first() }
+
+    fn rows(&self, table: clr::TableId) -> impl Iterator<Item = &[u32]> {
+        let t = &self.tables[usize::from(table.to_u8())];
+        t.values.chunks_exact(t.columns)
+    }
+
+    fn decode_rows(&mut self, m: &clr::Metadata) -> io::Result<()> {
+        let s = |i: u32| m.string(i).map(|s| s.into_owned()).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("clr::Tables: #Strings index 0x{:X} out of bounds", i)));
+        let b = |i: u32| m.blob(i).map(|b| b.to_vec()).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("clr::Tables: #Blob index 0x{:X} out of bounds", i)));
+        let u16_ = |v: u32| v as u16;
+        let tok = clr::Token::new;
+        let version = |r: &[u32]| clr::Version { major: r[0] as u16, minor: r[1] as u16, build: r[2] as u16, revision: r[3] as u16 };
+
+        self.modules            = self.rows(T::MODULE).map(|r| Ok(clr::Module { generation: u16_(r[0]), name: s(r[1])?, mvid: m.guid(r[2]) })).collect::<io::Result<_>>()?;
+        self.type_refs          = self.rows(T::TYPE_REF).map(|r| Ok(clr::TypeRef { resolution_scope: tok(r[0]), name: s(r[1])?, namespace: s(r[2])? })).collect::<io::Result<_>>()?;
+        self.type_defs          = self.rows(T::TYPE_DEF).map(|r| Ok(clr::TypeDef { flags: r[0], name: s(r[1])?, namespace: s(r[2])?, extends: tok(r[3]), field_list: r[4], method_list: r[5] })).collect::<io::Result<_>>()?;
+        self.fields             = self.rows(T::FIELD).map(|r| Ok(clr::Field { flags: u16_(r[0]), name: s(r[1])?, signature: r[2] })).collect::<io::Result<_>>()?;
+        self.method_defs        = self.rows(T::METHOD_DEF).map(|r| Ok(clr::MethodDef { rva: pe::RVA::new(r[0]), impl_flags: u16_(r[1]), flags: u16_(r[2]), name: s(r[3])?, signature: r[4], param_list: r[5] })).collect::<io::Result<_>>()?;
+        self.member_refs        = self.rows(T::MEMBER_REF).map(|r| Ok(clr::MemberRef { class: tok(r[0]), name: s(r[1])?, signature: r[2] })).collect::<io::Result<_>>()?;
+        self.custom_attributes  = self.rows(T::CUSTOM_ATTRIBUTE).map(|r| clr::CustomAttribute { parent: tok(r[0]), constructor: tok(r[1]), value: r[2] }).collect();
+        self.assemblies         = self.rows(T::ASSEMBLY).map(|r| Ok(clr::Assembly { hash_alg_id: r[0], version: version(&r[1..5]), flags: r[5], public_key: b(r[6])?, name: s(r[7])?, culture: s(r[8])? })).collect::<io::Result<_>>()?;
+        self.assembly_refs      = self.rows(T::ASSEMBLY_REF).map(|r| Ok(clr::AssemblyRef { version: version(&r[0..4]), flags: r[4], public_key_or_token: b(r[5])?, name: s(r[6])?, culture: s(r[7])?, hash_value: b(r[8])? })).collect::<io::Result<_>>()?;
+        self.manifest_resources = self.rows(T::MANIFEST_RESOURCE).map(|r| Ok(clr::ManifestResource { offset: r[0], flags: r[1], name: s(r[2])?, implementation: tok(r[3]) })).collect::<io::Result<_>>()?;
+        Ok(())
+    }
+}
+
+struct Cursor<'d> {
+    data:   &'d [u8],
+    offset: usize,
+}
+
+impl Cursor<'_> {
+    fn bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut b = [0u8; N];
+        b.copy_from_slice(self.data.get(self.offset .. self.offset + N).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "clr::Tables truncated"))?);
+        self.offset += N;
+        Ok(b)
+    }
+
+    fn u8 (&mut self) -> io::Result<u8>  { Ok(self.bytes::<1>()?[0]) }
+    fn u16(&mut self) -> io::Result<u16> { Ok(u16::from_le_bytes(self.bytes()?)) }
+    fn u32(&mut self) -> io::Result<u32> { Ok(u32::from_le_bytes(self.bytes()?)) }
+}
+
+#[derive(Clone, Copy)]
+enum Column {
+    U16,
+    U32,
+    Str,
+    Guid,
+    Blob,
+    Index(clr::TableId),
+    Coded(&'static CodedIndex),
+}
+
+struct CodedIndex {
+    bits:   u32,
+    tables: &'static [Option<clr::TableId>],
+}
+
+use Column::*;
+
+const TYPE_DEF_OR_REF           : Column = Coded(&CodedIndex { bits: 2, tables: &[Some(T::TYPE_DEF), Some(T::TYPE_REF), Some(T::TYPE_SPEC)] });
+const HAS_CONSTANT              : Column = Coded(&CodedIndex { bits: 2, tables: &[Some(T::FIELD), Some(T::PARAM), Some(T::PROPERTY)] });
+const HAS_CUSTOM_ATTRIBUTE      : Column = Coded(&CodedIndex { bits: 5, tables: &[
+    Some(T::METHOD_DEF), Some(T::FIELD), Some(T::TYPE_REF), Some(T::TYPE_DEF), Some(T::PARAM), Some(T::INTERFACE_IMPL), Some(T::MEMBER_REF), Some(T::MODULE),
+    Some(T::DECL_SECURITY), Some(T::PROPERTY), Some(T::EVENT), Some(T::STAND_ALONE_SIG), Some(T::MODULE_REF), Some(T::TYPE_SPEC), Some(T::ASSEMBLY), Some(T::ASSEMBLY_REF),
+    Some(T::FILE), Some(T::EXPORTED_TYPE), Some(T::MANIFEST_RESOURCE), Some(T::GENERIC_PARAM), Some(T::GENERIC_PARAM_CONSTRAINT), Some(T::METHOD_SPEC),
+]});
+const HAS_FIELD_MARSHAL         : Column = Coded(&CodedIndex { bits: 1, tables: &[Some(T::FIELD), Some(T::PARAM)] });
+const HAS_DECL_SECURITY         : Column = Coded(&CodedIndex { bits: 2, tables: &[Some(T::TYPE_DEF), Some(T::METHOD_DEF), Some(T::ASSEMBLY)] });
+const MEMBER_REF_PARENT         : Column = Coded(&CodedIndex { bits: 3, tables: &[Some(T::TYPE_DEF), Some(T::TYPE_REF), Some(T::MODULE_REF), Some(T::METHOD_DEF), Some(T::TYPE_SPEC)] });
+const HAS_SEMANTICS             : Column = Coded(&CodedIndex { bits: 1, tables: &[Some(T::EVENT), Some(T::PROPERTY)] });
+const METHOD_DEF_OR_REF         : Column = Coded(&CodedIndex { bits: 1, tables: &[Some(T::METHOD_DEF), Some(T::MEMBER_REF)] });
+const MEMBER_FORWARDED          : Column = Coded(&CodedIndex { bits: 1, tables: &[Some(T::FIELD), Some(T::METHOD_DEF)] });
+const IMPLEMENTATION            : Column = Coded(&CodedIndex { bits: 2, tables: &[Some(T::FILE), Some(T::ASSEMBLY_REF), Some(T::EXPORTED_TYPE)] });
+const CUSTOM_ATTRIBUTE_TYPE     : Column = Coded(&CodedIndex { bits: 3, tables: &[None, None, Some(T::METHOD_DEF), Some(T::MEMBER_REF), None] });
+const RESOLUTION_SCOPE          : Column = Coded(&CodedIndex { bits: 2, tables: &[Some(T::MODULE), Some(T::MODULE_REF), Some(T::ASSEMBLY_REF), Some(T::TYPE_REF)] });
+const TYPE_OR_METHOD_DEF        : Column = Coded(&CodedIndex { bits: 1, tables: &[Some(T::TYPE_DEF), Some(T::METHOD_DEF)] });
+
+/// Columns of every table, indexed by [`clr::TableId`]
+const SCHEMA : &[&[Column]] = &[
+    /* Module                   */ &[U16, Str, Guid, Guid, Guid],
+    /* TypeRef                  */ &[RESOLUTION_SCOPE, Str, Str],
+    /* TypeDef                  */ &[U32, Str, Str, TYPE_DEF_OR_REF, Index(T::FIELD), Index(T::METHOD_DEF)],
+    /* FieldPtr                 */ &[Index(T::FIELD)],
+    /* Field                    */ &[U16, Str, Blob],
+    /* MethodPtr                */ &[Index(T::METHOD_DEF)],
+    /* MethodDef                */ &[U32, U16, U16, Str, Blob, Index(T::PARAM)],
+    /* ParamPtr                 */ &[Index(T::PARAM)],
+    /* Param                    */ &[U16, U16, Str],
+    /* InterfaceImpl            */ &[Index(T::TYPE_DEF), TYPE_DEF_OR_REF],
+    /* MemberRef                */ &[MEMBER_REF_PARENT, Str, Blob],
+    /* Constant                 */ &[U16, HAS_CONSTANT, Blob],
+    /* CustomAttribute          */ &[HAS_CUSTOM_ATTRIBUTE, CUSTOM_ATTRIBUTE_TYPE, Blob],
+    /* FieldMarshal             */ &[HAS_FIELD_MARSHAL, Blob],
+    /* DeclSecurity             */ &[U16, HAS_DECL_SECURITY, Blob],
+    /* ClassLayout              */ &[U16, U32, Index(T::TYPE_DEF)],
+    /* FieldLayout              */ &[U32, Index(T::FIELD)],
+    /* StandAloneSig            */ &[Blob],
+    /* EventMap                 */ &[Index(T::TYPE_DEF), Index(T::EVENT)],
+    /* EventPtr                 */ &[Index(T::EVENT)],
+    /* Event                    */ &[U16, Str, TYPE_DEF_OR_REF],
+    /* PropertyMap              */ &[Index(T::TYPE_DEF), Index(T::PROPERTY)],
+    /* PropertyPtr              */ &[Index(T::PROPERTY)],
+    /* Property                 */ &[U16, Str, Blob],
+    /* MethodSemantics          */ &[U16, Index(T::METHOD_DEF), HAS_SEMANTICS],
+    /* MethodImpl               */ &[Index(T::TYPE_DEF), METHOD_DEF_OR_REF, METHOD_DEF_OR_REF],
+    /* ModuleRef                */ &[Str],
+    /* TypeSpec                 */ &[Blob],
+    /* ImplMap                  */ &[U16, MEMBER_FORWARDED, Str, Index(T::MODULE_REF)],
+    /* FieldRVA                 */ &[U32, Index(T::FIELD)],
+    /* ENCLog                   */ &[U32, U32],
+    /* ENCMap                   */ &[U32],
+    /* Assembly                 */ &[U32, U16, U16, U16, U16, U32, Blob, Str, Str],
+    /* AssemblyProcessor        */ &[U32],
+    /* AssemblyOS               */ &[U32, U32, U32],
+    /* AssemblyRef              */ &[U16, U16, U16, U16, U32, Blob, Str, Str, Blob],
+    /* AssemblyRefProcessor     */ &[U32, Index(T::ASSEMBLY_REF)],
+    /* AssemblyRefOS            */ &[U32, U32, U32, Index(T::ASSEMBLY_REF)],
+    /* File                     */ &[U32, Str, Blob],
+    /* ExportedType             */ &[U32, U32, Str, Str, IMPLEMENTATION],
+    /* ManifestResource         */ &[U32, U32, Str, IMPLEMENTATION],
+    /* NestedClass              */ &[Index(T::TYPE_DEF), Index(T::TYPE_DEF)],
+    /* GenericParam             */ &[U16, U16, TYPE_OR_METHOD_DEF, Str],
+    /* MethodSpec               */ &[METHOD_DEF_OR_REF, Blob],
+    /* GenericParamConstraint   */ &[Index(T::GENERIC_PARAM), TYPE_DEF_OR_REF],
+];
+
+
+
+#[test] fn parse() {
+    let strings = b"\0Lib.dll\0Lib\0Widget\0mscorlib\0Lib.Strings.resources\0";
+    let blob    = b"\0\x08\xB7\x7A\x5C\x56\x19\x34\xE0\x89";
+    let guid    = [0x11u8; 16];
+
+    let mut t = vec![0, 0, 0, 0, 2, 0, 0, 1];
+    let valid = 1u64 << 0x00 | 1 << 0x02 | 1 << 0x20 | 1 << 0x23 | 1 << 0x28;
+    t.extend_from_slice(&valid.to_le_bytes());
+    t.extend_from_slice(&0u64.to_le_bytes());
+    for _ in 0 .. 5 { t.extend_from_slice(&1u32.to_le_bytes()) }
+    let mut row = |values: &[u16]| for v in values { t.extend_from_slice(&v.to_le_bytes()) };
+    row(&[0, 1, 1, 0, 0]);                                      // Module: generation, name, mvid, encid, encbaseid
+    row(&[0x0001, 0x0000, 13, 9, 0x0000, 1, 1]);                // TypeDef: flags (u32), name, namespace, extends, field_list, method_list
+    row(&[0x8004, 0, 1, 2, 3, 4, 0, 0, 0, 9, 0]);               // Assembly: hash_alg_id (u32), version, flags (u32), public_key, name, culture
+    row(&[4, 0, 0, 0, 0, 0, 1, 20, 0, 0]);                      // AssemblyRef: version, flags (u32), public_key_or_token, name, culture, hash_value
+    row(&[0x10, 0, 1, 0, 29, 0]);                               // ManifestResource: offset (u32), flags (u32), name, implementation
+
+    let metadata = clr::Metadata::parse(metadata(&[("#~", &t[..]), ("#Strings", &strings[..]), ("#Blob", &blob[..]), ("#GUID", &guid[..])])).unwrap();
+    let tables = metadata.tables().unwrap();
+    assert_eq!(tables.row_count(T::TYPE_DEF), 1);
+    assert_eq!(tables.row_count(T::TYPE_REF), 0);
+    assert_eq!(tables.modules()[0].name, "Lib.dll");
+    assert_eq!(tables.modules()[0].mvid, Some([0x11; 16]));
+    assert_eq!(tables.type_defs()[0].full_name(), "Lib.Widget");
+    assert!(tables.type_defs()[0].is_public());
+    assert_eq!(tables.type_defs()[0].extends, clr::Token::from_table_row(T::TYPE_DEF, 0));
+    let assembly = tables.assembly().unwrap();
+    assert_eq!((assembly.name.as_str(), assembly.version.to_string().as_str()), ("Lib", "1.2.3.4"));
+    assert_eq!(tables.assembly_refs()[0].name, "mscorlib");
+    assert_eq!(tables.assembly_refs()[0].version.major, 4);
+    assert_eq!(tables.assembly_refs()[0].public_key_or_token, b"\xB7\x7A\x5C\x56\x19\x34\xE0\x89");
+    assert_eq!(tables.manifest_resources()[0].name, "Lib.Strings.resources");
+    assert_eq!(tables.manifest_resources()[0].offset, 0x10);
+    assert!(tables.manifest_resources()[0].implementation.is_null());
+    assert_eq!(tables.raw_row(T::ASSEMBLY, 1).map(|r| r[0]), Some(0x8004));
+    assert_eq!(tables.raw_row(T::ASSEMBLY, 2), None);
+}
+
+#[test] fn parse_truncated() {
+    let mut t = vec![0, 0, 0, 0, 2, 0, 0, 1];
+    t.extend_from_slice(&1u64.to_le_bytes());       // valid: Module
+    t.extend_from_slice(&0u64.to_le_bytes());       // sorted
+    t.extend_from_slice(&u32::MAX.to_le_bytes());   // 0xFFFFFFFF Module rows, with no row data
+    let metadata = clr::Metadata::parse(metadata(&[("#~", &t[..])])).unwrap();
+    assert_eq!(metadata.tables().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[cfg(test)] fn metadata(streams: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut data = b"BSJB\x01\x00\x01\x00\x00\x00\x00\x00\x04\x00\x00\x00v4.0\x00\x00".to_vec();
+    data.extend_from_slice(&(streams.len() as u16).to_le_bytes());
+    let mut offset = 0x80;
+    for (name, stream) in streams.iter() {
+        data.extend_from_slice(&(offset as u32).to_le_bytes());
+        data.extend_from_slice(&(stream.len() as u32).to_le_bytes());
+        data.extend_from_slice(name.as_bytes());
+        data.resize(data.len() + 4 - data.len() % 4, 0);
+        offset += (stream.len() + 3) & !3;
+    }
+    for (_, stream) in streams.iter() {
+        data.resize((data.len().max(0x80) + 3) & !3, 0);
+        data.extend_from_slice(stream);
+    }
+    data
+}
@@ -0,0 +1,38 @@
+//! Bare **C**ommon **O**bject **F**ile **F**ormat objects (`.obj` files), as emitted by MSVC, `clang-cl`, MinGW, etc.
+//!
+//! Objects share [`pe::FileHeader`], [`pe::SectionHeader`], and [`pe::Machine`] with [`pe`] images, but start at offset 0 with no [`mz::Header`] or signature.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Reader`]            | Reads the [`Header`] and [`pe::SectionHeader`]s, and per-section data on demand.
+//! | [`Header`]            | Either a classic [`pe::FileHeader`], or a [`BigObjHeader`].
+//! | [`BigObjHeader`]      | `ANON_OBJECT_HEADER_BIGOBJ`: the header of `/bigobj` objects, with 32-bit section counts.
+//! | [`Relocation`]        | A per-section relocation, patching a location with the address of a symbol.
+//! | [`RelocationType`]    | The [`pe::Machine`]-specific kind of a [`Relocation`].
+//! | [`LineNumber`]        | A (deprecated) COFF line number entry.
+//! | [`SymbolTable`]       | Every [`Symbol`] of an object (or MinGW-built image), plus the [`StringTable`].
+//! | [`Symbol`]            | `IMAGE_SYMBOL`: a named value (typically a section offset), with decoded [`AuxSymbol`]s.
+//! | [`StorageClass`]      | What kind of definition a [`Symbol`] represents.
+//! | [`ComdatSelection`]   | How the linker picks between duplicate definitions of a COMDAT section.
+//! | [`StringTable`]       | Long [`Symbol`] names and `/123`-style long section names.
+//! | [`ImportObject`]      | A short import object (import library member), with its [`ImportObjectHeader`] and names.
+//! | [`ImportName`]        | What an [`ImportObject`] imports: an ordinal, or a name derived per [`ImportNameType`].
+//! | [`ImportType`]        | Whether an [`ImportObject`] imports code, data, or a constant.
+//!
+//! ## References
+//! *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#coff-file-header-object-and-image>
+//! *   <https://wiki.osdev.org/COFF>
+
+mod comdat_selection;               pub use comdat_selection::*;
+mod header;                         pub use header::*;
+mod import_name_type;               pub use import_name_type::*;
+mod import_object;                  pub use import_object::*;
+mod import_type;                    pub use import_type::*;
+mod line_number;                    pub use line_number::*;
+mod reader;                         pub use reader::*;
+mod relocation;                     pub use relocation::*;
+mod storage_class;                  pub use storage_class::*;
+mod string_table;                   pub use string_table::*;
+mod symbol;                         pub use symbol::*;
+
+#[cfg(doc)] use crate::*;
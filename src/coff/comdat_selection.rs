@@ -0,0 +1,55 @@
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑
+/// [NODUPLICATES](Self::NODUPLICATES) |
+/// [ANY](Self::ANY) |
+/// [SAME_SIZE](Self::SAME_SIZE) |
+/// [EXACT_MATCH](Self::EXACT_MATCH) |
+/// [ASSOCIATIVE](Self::ASSOCIATIVE) |
+/// [LARGEST](Self::LARGEST) |
+/// [NEWEST](Self::NEWEST)<br>
+/// How the linker picks between duplicate definitions of a COMDAT section<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#comdat-sections-object-only>
+/// *   `IMAGE_COMDAT_SELECT_*` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Pod, Zeroable)]
+pub struct ComdatSelection(u8);
+
+impl ComdatSelection {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    #[doc = "Not a COMDAT section"                                              ] pub const NONE            : ComdatSelection = ComdatSelection::new(0);
+    #[doc = "Duplicate definitions are an error"                                ] pub const NODUPLICATES    : ComdatSelection = ComdatSelection::new(1);
+    #[doc = "Pick any one definition"                                           ] pub const ANY             : ComdatSelection = ComdatSelection::new(2);
+    #[doc = "Pick any one definition; duplicates must be the same size"        ] pub const SAME_SIZE       : ComdatSelection = ComdatSelection::new(3);
+    #[doc = "Pick any one definition; duplicates must match exactly"           ] pub const EXACT_MATCH     : ComdatSelection = ComdatSelection::new(4);
+    #[doc = "Linked if and only if the associated section is linked"           ] pub const ASSOCIATIVE     : ComdatSelection = ComdatSelection::new(5);
+    #[doc = "Pick the largest definition"                                       ] pub const LARGEST         : ComdatSelection = ComdatSelection::new(6);
+    #[doc = "Pick the newest definition"                                        ] pub const NEWEST          : ComdatSelection = ComdatSelection::new(7);
+}
+
+impl Debug for ComdatSelection {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::NONE          => write!(fmt, "ComdatSelection::NONE"),
+            Self::NODUPLICATES  => write!(fmt, "ComdatSelection::NODUPLICATES"),
+            Self::ANY           => write!(fmt, "ComdatSelection::ANY"),
+            Self::SAME_SIZE     => write!(fmt, "ComdatSelection::SAME_SIZE"),
+            Self::EXACT_MATCH   => write!(fmt, "ComdatSelection::EXACT_MATCH"),
+            Self::ASSOCIATIVE   => write!(fmt, "ComdatSelection::ASSOCIATIVE"),
+            Self::LARGEST       => write!(fmt, "ComdatSelection::LARGEST"),
+            Self::NEWEST        => write!(fmt, "ComdatSelection::NEWEST"),
+            other               => write!(fmt, "ComdatSelection({})", other.0),
+        }
+    }
+}
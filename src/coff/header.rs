@@ -0,0 +1,89 @@
+use crate::*;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [sig1](Self::sig1),
+    ///     [sig2](Self::sig2),
+    ///     [version](Self::version),
+    ///     [machine](Self::machine),
+    ///     [class_id](Self::class_id),
+    ///     [nsections](Self::nsections),
+    ///     [symbols](Self::symbols),
+    ///     [nsymbols](Self::nsymbols),
+    ///     ...
+    /// }<br>
+    /// `ANON_OBJECT_HEADER_BIGOBJ`: the header of a `/bigobj` COFF object, with 32-bit section numbers and 20-byte [`coff::Symbol`] records<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   `ANON_OBJECT_HEADER_BIGOBJ` in `winnt.h`
+    /// *   <https://learn.microsoft.com/en-us/cpp/build/reference/bigobj-increase-number-of-sections-in-dot-obj-file>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct BigObjHeader {
+        /// [`pe::Machine::UNKNOWN`] (0), where a [`pe::FileHeader::machine`] would be
+        pub sig1:                       u16,
+        /// `0xFFFF`, where a [`pe::FileHeader::nsections`] would be
+        pub sig2:                       u16,
+        /// 2 or higher (1 is an `ANON_OBJECT_HEADER`, 0 an `IMPORT_OBJECT_HEADER`)
+        pub version:                    u16,
+        pub machine:                    pe::Machine,
+        pub time_date:                  pe::TimeDate,
+        /// [`BigObjHeader::CLASS_ID`]
+        pub class_id:                   [u8; 16],
+        pub size_of_data:               u32,
+        pub flags:                      u32,
+        pub meta_data_size:             u32,
+        pub meta_data_offset:           u32,
+        pub nsections:                  u32,
+        /// File offset of the symbol table
+        pub symbols:                    u32,
+        pub nsymbols:                   u32,
+    }
+}
+
+impl BigObjHeader {
+    /// `{D1BAA1C7-BAEE-4BA9-AF20-FAF66AA4DCB8}`, in memory order
+    pub const CLASS_ID : [u8; 16] = [0xC7, 0xA1, 0xBA, 0xD1, 0xEE, 0xBA, 0xA9, 0x4B, 0xAF, 0x20, 0xFA, 0xF6, 0x6A, 0xA4, 0xDC, 0xB8];
+
+    /// `true` if the signature, version, and [`class_id`](Self::class_id) all identify a bigobj header
+    pub fn is_valid(&self) -> bool {
+        self.sig1 == 0 && self.sig2 == 0xFFFF && self.version >= 2 && self.class_id == Self::CLASS_ID
+    }
+}
+
+/// ∑ [Classic](Self::Classic) | [BigObj](Self::BigObj)<br>
+/// The header of a COFF object: either the classic [`pe::FileHeader`], or a [`coff::BigObjHeader`]<br>
+/// <br>
+#[derive(Clone, Copy, Debug)]
+pub enum Header {
+    Classic(pe::FileHeader),
+    BigObj(BigObjHeader),
+}
+
+impl Header {
+    /// `true` for [`Header::BigObj`]
+    pub fn is_bigobj(&self) -> bool { matches!(self, Header::BigObj(_)) }
+
+    #[allow(missing_docs)] pub fn machine(&self) -> pe::Machine { match self { Header::Classic(h) => h.machine, Header::BigObj(h) => h.machine } }
+    #[allow(missing_docs)] pub fn time_date(&self) -> pe::TimeDate { match self { Header::Classic(h) => h.link_time_date, Header::BigObj(h) => h.time_date } }
+    #[allow(missing_docs)] pub fn nsections(&self) -> u32 { match self { Header::Classic(h) => h.nsections.into(), Header::BigObj(h) => h.nsections } }
+    #[allow(missing_docs)] pub fn nsymbols(&self) -> u32 { match self { Header::Classic(h) => h.nsymbols, Header::BigObj(h) => h.nsymbols } }
+
+    /// File offset of the symbol table, or 0 if there is none
+    pub fn symbols(&self) -> u32 { match self { Header::Classic(h) => h.symbols, Header::BigObj(h) => h.symbols } }
+
+    /// Size of each [`coff::Symbol`] record: [`coff::Symbol::SIZE`] or [`coff::Symbol::SIZE_EX`]
+    pub fn symbol_size(&self) -> usize { if self.is_bigobj() { coff::Symbol::SIZE_EX } else { coff::Symbol::SIZE } }
+
+    /// File offset of the [`coff::StringTable`]
+    pub fn string_table(&self) -> u64 { u64::from(self.symbols()) + u64::from(self.nsymbols()) * self.symbol_size() as u64 }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(56, std::mem::size_of::<<BigObjHeader as FromMemory>::Raw>());
+}
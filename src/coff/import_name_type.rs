@@ -0,0 +1,49 @@
+#[cfg(doc)] use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑ [ORDINAL](Self::ORDINAL) | [NAME](Self::NAME) | [NAME_NOPREFIX](Self::NAME_NOPREFIX) | [NAME_UNDECORATE](Self::NAME_UNDECORATE) | [NAME_EXPORTAS](Self::NAME_EXPORTAS)<br>
+/// [coff::ImportObjectHeader::name_type]: how to derive the name imported from the DLL from the public symbol name<br>
+/// <br>
+///
+/// See [`coff::ImportObject::import_name`] to apply these rules.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#import-name-type>
+/// *   `IMPORT_OBJECT_NAME_TYPE` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Pod, Zeroable)]
+pub struct ImportNameType(u8);
+
+impl ImportNameType {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    pub const fn to_u8(self) -> u8 { self.0 }
+
+    #[doc = "Import by ordinal: [`coff::ImportObjectHeader::ordinal_or_hint`] is an ordinal"                    ] pub const ORDINAL         : ImportNameType = ImportNameType::new(0);
+    #[doc = "Import by the public symbol name, as-is"                                                           ] pub const NAME            : ImportNameType = ImportNameType::new(1);
+    #[doc = "Import by the public symbol name, skipping a leading `?`, `@`, or (x86 only) `_`"                  ] pub const NAME_NOPREFIX   : ImportNameType = ImportNameType::new(2);
+    #[doc = "Import by the public symbol name, skipping a leading `?`, `@`, or (x86 only) `_`, truncated at the first `@`"] pub const NAME_UNDECORATE : ImportNameType = ImportNameType::new(3);
+    #[doc = "Import by the explicit export name following the DLL name"                                         ] pub const NAME_EXPORTAS   : ImportNameType = ImportNameType::new(4);
+}
+
+impl Debug for ImportNameType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::ORDINAL           => "ORDINAL",
+            Self::NAME              => "NAME",
+            Self::NAME_NOPREFIX     => "NAME_NOPREFIX",
+            Self::NAME_UNDECORATE   => "NAME_UNDECORATE",
+            Self::NAME_EXPORTAS     => "NAME_EXPORTAS",
+            other                   => return write!(fmt, "ImportNameType({})", other.0),
+        };
+        write!(fmt, "ImportNameType::{}", name)
+    }
+}
@@ -0,0 +1,217 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::io;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [sig1](Self::sig1),
+    ///     [sig2](Self::sig2),
+    ///     [version](Self::version),
+    ///     [machine](Self::machine),
+    ///     [time_date](Self::time_date),
+    ///     [size_of_data](Self::size_of_data),
+    ///     [ordinal_or_hint](Self::ordinal_or_hint),
+    ///     [type_info](Self::type_info),
+    /// }<br>
+    /// `IMPORT_OBJECT_HEADER`: the header of a short import object, as found in import libraries<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#import-header>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ImportObjectHeader {
+        /// [`pe::Machine::UNKNOWN`] (0), where a [`pe::FileHeader::machine`] would be
+        pub sig1:               u16,
+        /// `0xFFFF`, where a [`pe::FileHeader::nsections`] would be
+        pub sig2:               u16,
+        /// 0 (1 is an `ANON_OBJECT_HEADER`, 2+ a [`coff::BigObjHeader`])
+        pub version:            u16,
+        pub machine:            pe::Machine,
+        pub time_date:          pe::TimeDate,
+        /// Size of the names following this header
+        pub size_of_data:       u32,
+        /// An ordinal if [`name_type`](Self::name_type) is [`ORDINAL`](coff::ImportNameType::ORDINAL), otherwise a hint into the DLL's export name table
+        pub ordinal_or_hint:    u16,
+        /// Bits 0-1: [`import_type`](Self::import_type), bits 2-4: [`name_type`](Self::name_type)
+        pub type_info:          u16,
+    }
+}
+
+impl ImportObjectHeader {
+    /// Size of an `IMPORT_OBJECT_HEADER` in bytes
+    pub const SIZE : usize = 20;
+
+    /// `true` if the signature and version identify a short import object
+    pub fn is_valid(&self) -> bool { self.sig1 == 0 && self.sig2 == 0xFFFF && self.version == 0 }
+
+    #[allow(missing_docs)] pub fn import_type(&self) -> coff::ImportType { coff::ImportType::new((self.type_info & 0x3) as u8) }
+    #[allow(missing_docs)] pub fn name_type(&self) -> coff::ImportNameType { coff::ImportNameType::new(((self.type_info >> 2) & 0x7) as u8) }
+}
+
+/// ⨯ { [header](Self::header), [symbol_name](Self::symbol_name), [dll_name](Self::dll_name), [export_name](Self::export_name) }<br>
+/// A short import object: an [`ImportObjectHeader`] followed by the names it imports<br>
+/// <br>
+///
+/// Import libraries (`.lib` files for DLLs) contain one of these per export, in place of a full COFF object.
+/// The linker synthesizes the `__imp_` symbol and (for [`CODE`](coff::ImportType::CODE)) thunk from them.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#import-library-format>
+#[derive(Clone, Debug)]
+pub struct ImportObject {
+    pub header:         ImportObjectHeader,
+    /// The public symbol name the linker resolves against (e.g. `_Sleep@4`)
+    pub symbol_name:    String,
+    /// The name of the DLL to import from (e.g. `KERNEL32.dll`)
+    pub dll_name:       String,
+    /// The explicit export name, if [`name_type`](ImportObjectHeader::name_type) is [`NAME_EXPORTAS`](coff::ImportNameType::NAME_EXPORTAS)
+    pub export_name:    Option<String>,
+}
+
+/// ∑ [Ordinal](Self::Ordinal) | [Name](Self::Name)<br>
+/// What an [`ImportObject`] imports from its DLL<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportName {
+    Ordinal(u16),
+    Name {
+        /// The name as it appears in the DLL's export name table
+        name:   String,
+        /// Index into the DLL's export name table to try first
+        hint:   u16,
+    },
+}
+
+impl ImportObject {
+    /// Create a short import object of `symbol_name` importing `name` from `dll_name`.
+    ///
+    /// [`name_type`](ImportObjectHeader::name_type) is the simplest that derives `name` from `symbol_name`,
+    /// falling back on [`NAME_EXPORTAS`](coff::ImportNameType::NAME_EXPORTAS) (which older linkers don't understand.)
+    pub fn new(machine: pe::Machine, dll_name: impl Into<String>, symbol_name: impl Into<String>, name: ImportName, import_type: coff::ImportType) -> Self {
+        let mut import = Self {
+            header:         ImportObjectHeader { sig1: 0, sig2: 0xFFFF, version: 0, machine, time_date: pe::TimeDate::UNIX_EPOCH, size_of_data: 0, ordinal_or_hint: 0, type_info: 0 },
+            symbol_name:    symbol_name.into(),
+            dll_name:       dll_name.into(),
+            export_name:    None,
+        };
+        let name_type = match name {
+            ImportName::Ordinal(ordinal) => {
+                import.header.ordinal_or_hint = ordinal;
+                coff::ImportNameType::ORDINAL
+            },
+            ImportName::Name { name, hint } => {
+                import.header.ordinal_or_hint = hint;
+                let types = [coff::ImportNameType::NAME, coff::ImportNameType::NAME_NOPREFIX, coff::ImportNameType::NAME_UNDECORATE];
+                match types.iter().copied().find(|t| { import.set_type_info(*t, import_type); import.import_name() == ImportName::Name { name: name.clone(), hint } }) {
+                    Some(name_type) => name_type,
+                    None => {
+                        import.export_name = Some(name);
+                        coff::ImportNameType::NAME_EXPORTAS
+                    },
+                }
+            },
+        };
+        import.set_type_info(name_type, import_type);
+        import.header.size_of_data = (import.names().map(|n| n.len() + 1).sum::<usize>()) as u32;
+        import
+    }
+
+    /// Serialize this short import object (header included), e.g. as the data of an archive member.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let h = &self.header;
+        let mut data = Vec::new();
+        data.extend_from_slice(&h.sig1.to_le_bytes());
+        data.extend_from_slice(&h.sig2.to_le_bytes());
+        data.extend_from_slice(&h.version.to_le_bytes());
+        data.extend_from_slice(bytes_of(&h.machine));
+        data.extend_from_slice(bytes_of(&h.time_date));
+        data.extend_from_slice(&h.size_of_data.to_le_bytes());
+        data.extend_from_slice(&h.ordinal_or_hint.to_le_bytes());
+        data.extend_from_slice(&h.type_info.to_le_bytes());
+        for name in self.names() {
+            data.extend_from_slice(name.as_bytes());
+            data.push(0);
+        }
+        data
+    }
+
+    fn names(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.symbol_name).chain(std::iter::once(&self.dll_name)).chain(self.export_name.as_ref())
+    }
+
+    fn set_type_info(&mut self, name_type: coff::ImportNameType, import_type: coff::ImportType) {
+        self.header.type_info = u16::from(name_type.to_u8()) << 2 | u16::from(import_type.to_u8());
+    }
+
+    /// Parse a short import object (header included) from the data of an archive member.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the header isn't an `IMPORT_OBJECT_HEADER`
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the names are truncated or unterminated
+    pub fn parse(data: &[u8]) -> io::Result<Self> {
+        let mut r = data;
+        let header = ImportObjectHeader::from_memory(&mut r)?;
+        if !header.is_valid() { return Err(io::Error::new(io::ErrorKind::InvalidData, "coff::ImportObjectHeader::{sig1, sig2, version} != {0, 0xFFFF, 0}")) }
+        let mut r = r.get(..header.size_of_data as usize).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "coff::ImportObjectHeader::size_of_data extends past end of object"))?;
+        let mut take_name = || -> io::Result<String> {
+            let nul = r.iter().position(|b| *b == 0).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "coff::ImportObject name unterminated"))?;
+            let name = String::from_utf8_lossy(&r[..nul]).into_owned();
+            r = &r[nul+1..];
+            Ok(name)
+        };
+        let symbol_name = take_name()?;
+        let dll_name    = take_name()?;
+        let export_name = if header.name_type() == coff::ImportNameType::NAME_EXPORTAS { Some(take_name()?) } else { None };
+        Ok(Self { header, symbol_name, dll_name, export_name })
+    }
+
+    /// What this imports from [`dll_name`](Self::dll_name), applying the [`name_type`](ImportObjectHeader::name_type) rules to [`symbol_name`](Self::symbol_name).
+    ///
+    /// Unknown name types import [`symbol_name`](Self::symbol_name) as-is.
+    pub fn import_name(&self) -> ImportName {
+        let hint = self.header.ordinal_or_hint;
+        let strip_prefix = |name: &str| -> String {
+            let x86 = self.header.machine == pe::Machine::I386;
+            match name.as_bytes().first() {
+                Some(b'?') | Some(b'@')     => name[1..].into(),
+                Some(b'_') if x86           => name[1..].into(),
+                _                           => name.into(),
+            }
+        };
+        let name = match self.header.name_type() {
+            coff::ImportNameType::ORDINAL           => return ImportName::Ordinal(hint),
+            coff::ImportNameType::NAME_NOPREFIX     => strip_prefix(&self.symbol_name),
+            coff::ImportNameType::NAME_UNDECORATE   => strip_prefix(&self.symbol_name).split('@').next().unwrap_or("").into(),
+            coff::ImportNameType::NAME_EXPORTAS     => self.export_name.clone().unwrap_or_default(),
+            _                                       => self.symbol_name.clone(),
+        };
+        ImportName::Name { name, hint }
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(ImportObjectHeader::SIZE, std::mem::size_of::<<ImportObjectHeader as FromMemory>::Raw>());
+}
+
+#[test] fn import_name() {
+    let mut data = b"\0\0\xFF\xFF\0\0\x4C\x01\0\0\0\0\x18\0\0\0\x2A\0\x0C\0".to_vec();
+    data.extend_from_slice(b"_Sleep@4\0KERNEL32.dll\0\0\0");
+    let import = ImportObject::parse(&data[..]).unwrap();
+    assert_eq!(import.header.import_type(), coff::ImportType::CODE);
+    assert_eq!(import.header.name_type(), coff::ImportNameType::NAME_UNDECORATE);
+    assert_eq!(import.dll_name, "KERNEL32.dll");
+    assert_eq!(import.import_name(), ImportName::Name { name: "Sleep".into(), hint: 42 });
+
+    let roundtrip = ImportObject::new(pe::Machine::I386, "KERNEL32.dll", "_Sleep@4", ImportName::Name { name: "Sleep".into(), hint: 42 }, coff::ImportType::CODE);
+    assert_eq!(roundtrip.header.name_type(), coff::ImportNameType::NAME_UNDECORATE);
+    let roundtrip = ImportObject::parse(&roundtrip.to_bytes()[..]).unwrap();
+    assert_eq!((&roundtrip.symbol_name[..], &roundtrip.dll_name[..]), ("_Sleep@4", "KERNEL32.dll"));
+    assert_eq!(roundtrip.import_name(), ImportName::Name { name: "Sleep".into(), hint: 42 });
+}
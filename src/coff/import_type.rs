@@ -0,0 +1,43 @@
+#[cfg(doc)] use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑ [CODE](Self::CODE) | [DATA](Self::DATA) | [CONST](Self::CONST)<br>
+/// [coff::ImportObjectHeader::import_type]: what kind of symbol a short import object imports<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#import-type>
+/// *   `IMPORT_OBJECT_TYPE` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Pod, Zeroable)]
+pub struct ImportType(u8);
+
+impl ImportType {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    pub const fn to_u8(self) -> u8 { self.0 }
+
+    #[doc = "Executable code: defines both `__imp_name` and a `name` thunk" ] pub const CODE    : ImportType = ImportType::new(0);
+    #[doc = "Data: only defines `__imp_name`"                               ] pub const DATA    : ImportType = ImportType::new(1);
+    #[doc = "Specified as `CONSTANT` in the `.def` file"                    ] pub const CONST   : ImportType = ImportType::new(2);
+}
+
+impl Debug for ImportType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::CODE  => "CODE",
+            Self::DATA  => "DATA",
+            Self::CONST => "CONST",
+            other       => return write!(fmt, "ImportType({})", other.0),
+        };
+        write!(fmt, "ImportType::{}", name)
+    }
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+
+
+/// ⨯ { [address_or_symbol](Self::address_or_symbol), [line](Self::line) }<br>
+/// `IMAGE_LINENUMBER`: a COFF line number entry<br>
+/// <br>
+///
+/// Modern toolchains emit CodeView / PDB line information instead, so these are rare outside of very old objects.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#coff-line-numbers-deprecated>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineNumber {
+    /// If [`line`](Self::line) is 0, the symbol table index of the function this run of line numbers belongs to.
+    /// Otherwise, the address of the code for [`line`](Self::line).
+    pub address_or_symbol:  u32,
+    /// 1-based line number relative to the start of the function, or 0 to mark the start of a function
+    pub line:               u16,
+}
+
+impl LineNumber {
+    /// Size of a single `IMAGE_LINENUMBER` in bytes
+    pub const SIZE : usize = 6;
+
+    /// The symbol table index of the function, if this entry marks the start of one
+    pub fn symbol_table_index(&self) -> Option<u32> { if self.line == 0 { Some(self.address_or_symbol) } else { None } }
+
+    /// The address of the code for [`line`](Self::line), unless this entry marks the start of a function
+    pub fn virtual_address(&self) -> Option<u32> { if self.line != 0 { Some(self.address_or_symbol) } else { None } }
+
+    pub(crate) fn parse_table(data: &[u8]) -> io::Result<Vec<Self>> {
+        Ok(data.chunks_exact(Self::SIZE).map(|e| LineNumber {
+            address_or_symbol:  u32::from_le_bytes([e[0], e[1], e[2], e[3]]),
+            line:               u16::from_le_bytes([e[4], e[5]]),
+        }).collect())
+    }
+}
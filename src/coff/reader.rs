@@ -0,0 +1,202 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, SeeklessFile};
+
+use std::convert::*;
+use std::fs::File;
+use std::path::PathBuf;
+
+
+
+/// Wraps a [`ReadAt`] for ease of reading a bare COFF object (`.obj`) by caching [`coff::Header`] and [`pe::SectionHeader`]s.<br>
+/// <br>
+///
+/// Both classic and `/bigobj` ([`coff::BigObjHeader`]) objects are supported.
+pub struct Reader<R> {
+    src:                        Src,
+    reader:                     R,
+    header:                     coff::Header,
+    section_headers:            Vec<pe::SectionHeader>,
+}
+
+impl Reader<SeeklessFile> {
+    /// Open a COFF object and parse the file and section headers.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path);
+        let src = Src::PathBuf(path);
+        Reader::read_src(SeeklessFile::from(src.anno(file, "error opening coff::Reader")?), src)
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    /// Read a COFF object and parse the file and section headers.  Prefer [`Reader::open`] for better error messages.
+    pub fn read(reader: R) -> io::Result<Self> { Self::read_src(reader, Src::Unknown) }
+
+    /// Read the raw data referenced by a [`pe::SectionHeader`].  Uninitialized (`.bss`) sections read as empty.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the [`pe::SectionHeader`] references outside the underlying reader.
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_section_data(&self, header: &pe::SectionHeader) -> io::Result<Vec<u8>> {
+        let ptr = match header.pointer_to_raw_data { None => return Ok(Vec::new()), Some(ptr) => ptr };
+        self.src.anno(self.reader.read_exact_vec_at(header.size_of_raw_data as usize, ptr.get().into()), "error reading COFF section")
+    }
+
+    /// Read the [`coff::Relocation`]s of a [`pe::SectionHeader`].
+    ///
+    /// Handles [`pe::SectionCharacteristics::LNK_NRELOC_OVFL`], where the real count is stored in the first relocation (which is then skipped.)
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the relocations extend outside the underlying reader.
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_relocations(&self, header: &pe::SectionHeader) -> io::Result<Vec<coff::Relocation>> {
+        let ptr = match header.pointer_to_relocations { None => return Ok(Vec::new()), Some(ptr) => u64::from(ptr.get()) };
+        let mut count = usize::from(header.number_of_relocations);
+        let mut skip = 0;
+        if header.characteristics.contains(pe::SectionCharacteristics::LNK_NRELOC_OVFL) && count == 0xFFFF {
+            let mut first = [0u8; coff::Relocation::SIZE];
+            self.src.anno(self.reader.read_exact_at(&mut first[..], ptr), "error reading coff::Relocation")?;
+            count = coff::Relocation::from_record(&first[..]).virtual_address as usize;
+            skip = 1;
+        }
+
+        let size = count.checked_mul(coff::Relocation::SIZE).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "coff::Relocation count exceeds usize::MAX"));
+        let records = self.src.anno(size.and_then(|size| self.reader.read_exact_vec_at(size, ptr)), "error reading coff::Relocation")?;
+        Ok(records.chunks_exact(coff::Relocation::SIZE).skip(skip).map(coff::Relocation::from_record).collect())
+    }
+
+    /// Read the (deprecated) [`coff::LineNumber`]s of a [`pe::SectionHeader`].
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the line numbers extend outside the underlying reader.
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_line_numbers(&self, header: &pe::SectionHeader) -> io::Result<Vec<coff::LineNumber>> {
+        let ptr = match header.pointer_to_linenumbers { None => return Ok(Vec::new()), Some(ptr) => u64::from(ptr.get()) };
+        let records = self.src.anno(self.reader.read_exact_vec_at(usize::from(header.number_of_linenumbers) * coff::LineNumber::SIZE, ptr), "error reading coff::LineNumber")?;
+        self.src.anno(coff::LineNumber::parse_table(&records[..]), "error decoding coff::LineNumber")
+    }
+
+    /// Read the [`coff::SymbolTable`] (including the [`coff::StringTable`] following it.)  Objects without symbols read as empty.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the symbol table or auxiliary records were truncated
+    /// *   [`io::ErrorKind::InvalidData`] if a symbol name referenced outside the string table
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_symbol_table(&self) -> io::Result<coff::SymbolTable> {
+        if self.header.symbols() == 0 { return Ok(coff::SymbolTable::default()) }
+        self.src.anno(coff::SymbolTable::read_from(&self.reader, self.header.symbols().into(), self.header.nsymbols(), self.header.symbol_size()), "error reading coff::SymbolTable")
+    }
+
+    /// Read just the [`coff::StringTable`], e.g. to resolve long section names with [`coff::StringTable::section_name`].
+    pub fn read_string_table(&self) -> io::Result<coff::StringTable> {
+        if self.header.symbols() == 0 { return Ok(coff::StringTable::default()) }
+        self.src.anno(coff::StringTable::read_from(&self.reader, self.header.string_table()), "error reading coff::StringTable")
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
+        let mut offset = 0;
+        let file_header = src.anno(pe::FileHeader::from_read_at_advance(&reader, &mut offset), "error reading pe::FileHeader")?;
+        let header = if file_header.machine == pe::Machine::UNKNOWN && file_header.nsections == 0xFFFF {
+            offset = 0;
+            let bigobj = src.anno(coff::BigObjHeader::from_read_at_advance(&reader, &mut offset), "error reading coff::BigObjHeader")?;
+            if !bigobj.is_valid() {
+                return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "pe::FileHeader looks like an import object or anonymous object header, not a COFF object")), "error reading pe::FileHeader");
+            }
+            coff::Header::BigObj(bigobj)
+        } else if file_header.machine == pe::Machine::new(u16::from_le_bytes(*b"MZ")) {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "pe::FileHeader looks like an mz::Header: this is an executable, not a COFF object")), "error reading pe::FileHeader");
+        } else {
+            offset += u64::from(file_header.optional_header_size); // typically 0 for objects
+            coff::Header::Classic(file_header)
+        };
+
+        // bigobj nsections is an untrusted u32: grow as headers are actually read instead of preallocating
+        let mut section_headers = Vec::new();
+        for _ in 0 .. header.nsections() {
+            section_headers.push(src.anno(pe::SectionHeader::from_read_at_advance(&reader, &mut offset), "error reading pe::SectionHeader")?);
+        }
+
+        Ok(Self { src, reader, header, section_headers })
+    }
+}
+
+impl<R> Reader<R> {
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn header(&self) -> &coff::Header { &self.header }
+
+    /// The classic [`pe::FileHeader`], or [`None`] for [`coff::BigObjHeader`] objects
+    pub fn file_header(&self) -> Option<&pe::FileHeader> { match &self.header { coff::Header::Classic(h) => Some(h), coff::Header::BigObj(_) => None } }
+    #[allow(missing_docs)] pub fn section_headers(&self) -> &[pe::SectionHeader] { &self.section_headers[..] }
+
+    #[allow(missing_docs)] pub fn section_header(&self, idx: impl TryInto<usize>) -> Option<&pe::SectionHeader> {
+        idx.try_into().ok().and_then(|idx| self.section_headers.get(idx))
+    }
+}
+
+
+
+#[cfg(test)] fn bigobj(nsections: u32, symbols: &[u8], strings: &[u8]) -> Vec<u8> {
+    let mut image = Vec::new();
+    image.extend_from_slice(&0u16.to_le_bytes());                   // sig1
+    image.extend_from_slice(&0xFFFFu16.to_le_bytes());              // sig2
+    image.extend_from_slice(&2u16.to_le_bytes());                   // version
+    image.extend_from_slice(&0x8664u16.to_le_bytes());              // machine
+    image.extend_from_slice(&0u32.to_le_bytes());                   // time_date
+    image.extend_from_slice(&coff::BigObjHeader::CLASS_ID);
+    image.extend_from_slice(&[0u8; 16]);                            // size_of_data, flags, meta_data_size, meta_data_offset
+    image.extend_from_slice(&nsections.to_le_bytes());
+    image.extend_from_slice(&(56u32 + 40).to_le_bytes());           // symbols
+    image.extend_from_slice(&((symbols.len() / coff::Symbol::SIZE_EX) as u32).to_le_bytes());
+    let mut text = [0u8; 40];
+    text[..5].copy_from_slice(b".text");
+    image.extend_from_slice(&text);
+    image.extend_from_slice(symbols);
+    image.extend_from_slice(strings);
+    image
+}
+
+#[test] fn read_bigobj() {
+    let mut symbols = Vec::new();
+    symbols.extend_from_slice(b".text\0\0\0\0\0\0\0\x01\0\0\0\0\0\x03\x01");                      // section 1, STATIC, 1 aux
+    symbols.extend_from_slice(b"\x10\0\0\0\x02\0\0\0\x78\x56\x34\x12\x01\0\x02\0\x01\0\0\0");    // length 0x10, 2 relocs, number 0x10001
+    symbols.extend_from_slice(b"\0\0\0\0\x04\0\0\0\x08\0\0\0\x02\0\x01\0\x20\0\x02\0");          // long name, section 0x10002, function, EXTERNAL
+    let image = bigobj(1, &symbols[..], b"\x15\0\0\0long_symbol_name\0");
+
+    let coff = Reader::read(MemoryReadAt(image)).unwrap();
+    assert!(coff.header().is_bigobj());
+    assert_eq!(coff.section_headers().len(), 1);
+    let table = coff.read_symbol_table().unwrap();
+    assert_eq!(table.symbols.len(), 2);
+    assert_eq!((&table.symbols[0].name[..], table.symbols[0].section_number), (".text", 1));
+    assert_eq!(table.symbols[0].aux, vec![coff::AuxSymbol::SectionDefinition {
+        length: 0x10, number_of_relocations: 2, number_of_linenumbers: 0, checksum: 0x12345678, number: 0x10001, selection: coff::ComdatSelection::new(2),
+    }]);
+    assert_eq!((table.symbols[1].index, &table.symbols[1].name[..], table.symbols[1].value, table.symbols[1].section_number), (2, "long_symbol_name", 8, 0x10002));
+    assert!(table.symbols[1].is_function());
+}
+
+#[test] fn read_bigobj_nsections() {
+    let image = bigobj(u32::MAX, &[], &[]);
+    assert_eq!(image.len(), 96);
+    assert_eq!(Reader::read(MemoryReadAt(image)).map(|_| ()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof); // doesn't try to allocate 160 GB
+}
+
+#[test] fn read_bigobj_truncated() {
+    let mut image = bigobj(1, &[], b"\xFF\xFF\xFF\xFF");
+    assert_eq!(Reader::read(MemoryReadAt(image.clone())).unwrap().read_string_table().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    image[52 .. 56].copy_from_slice(&u32::MAX.to_le_bytes()); // nsymbols
+    assert_eq!(Reader::read(MemoryReadAt(image)).unwrap().read_symbol_table().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test] fn read_section_truncated() {
+    let coff = Reader::read(MemoryReadAt(bigobj(1, b"\xFF\xFF\xFF\xFF\0\0\0\0\0\0", &[]))).unwrap();
+    let ptr = std::num::NonZeroU32::new(96);
+    let data = pe::SectionHeader { size_of_raw_data: u32::MAX, pointer_to_raw_data: ptr, .. coff.section_headers()[0] };
+    assert_eq!(coff.read_section_data(&data).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    let relocs = pe::SectionHeader { pointer_to_relocations: ptr, number_of_relocations: 0xFFFF, characteristics: pe::SectionCharacteristics::LNK_NRELOC_OVFL, .. coff.section_headers()[0] };
+    assert_eq!(coff.read_relocations(&relocs).unwrap_err().kind(), io::ErrorKind::UnexpectedEof); // u32::MAX relocations
+    let lines = pe::SectionHeader { pointer_to_linenumbers: ptr, number_of_linenumbers: 0xFFFF, .. coff.section_headers()[0] };
+    assert_eq!(coff.read_line_numbers(&lines).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,155 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ⨯ { [virtual_address](Self::virtual_address), [symbol_table_index](Self::symbol_table_index), [kind](Self::kind) }<br>
+/// `IMAGE_RELOCATION`: patch a location within a section with (some function of) the address of a symbol<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#coff-relocations-object-only>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Relocation {
+    /// Offset of the location to patch, relative to the start of the section (plus [`pe::SectionHeader::virtual_address`], typically 0 for objects)
+    pub virtual_address:    u32,
+    /// Index into the COFF symbol table of the target
+    pub symbol_table_index: u32,
+    pub kind:               RelocationType,
+}
+
+impl Relocation {
+    /// Size of a single `IMAGE_RELOCATION` in bytes
+    pub const SIZE : usize = 10;
+
+    pub(crate) fn from_record(r: &[u8]) -> Self {
+        Relocation {
+            virtual_address:    u32::from_le_bytes([r[0], r[1], r[2], r[3]]),
+            symbol_table_index: u32::from_le_bytes([r[4], r[5], r[6], r[7]]),
+            kind:               RelocationType::new(u16::from_le_bytes([r[8], r[9]])),
+        }
+    }
+}
+
+/// [`u16`]: the [`pe::Machine`]-specific kind of a [`Relocation`], such as `IMAGE_REL_AMD64_REL32`<br>
+/// <br>
+///
+/// The same value means different things for different machines: use [`name`](Self::name) to decode it.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#type-indicators>
+/// *   `IMAGE_REL_*` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Pod, Zeroable)]
+pub struct RelocationType(u16);
+
+impl RelocationType {
+    pub const fn new(value: u16) -> Self { Self(value) }
+
+    pub const fn to_u16(self) -> u16 { self.0 }
+
+    /// The `IMAGE_REL_*` name of this relocation type for `machine`, e.g. `"IMAGE_REL_AMD64_REL32"`, if known.
+    pub fn name(self, machine: pe::Machine) -> Option<&'static str> {
+        let table : &[(u16, &str)] = match machine {
+            pe::Machine::I386   => I386,
+            pe::Machine::AMD64  => AMD64,
+            pe::Machine::ARM64  => ARM64,
+            pe::Machine::ARM | pe::Machine::THUMB | pe::Machine::ARMNT => ARM,
+            _ => return None,
+        };
+        table.iter().find(|(v, _)| *v == self.0).map(|(_, name)| *name)
+    }
+}
+
+impl Debug for RelocationType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "RelocationType(0x{:04x})", self.0)
+    }
+}
+
+const I386 : &[(u16, &str)] = &[
+    (0x0000, "IMAGE_REL_I386_ABSOLUTE"),
+    (0x0001, "IMAGE_REL_I386_DIR16"),
+    (0x0002, "IMAGE_REL_I386_REL16"),
+    (0x0006, "IMAGE_REL_I386_DIR32"),
+    (0x0007, "IMAGE_REL_I386_DIR32NB"),
+    (0x0009, "IMAGE_REL_I386_SEG12"),
+    (0x000A, "IMAGE_REL_I386_SECTION"),
+    (0x000B, "IMAGE_REL_I386_SECREL"),
+    (0x000C, "IMAGE_REL_I386_TOKEN"),
+    (0x000D, "IMAGE_REL_I386_SECREL7"),
+    (0x0014, "IMAGE_REL_I386_REL32"),
+];
+
+const AMD64 : &[(u16, &str)] = &[
+    (0x0000, "IMAGE_REL_AMD64_ABSOLUTE"),
+    (0x0001, "IMAGE_REL_AMD64_ADDR64"),
+    (0x0002, "IMAGE_REL_AMD64_ADDR32"),
+    (0x0003, "IMAGE_REL_AMD64_ADDR32NB"),
+    (0x0004, "IMAGE_REL_AMD64_REL32"),
+    (0x0005, "IMAGE_REL_AMD64_REL32_1"),
+    (0x0006, "IMAGE_REL_AMD64_REL32_2"),
+    (0x0007, "IMAGE_REL_AMD64_REL32_3"),
+    (0x0008, "IMAGE_REL_AMD64_REL32_4"),
+    (0x0009, "IMAGE_REL_AMD64_REL32_5"),
+    (0x000A, "IMAGE_REL_AMD64_SECTION"),
+    (0x000B, "IMAGE_REL_AMD64_SECREL"),
+    (0x000C, "IMAGE_REL_AMD64_SECREL7"),
+    (0x000D, "IMAGE_REL_AMD64_TOKEN"),
+    (0x000E, "IMAGE_REL_AMD64_SREL32"),
+    (0x000F, "IMAGE_REL_AMD64_PAIR"),
+    (0x0010, "IMAGE_REL_AMD64_SSPAN32"),
+];
+
+const ARM : &[(u16, &str)] = &[
+    (0x0000, "IMAGE_REL_ARM_ABSOLUTE"),
+    (0x0001, "IMAGE_REL_ARM_ADDR32"),
+    (0x0002, "IMAGE_REL_ARM_ADDR32NB"),
+    (0x0003, "IMAGE_REL_ARM_BRANCH24"),
+    (0x0004, "IMAGE_REL_ARM_BRANCH11"),
+    (0x0005, "IMAGE_REL_ARM_TOKEN"),
+    (0x0008, "IMAGE_REL_ARM_BLX24"),
+    (0x0009, "IMAGE_REL_ARM_BLX11"),
+    (0x000A, "IMAGE_REL_ARM_REL32"),
+    (0x000E, "IMAGE_REL_ARM_SECTION"),
+    (0x000F, "IMAGE_REL_ARM_SECREL"),
+    (0x0010, "IMAGE_REL_ARM_MOV32"),
+    (0x0011, "IMAGE_REL_THUMB_MOV32"),
+    (0x0012, "IMAGE_REL_THUMB_BRANCH20"),
+    (0x0014, "IMAGE_REL_THUMB_BRANCH24"),
+    (0x0015, "IMAGE_REL_THUMB_BLX23"),
+    (0x0016, "IMAGE_REL_ARM_PAIR"),
+];
+
+const ARM64 : &[(u16, &str)] = &[
+    (0x0000, "IMAGE_REL_ARM64_ABSOLUTE"),
+    (0x0001, "IMAGE_REL_ARM64_ADDR32"),
+    (0x0002, "IMAGE_REL_ARM64_ADDR32NB"),
+    (0x0003, "IMAGE_REL_ARM64_BRANCH26"),
+    (0x0004, "IMAGE_REL_ARM64_PAGEBASE_REL21"),
+    (0x0005, "IMAGE_REL_ARM64_REL21"),
+    (0x0006, "IMAGE_REL_ARM64_PAGEOFFSET_12A"),
+    (0x0007, "IMAGE_REL_ARM64_PAGEOFFSET_12L"),
+    (0x0008, "IMAGE_REL_ARM64_SECREL"),
+    (0x0009, "IMAGE_REL_ARM64_SECREL_LOW12A"),
+    (0x000A, "IMAGE_REL_ARM64_SECREL_HIGH12A"),
+    (0x000B, "IMAGE_REL_ARM64_SECREL_LOW12L"),
+    (0x000C, "IMAGE_REL_ARM64_TOKEN"),
+    (0x000D, "IMAGE_REL_ARM64_SECTION"),
+    (0x000E, "IMAGE_REL_ARM64_ADDR64"),
+    (0x000F, "IMAGE_REL_ARM64_BRANCH19"),
+    (0x0010, "IMAGE_REL_ARM64_BRANCH14"),
+    (0x0011, "IMAGE_REL_ARM64_REL32"),
+];
+
+
+
+#[test] fn name() {
+    assert_eq!(RelocationType::new(4).name(pe::Machine::AMD64), Some("IMAGE_REL_AMD64_REL32"));
+    assert_eq!(RelocationType::new(4).name(pe::Machine::ARM64), Some("IMAGE_REL_ARM64_PAGEBASE_REL21"));
+    assert_eq!(RelocationType::new(4).name(pe::Machine::IA64), None);
+}
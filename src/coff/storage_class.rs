@@ -0,0 +1,97 @@
+#[cfg(doc)] use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑
+/// [EXTERNAL](Self::EXTERNAL) |
+/// [STATIC](Self::STATIC) |
+/// [FUNCTION](Self::FUNCTION) |
+/// [FILE](Self::FILE) |
+/// [WEAK_EXTERNAL](Self::WEAK_EXTERNAL) |
+/// ...<br>
+/// [coff::Symbol::storage_class]: what kind of definition a symbol represents<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#storage-class>
+/// *   `IMAGE_SYM_CLASS_*` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[derive(Pod, Zeroable)]
+pub struct StorageClass(u8);
+
+impl StorageClass {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    pub const fn to_u8(self) -> u8 { self.0 }
+
+    #[doc = "Special symbol representing the end of a function, for debugging"  ] pub const END_OF_FUNCTION     : StorageClass = StorageClass::new(0xFF);
+    #[doc = "No assigned storage class"                                         ] pub const NULL                : StorageClass = StorageClass::new(0);
+    #[doc = "Automatic (stack) variable; value is the stack frame offset"       ] pub const AUTOMATIC           : StorageClass = StorageClass::new(1);
+    #[doc = "External symbol: defined if section number ≠ 0, otherwise undefined"] pub const EXTERNAL           : StorageClass = StorageClass::new(2);
+    #[doc = "Static symbol: value is the offset within its section (0 for section names)"] pub const STATIC      : StorageClass = StorageClass::new(3);
+    #[doc = "Register variable"                                                 ] pub const REGISTER            : StorageClass = StorageClass::new(4);
+    #[doc = "Externally defined symbol"                                         ] pub const EXTERNAL_DEF        : StorageClass = StorageClass::new(5);
+    #[doc = "Code label"                                                        ] pub const LABEL               : StorageClass = StorageClass::new(6);
+    #[doc = "Reference to an undefined code label"                              ] pub const UNDEFINED_LABEL     : StorageClass = StorageClass::new(7);
+    #[doc = "Structure member"                                                  ] pub const MEMBER_OF_STRUCT    : StorageClass = StorageClass::new(8);
+    #[doc = "Formal argument of a function"                                     ] pub const ARGUMENT            : StorageClass = StorageClass::new(9);
+    #[doc = "Structure tag-name entry"                                          ] pub const STRUCT_TAG          : StorageClass = StorageClass::new(10);
+    #[doc = "Union member"                                                      ] pub const MEMBER_OF_UNION     : StorageClass = StorageClass::new(11);
+    #[doc = "Union tag-name entry"                                              ] pub const UNION_TAG           : StorageClass = StorageClass::new(12);
+    #[doc = "Typedef entry"                                                     ] pub const TYPE_DEFINITION     : StorageClass = StorageClass::new(13);
+    #[doc = "Static data declaration"                                           ] pub const UNDEFINED_STATIC    : StorageClass = StorageClass::new(14);
+    #[doc = "Enumerated type tagname entry"                                     ] pub const ENUM_TAG            : StorageClass = StorageClass::new(15);
+    #[doc = "Enumeration member"                                                ] pub const MEMBER_OF_ENUM      : StorageClass = StorageClass::new(16);
+    #[doc = "Register parameter"                                                ] pub const REGISTER_PARAM      : StorageClass = StorageClass::new(17);
+    #[doc = "Bit-field reference"                                               ] pub const BIT_FIELD           : StorageClass = StorageClass::new(18);
+    #[doc = "`.bb` (beginning of block) or `.eb` (end of block)"                ] pub const BLOCK               : StorageClass = StorageClass::new(100);
+    #[doc = "`.bf` (begin function), `.ef` (end function), or `.lf` (lines in function)"] pub const FUNCTION    : StorageClass = StorageClass::new(101);
+    #[doc = "End-of-structure entry"                                            ] pub const END_OF_STRUCT       : StorageClass = StorageClass::new(102);
+    #[doc = "Source file name, in the following auxiliary records"              ] pub const FILE                : StorageClass = StorageClass::new(103);
+    #[doc = "Section definition (unused, Microsoft tools use STATIC instead)"   ] pub const SECTION             : StorageClass = StorageClass::new(104);
+    #[doc = "Weak external"                                                     ] pub const WEAK_EXTERNAL       : StorageClass = StorageClass::new(105);
+    #[doc = "CLR token"                                                         ] pub const CLR_TOKEN           : StorageClass = StorageClass::new(107);
+}
+
+impl Debug for StorageClass {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::END_OF_FUNCTION   => "END_OF_FUNCTION",
+            Self::NULL              => "NULL",
+            Self::AUTOMATIC         => "AUTOMATIC",
+            Self::EXTERNAL          => "EXTERNAL",
+            Self::STATIC            => "STATIC",
+            Self::REGISTER          => "REGISTER",
+            Self::EXTERNAL_DEF      => "EXTERNAL_DEF",
+            Self::LABEL             => "LABEL",
+            Self::UNDEFINED_LABEL   => "UNDEFINED_LABEL",
+            Self::MEMBER_OF_STRUCT  => "MEMBER_OF_STRUCT",
+            Self::ARGUMENT          => "ARGUMENT",
+            Self::STRUCT_TAG        => "STRUCT_TAG",
+            Self::MEMBER_OF_UNION   => "MEMBER_OF_UNION",
+            Self::UNION_TAG         => "UNION_TAG",
+            Self::TYPE_DEFINITION   => "TYPE_DEFINITION",
+            Self::UNDEFINED_STATIC  => "UNDEFINED_STATIC",
+            Self::ENUM_TAG          => "ENUM_TAG",
+            Self::MEMBER_OF_ENUM    => "MEMBER_OF_ENUM",
+            Self::REGISTER_PARAM    => "REGISTER_PARAM",
+            Self::BIT_FIELD         => "BIT_FIELD",
+            Self::BLOCK             => "BLOCK",
+            Self::FUNCTION          => "FUNCTION",
+            Self::END_OF_STRUCT     => "END_OF_STRUCT",
+            Self::FILE              => "FILE",
+            Self::SECTION           => "SECTION",
+            Self::WEAK_EXTERNAL     => "WEAK_EXTERNAL",
+            Self::CLR_TOKEN         => "CLR_TOKEN",
+            other                   => return write!(fmt, "StorageClass({})", other.0),
+        };
+        write!(fmt, "StorageClass::{}", name)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::*;
+use crate::io::ReadAtExt;
+
+use maulingmonkey_io_adapters::ReadAt;
+
+use std::borrow::Cow;
+use std::convert::*;
+use std::io;
+
+
+
+/// The COFF string table, immediately following the symbol table: long [`coff::Symbol`] names and `/123`-style long section names<br>
+/// <br>
+///
+/// Offsets are relative to the start of the table, which begins with its own 4-byte size - so the first string is at offset 4.
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#coff-string-table>
+#[derive(Clone, Debug, Default)]
+pub struct StringTable {
+    data: Vec<u8>,
+}
+
+impl StringTable {
+    /// Create a string table from its raw bytes, including the leading 4-byte size.
+    pub fn from_bytes(data: Vec<u8>) -> Self { Self { data } }
+
+    /// The raw bytes of the table, including the leading 4-byte size.
+    pub fn as_bytes(&self) -> &[u8] { &self.data[..] }
+
+    /// The `\0`-terminated string at `offset`, without the terminator, or [`None`] if `offset` is out of bounds.
+    pub fn get(&self, offset: u32) -> Option<&[u8]> {
+        if offset < 4 { return None }
+        let s = self.data.get(offset as usize ..)?;
+        Some(&s[..s.iter().position(|b| *b == 0).unwrap_or(s.len())])
+    }
+
+    /// The name of a section, resolving `/123` (decimal) and `//BASE64` long section names through this table.
+    ///
+    /// Names that can't be resolved are returned as-is.
+    pub fn section_name<'h>(&self, header: &'h pe::SectionHeader) -> Cow<'h, str> {
+        match long_name_offset(header.name.to_bytes()).and_then(|o| self.get(o)) {
+            Some(name)  => Cow::Owned(String::from_utf8_lossy(name).into_owned()),
+            None        => header.name.to_string_lossy(),
+        }
+    }
+
+    /// Read the string table at `offset` (the end of the symbol table.)  A missing table (end of file) reads as empty.
+    pub(crate) fn read_from(read_at: &impl ReadAt, offset: u64) -> io::Result<Self> {
+        let mut size = [0u8; 4];
+        if read_at.read_up_to_at(&mut size[..], offset)? < 4 { return Ok(Self::default()) }
+        let size = u32::from_le_bytes(size);
+        if size < 4 { return Ok(Self::default()) }
+        let data = read_at.read_exact_vec_at(size as usize, offset)?;
+        Ok(Self { data })
+    }
+}
+
+/// Parse a `/123` (decimal) or `//BASE64` long section name into a string table offset
+fn long_name_offset(name: &[u8]) -> Option<u32> {
+    if let Some(b64) = name.strip_prefix(b"//") {
+        b64.iter().try_fold(0u64, |o, c| {
+            let digit = match c {
+                b'A' ..= b'Z' => c - b'A',
+                b'a' ..= b'z' => c - b'a' + 26,
+                b'0' ..= b'9' => c - b'0' + 52,
+                b'+'          => 62,
+                b'/'          => 63,
+                _             => return None,
+            };
+            Some(o * 64 + u64::from(digit))
+        }).and_then(|o| u32::try_from(o).ok())
+    } else if let Some(dec) = name.strip_prefix(b"/") {
+        if dec.is_empty() || !dec.iter().all(u8::is_ascii_digit) { return None }
+        std::str::from_utf8(dec).ok()?.parse().ok()
+    } else {
+        None
+    }
+}
+
+
+
+#[test] fn section_name() {
+    let strings = StringTable::from_bytes(b"\x16\x00\x00\x00.debug_info\x00.text\x00".to_vec());
+    let section = |name: &[u8]| {
+        let mut buf = [0u8; 8];
+        buf[..name.len()].copy_from_slice(name);
+        pe::SectionHeader { name: bytemuck::cast(buf), .. Default::default() }
+    };
+    assert_eq!(strings.section_name(&section(b"/4")),       ".debug_info");
+    assert_eq!(strings.section_name(&section(b"/16")),      ".text");
+    assert_eq!(strings.section_name(&section(b"//AAAAAQ")), ".text");
+    assert_eq!(strings.section_name(&section(b".data")),    ".data");
+    assert_eq!(strings.section_name(&section(b"/99")),      "/99");
+    assert_eq!(strings.section_name(&section(b"/x")),       "/x");
+}
@@ -0,0 +1,228 @@
+use crate::*;
+use crate::io::ReadAtExt;
+
+use maulingmonkey_io_adapters::ReadAt;
+
+use std::io;
+
+
+
+/// ⨯ { [index](Self::index), [name](Self::name), [value](Self::value), [section_number](Self::section_number), [storage_class](Self::storage_class), [aux](Self::aux), ... }<br>
+/// `IMAGE_SYMBOL` / `IMAGE_SYMBOL_EX`: an entry of the COFF symbol table, with its auxiliary records decoded<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#coff-symbol-table>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    /// Index of this symbol in the symbol table, as referenced by [`coff::Relocation::symbol_table_index`].
+    /// Auxiliary records occupy indicies too, so these aren't contiguous.
+    pub index:          u32,
+    /// The name, resolved through the [`coff::StringTable`] if long.  [`FILE`](coff::StorageClass::FILE) symbols are named `".file"`: see [`coff::AuxSymbol::File`].
+    pub name:           String,
+    /// Meaning depends on [`section_number`](Self::section_number) and [`storage_class`](Self::storage_class), but typically an offset within the section
+    pub value:          u32,
+    /// 1-based section number, or one of [`Symbol::UNDEFINED`], [`Symbol::ABSOLUTE`], [`Symbol::DEBUG`]
+    pub section_number: i32,
+    /// Base type (low byte) and complex type (bits 4-5): `0x20` for functions, `0x00` for everything else in practice
+    pub symbol_type:    u16,
+    pub storage_class:  coff::StorageClass,
+    pub aux:            Vec<AuxSymbol>,
+}
+
+/// ∑ [FunctionDefinition](Self::FunctionDefinition) | [BeginEndFunction](Self::BeginEndFunction) | [WeakExternal](Self::WeakExternal) | [File](Self::File) | [SectionDefinition](Self::SectionDefinition) | [ClrToken](Self::ClrToken) | [Unknown](Self::Unknown)<br>
+/// A decoded auxiliary symbol record<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#auxiliary-symbol-records>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuxSymbol {
+    /// Format 1: follows an [`EXTERNAL`](coff::StorageClass::EXTERNAL) function definition
+    FunctionDefinition {
+        /// Symbol table index of the corresponding `.bf` symbol
+        tag_index:                  u32,
+        /// Size of the function's code
+        total_size:                 u32,
+        /// File offset of the function's [`coff::LineNumber`]s, or 0
+        pointer_to_linenumber:      u32,
+        /// Symbol table index of the next function, or 0 for the last
+        pointer_to_next_function:   u32,
+    },
+    /// Format 2: follows a `.bf` or `.ef` [`FUNCTION`](coff::StorageClass::FUNCTION) symbol
+    BeginEndFunction {
+        /// 1-based source line number
+        line_number:                u16,
+        /// Symbol table index of the next `.bf`, or 0 (`.bf` only)
+        pointer_to_next_function:   u32,
+    },
+    /// Format 3: follows a weak external
+    WeakExternal {
+        /// Symbol table index of the symbol to link if the weak external isn't otherwise defined
+        tag_index:                  u32,
+        /// `IMAGE_WEAK_EXTERN_SEARCH_*`: 1 = NOLIBRARY, 2 = LIBRARY, 3 = ALIAS, 4 = ANTI_DEPENDENCY
+        characteristics:            u32,
+    },
+    /// Format 4: follows a [`FILE`](coff::StorageClass::FILE) symbol.  Spans every auxiliary record of the symbol.
+    File {
+        name:                       String,
+    },
+    /// Format 5: follows the [`STATIC`](coff::StorageClass::STATIC) symbol naming a section
+    SectionDefinition {
+        /// Size of the section data ([`pe::SectionHeader::size_of_raw_data`])
+        length:                     u32,
+        number_of_relocations:      u16,
+        number_of_linenumbers:      u16,
+        /// Checksum of the section data, for COMDAT matching
+        checksum:                   u32,
+        /// 1-based section number of the associated section, for [`coff::ComdatSelection::ASSOCIATIVE`]
+        number:                     u32,
+        selection:                  coff::ComdatSelection,
+    },
+    /// Follows a [`CLR_TOKEN`](coff::StorageClass::CLR_TOKEN) symbol
+    ClrToken {
+        /// Symbol table index of the corresponding CLR token definition
+        symbol_table_index:         u32,
+    },
+    /// An auxiliary record of a symbol with no recognized format
+    Unknown(Vec<u8>),
+}
+
+impl Symbol {
+    /// Size of a single `IMAGE_SYMBOL` (or auxiliary record) in bytes
+    pub const SIZE : usize = 18;
+
+    /// Size of a single `IMAGE_SYMBOL_EX` (or auxiliary record) in bytes, as used by [`coff::BigObjHeader`] objects
+    pub const SIZE_EX : usize = 20;
+
+    /// [`section_number`](Self::section_number): an external symbol, defined elsewhere (unless [`value`](Self::value) ≠ 0, in which case it's a common symbol of that size)
+    pub const UNDEFINED : i32 = 0;
+    /// [`section_number`](Self::section_number): [`value`](Self::value) is an absolute, non-relocatable value
+    pub const ABSOLUTE  : i32 = -1;
+    /// [`section_number`](Self::section_number): a debugging symbol
+    pub const DEBUG     : i32 = -2;
+
+    /// `true` if [`symbol_type`](Self::symbol_type) indicates a function
+    pub fn is_function(&self) -> bool { self.symbol_type & 0x30 == 0x20 }
+
+    /// The 0-based index of this symbol's section in [`coff::Reader::section_headers`], if [`section_number`](Self::section_number) refers to a section
+    pub fn section_index(&self) -> Option<usize> {
+        if self.section_number > 0 { Some(self.section_number as usize - 1) } else { None }
+    }
+
+    fn decode_aux(&self, records: &[&[u8]]) -> Vec<AuxSymbol> {
+        let d = |r: &[u8], i: usize| u32::from_le_bytes([r[i], r[i+1], r[i+2], r[i+3]]);
+        let w = |r: &[u8], i: usize| u16::from_le_bytes([r[i], r[i+1]]);
+
+        if self.storage_class == coff::StorageClass::FILE {
+            let name = records.iter().flat_map(|r| r.iter().copied()).take_while(|b| *b != 0).collect::<Vec<u8>>();
+            return vec![AuxSymbol::File { name: String::from_utf8_lossy(&name[..]).into_owned() }];
+        }
+
+        records.iter().map(|r| match self.storage_class {
+            coff::StorageClass::EXTERNAL if self.is_function() && self.section_number > 0 => AuxSymbol::FunctionDefinition {
+                tag_index: d(r, 0), total_size: d(r, 4), pointer_to_linenumber: d(r, 8), pointer_to_next_function: d(r, 12),
+            },
+            coff::StorageClass::FUNCTION => AuxSymbol::BeginEndFunction {
+                line_number: w(r, 4), pointer_to_next_function: d(r, 12),
+            },
+            coff::StorageClass::WEAK_EXTERNAL => AuxSymbol::WeakExternal { tag_index: d(r, 0), characteristics: d(r, 4) },
+            coff::StorageClass::EXTERNAL if self.section_number == Self::UNDEFINED && self.value == 0 => AuxSymbol::WeakExternal { tag_index: d(r, 0), characteristics: d(r, 4) },
+            coff::StorageClass::STATIC if self.value == 0 && self.section_number > 0 => AuxSymbol::SectionDefinition {
+                length:                 d(r, 0),
+                number_of_relocations:  w(r, 4),
+                number_of_linenumbers:  w(r, 6),
+                checksum:               d(r, 8),
+                number:                 u32::from(w(r, 12)) | if r.len() >= 20 { u32::from(w(r, 16)) << 16 } else { 0 },
+                selection:              coff::ComdatSelection::new(r[14]),
+            },
+            coff::StorageClass::CLR_TOKEN => AuxSymbol::ClrToken { symbol_table_index: d(r, 4) },
+            _ => AuxSymbol::Unknown(r.to_vec()),
+        }).collect()
+    }
+}
+
+/// ⨯ { [symbols](Self::symbols), [strings](Self::strings) }<br>
+/// The COFF symbol table and the [`coff::StringTable`] following it<br>
+/// <br>
+#[derive(Clone, Debug, Default)]
+pub struct SymbolTable {
+    /// Every symbol, in order, with auxiliary records folded into [`coff::Symbol::aux`]
+    pub symbols:    Vec<Symbol>,
+    pub strings:    coff::StringTable,
+}
+
+impl SymbolTable {
+    /// Find a symbol by [`coff::Symbol::index`], as referenced by [`coff::Relocation::symbol_table_index`]
+    pub fn get(&self, index: u32) -> Option<&Symbol> {
+        self.symbols.binary_search_by_key(&index, |s| s.index).ok().map(|i| &self.symbols[i])
+    }
+
+    /// Read `nsymbols` records of `record_size` ([`Symbol::SIZE`] or [`Symbol::SIZE_EX`]) bytes at `offset`, followed by the string table
+    pub(crate) fn read_from(read_at: &impl ReadAt, offset: u64, nsymbols: u32, record_size: usize) -> io::Result<Self> {
+        let size = (nsymbols as usize).checked_mul(record_size).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "coff::SymbolTable size exceeds usize::MAX"))?;
+        let records = read_at.read_exact_vec_at(size, offset)?; // nsymbols is untrusted: don't preallocate
+        let strings = coff::StringTable::read_from(read_at, offset + records.len() as u64)?;
+        let symbols = Self::parse(&records[..], record_size, &strings)?;
+        Ok(Self { symbols, strings })
+    }
+
+    fn parse(data: &[u8], record_size: usize, strings: &coff::StringTable) -> io::Result<Vec<Symbol>> {
+        let records = data.chunks_exact(record_size).collect::<Vec<_>>();
+        let mut symbols = Vec::new();
+        let mut index = 0;
+        while let Some(r) = records.get(index) {
+            let naux = usize::from(r[record_size - 1]);
+            let aux = records.get(index + 1 .. index + 1 + naux).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "coff::Symbol auxiliary records extend past end of symbol table"))?;
+
+            let name = if r[..4] == [0, 0, 0, 0] {
+                let offset = u32::from_le_bytes([r[4], r[5], r[6], r[7]]);
+                let name = strings.get(offset).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("coff::Symbol name offset {} out of bounds of string table", offset)))?;
+                String::from_utf8_lossy(name).into_owned()
+            } else {
+                String::from_utf8_lossy(&r[..r[..8].iter().position(|b| *b == 0).unwrap_or(8)]).into_owned()
+            };
+
+            let (section_number, rest) = if record_size == Symbol::SIZE_EX {
+                (i32::from_le_bytes([r[12], r[13], r[14], r[15]]), &r[16..])
+            } else {
+                (i32::from(i16::from_le_bytes([r[12], r[13]])), &r[14..])
+            };
+            let mut symbol = Symbol {
+                index:          index as u32,
+                name,
+                value:          u32::from_le_bytes([r[8], r[9], r[10], r[11]]),
+                section_number,
+                symbol_type:    u16::from_le_bytes([rest[0], rest[1]]),
+                storage_class:  coff::StorageClass::new(rest[2]),
+                aux:            Vec::new(),
+            };
+            symbol.aux = symbol.decode_aux(aux);
+            symbols.push(symbol);
+            index += 1 + naux;
+        }
+        Ok(symbols)
+    }
+}
+
+
+
+#[test] fn parse() {
+    let strings = coff::StringTable::from_bytes(b"\x15\x00\x00\x00long_symbol_name\x00".to_vec());
+    let mut data = Vec::new();
+    data.extend_from_slice(b".file\0\0\0\0\0\0\0\xFE\xFF\x00\x00\x67\x01");
+    data.extend_from_slice(b"foo.c\0\0\0\0\0\0\0\0\0\0\0\0\0");
+    data.extend_from_slice(b"\0\0\0\0\x04\0\0\0\x10\0\0\0\x01\0\x20\0\x02\x00");
+    data.extend_from_slice(b".text\0\0\0\0\0\0\0\x01\0\0\0\x03\x01");
+    data.extend_from_slice(b"\x20\0\0\0\x02\0\0\0\x78\x56\x34\x12\0\0\x02\0\0\0");
+
+    let symbols = SymbolTable::parse(&data[..], Symbol::SIZE, &strings).unwrap();
+    assert_eq!(symbols.len(), 3);
+    assert_eq!(symbols[0].aux, vec![AuxSymbol::File { name: "foo.c".into() }]);
+    assert_eq!((symbols[1].index, &symbols[1].name[..], symbols[1].value, symbols[1].section_number), (2, "long_symbol_name", 0x10, 1));
+    assert!(symbols[1].is_function());
+    assert_eq!(symbols[2].index, 3);
+    assert_eq!(symbols[2].aux, vec![AuxSymbol::SectionDefinition {
+        length: 0x20, number_of_relocations: 2, number_of_linenumbers: 0, checksum: 0x12345678, number: 0, selection: coff::ComdatSelection::ANY,
+    }]);
+}
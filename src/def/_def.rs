@@ -0,0 +1,18 @@
+//! **Def**inition files (`.def`): module-definition statements describing a DLL's name and exports for `link.exe`, `lib.exe`, and `dlltool`.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`ModuleDefinition`]  | A parsed `.def` file.  Parse with [`str::parse`], write with [`Display`](std::fmt::Display).
+//! | [`Export`]            | An `EXPORTS` entry: `name[=internal_name] [@ordinal [NONAME]] [PRIVATE] [DATA]`.
+//! | [`Section`]           | A `SECTIONS` entry: a section name and its overridden attributes.
+//! | [`ModuleKind`]        | Whether the module was declared as a `LIBRARY` (DLL) or `NAME` (executable.)
+//! | [`Size`]              | `HEAPSIZE` / `STACKSIZE` reserve and commit sizes.
+//!
+//! ## References
+//! *   <https://learn.microsoft.com/en-us/cpp/build/reference/module-definition-dot-def-files>
+//! *   <https://sourceware.org/binutils/docs/binutils/def-file-format.html>
+
+mod export;                         pub use export::*;
+mod module_definition;              pub use module_definition::*;
+mod parse;
+mod section;                        pub use section::*;
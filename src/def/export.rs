@@ -0,0 +1,36 @@
+/// ⨯ { [name](Self::name), [internal_name](Self::internal_name), [ordinal](Self::ordinal), [noname](Self::noname), [private](Self::private), [data](Self::data), [constant](Self::constant) }<br>
+/// An `EXPORTS` entry of a `.def` file<br>
+/// <br>
+///
+/// ```text
+/// entryname[=internal_name|other_module.exported_name] [@ordinal [NONAME]] [[PRIVATE] | [DATA]]
+/// ```
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/cpp/build/reference/exports>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Export {
+    /// The name exported by the DLL
+    pub name:           String,
+    /// The symbol defining the export if it differs from [`name`](Self::name), or a forwarder (`other_module.exported_name`)
+    pub internal_name:  Option<String>,
+    pub ordinal:        Option<u16>,
+    /// Export only by [`ordinal`](Self::ordinal), omitting [`name`](Self::name) from the export name table
+    pub noname:         bool,
+    /// Omit from the import library (the DLL still exports it)
+    pub private:        bool,
+    /// Exports data instead of code: the import library only defines `__imp_name`
+    pub data:           bool,
+    /// Obsolete alternative to [`data`](Self::data)
+    pub constant:       bool,
+}
+
+impl Export {
+    /// Create an export of `name` with no other options set.
+    pub fn new(name: impl Into<String>) -> Self { Self { name: name.into(), ..Self::default() } }
+
+    /// The `other_module.exported_name` this export forwards to, if [`internal_name`](Self::internal_name) is a forwarder
+    pub fn forwarder(&self) -> Option<&str> {
+        self.internal_name.as_deref().filter(|n| n.contains('.'))
+    }
+}
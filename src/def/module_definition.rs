@@ -0,0 +1,177 @@
+use crate::*;
+
+use maulingmonkey_io_adapters::ReadAt;
+
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
+
+
+/// ∑ [Library](Self::Library) | [Name](Self::Name)<br>
+/// Which statement named a [`ModuleDefinition`]<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleKind {
+    /// `LIBRARY`: a DLL
+    Library,
+    /// `NAME`: an executable
+    Name,
+}
+
+/// ⨯ { [reserve](Self::reserve), [commit](Self::commit) }<br>
+/// A `HEAPSIZE` or `STACKSIZE` statement: `reserve[,commit]`<br>
+/// <br>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Size {
+    pub reserve:    u64,
+    pub commit:     Option<u64>,
+}
+
+/// ⨯ { [kind](Self::kind), [name](Self::name), [base](Self::base), [exports](Self::exports), [sections](Self::sections), ... }<br>
+/// A module-definition (`.def`) file<br>
+/// <br>
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_format_exe::*;
+/// let def : def::ModuleDefinition = "LIBRARY example.dll\nEXPORTS\n    foo @1\n    bar=bar_impl DATA\n".parse().unwrap();
+/// assert_eq!(def.name.as_deref(), Some("example.dll"));
+/// assert_eq!(def.exports[1].internal_name.as_deref(), Some("bar_impl"));
+/// assert_eq!(def.to_string(), "LIBRARY example.dll\nEXPORTS\n    foo @1\n    bar=bar_impl DATA\n");
+/// ```
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/cpp/build/reference/module-definition-dot-def-files>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleDefinition {
+    /// `LIBRARY` or `NAME`, if either statement was present
+    pub kind:           Option<ModuleKind>,
+    /// The module name (e.g. `"example.dll"`) from the `LIBRARY` or `NAME` statement
+    pub name:           Option<String>,
+    /// `BASE=address` from the `LIBRARY` or `NAME` statement
+    pub base:           Option<u64>,
+    /// `DESCRIPTION "text"` (obsolete)
+    pub description:    Option<String>,
+    /// `VERSION major[.minor]`
+    pub version:        Option<(u16, u16)>,
+    pub heap_size:      Option<Size>,
+    pub stack_size:     Option<Size>,
+    pub exports:        Vec<def::Export>,
+    pub sections:       Vec<def::Section>,
+}
+
+impl ModuleDefinition {
+    /// Describe the exports of a DLL, e.g. to regenerate a lost `.def` file or validate a hand-maintained one.
+    ///
+    /// Exports pointing into non-executable sections are marked [`data`](def::Export::data).
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the image has no exports
+    /// *   [`io::Error`] forwarded from [`pe::Reader::read_exports`]
+    pub fn from_pe<R: ReadAt>(reader: &pe::Reader<R>) -> io::Result<Self> {
+        let exports = reader.read_exports()?.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "image has no exports to describe"))?;
+        Ok(Self {
+            kind:       Some(ModuleKind::Library),
+            name:       Some(exports.dll_name),
+            exports:    exports.exports.into_iter().map(|e| {
+                let ordinal = e.ordinal;
+                let (internal_name, data) = match e.target {
+                    pe::ExportTarget::Forwarder(f)  => (Some(f), false),
+                    pe::ExportTarget::Rva(rva)      => (None, match reader.pe_section_headers().iter().find(|s| s.virtual_address_range().contains(&rva)) {
+                        Some(section)   => !section.characteristics.contains(pe::SectionCharacteristics::MEM_EXECUTE),
+                        None            => false,
+                    }),
+                };
+                def::Export {
+                    noname:         e.name.is_none(),
+                    name:           e.name.unwrap_or_else(|| format!("#{}", ordinal)),
+                    internal_name,
+                    ordinal:        Some(ordinal),
+                    data,
+                    ..def::Export::default()
+                }
+            }).collect(),
+            ..Self::default()
+        })
+    }
+}
+
+impl FromStr for ModuleDefinition {
+    type Err = io::Error;
+
+    /// Parse a `.def` file.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] on syntax errors or unknown statements, noting the line number
+    fn from_str(s: &str) -> io::Result<Self> { def::parse::module_definition(s) }
+}
+
+impl Display for ModuleDefinition {
+    /// Write a `.def` file.
+    ///
+    /// ### Errors
+    /// *   [`fmt::Error`] if a name or description contains both `"` and `'`, or a line break, which `.def` syntax can't quote
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        if let Some(kind) = self.kind {
+            write!(fmt, "{}", match kind { ModuleKind::Library => "LIBRARY", ModuleKind::Name => "NAME" })?;
+            if let Some(name) = self.name.as_ref() { write!(fmt, " {}", Quoted(name))? }
+            if let Some(base) = self.base { write!(fmt, " BASE=0x{:X}", base)? }
+            writeln!(fmt)?;
+        }
+        if let Some(description) = self.description.as_ref() { writeln!(fmt, "DESCRIPTION {}", AlwaysQuoted(description))? }
+        if let Some((major, minor)) = self.version { writeln!(fmt, "VERSION {}.{}", major, minor)? }
+        for (statement, size) in [("HEAPSIZE", self.heap_size), ("STACKSIZE", self.stack_size)].iter() {
+            match size {
+                None => {},
+                Some(Size { reserve, commit: None })            => writeln!(fmt, "{} 0x{:X}", statement, reserve)?,
+                Some(Size { reserve, commit: Some(commit) })    => writeln!(fmt, "{} 0x{:X},0x{:X}", statement, reserve, commit)?,
+            }
+        }
+        if !self.sections.is_empty() {
+            writeln!(fmt, "SECTIONS")?;
+            for section in self.sections.iter() {
+                write!(fmt, "    {}", Quoted(&section.name))?;
+                for (attribute, flag) in def::Section::ATTRIBUTES.iter() {
+                    if section.characteristics.contains(*flag) { write!(fmt, " {}", attribute)? }
+                }
+                writeln!(fmt)?;
+            }
+        }
+        if !self.exports.is_empty() {
+            writeln!(fmt, "EXPORTS")?;
+            for export in self.exports.iter() {
+                write!(fmt, "    {}", Quoted(&export.name))?;
+                if let Some(internal) = export.internal_name.as_ref() { write!(fmt, "={}", Quoted(internal))? }
+                if let Some(ordinal) = export.ordinal { write!(fmt, " @{}", ordinal)? }
+                if export.noname    { write!(fmt, " NONAME")? }
+                if export.private   { write!(fmt, " PRIVATE")? }
+                if export.data      { write!(fmt, " DATA")? }
+                if export.constant  { write!(fmt, " CONSTANT")? }
+                writeln!(fmt)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Quotes a name if it would otherwise be misparsed
+struct Quoted<'s>(&'s str);
+struct AlwaysQuoted<'s>(&'s str);
+
+impl Display for Quoted<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let plain = !self.0.is_empty() && !self.0.starts_with('@') && !def::parse::is_keyword(self.0)
+            && !self.0.contains(|c: char| c.is_whitespace() || "=,;\"'".contains(c));
+        if plain { write!(fmt, "{}", self.0) } else { AlwaysQuoted(self.0).fmt(fmt) }
+    }
+}
+
+impl Display for AlwaysQuoted<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        // Quotes can't be escaped, but either kind may delimit a string.  Line breaks always end a statement.
+        let quote = if !self.0.contains('"') { '"' } else if !self.0.contains('\'') { '\'' } else { return Err(fmt::Error) };
+        if self.0.contains(&['\n', '\r'][..]) { return Err(fmt::Error) }
+        write!(fmt, "{}{}{}", quote, self.0, quote)
+    }
+}
@@ -0,0 +1,246 @@
+use crate::*;
+
+use std::convert::*;
+use std::io;
+use std::iter::Peekable;
+
+
+
+const KEYWORDS : &[&str] = &[
+    "BASE", "CLASS", "CONSTANT", "DATA", "DESCRIPTION", "EXECUTE", "EXPORTS", "HEAPSIZE", "LIBRARY", "NAME",
+    "NONAME", "PRIVATE", "READ", "SECTIONS", "SEGMENTS", "SHARED", "STACKSIZE", "VERSION", "WRITE",
+];
+
+pub(crate) fn is_keyword(word: &str) -> bool { KEYWORDS.contains(&word) }
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// An unquoted word: a keyword, name, or number
+    Word(String),
+    /// A `"quoted"` or `'quoted'` name, never a keyword
+    Quoted(String),
+    Equal,
+    Comma,
+}
+
+struct Parser<I: Iterator<Item = (usize, Token)>> {
+    tokens: Peekable<I>,
+    line:   usize,
+}
+
+pub(crate) fn module_definition(text: &str) -> io::Result<def::ModuleDefinition> {
+    let mut p = Parser { tokens: tokenize(text)?.into_iter().peekable(), line: 1 };
+    let mut def = def::ModuleDefinition::default();
+    while let Some(token) = p.next() {
+        match keyword(&token) {
+            Some(kind @ "LIBRARY") | Some(kind @ "NAME") => {
+                def.kind = Some(if kind == "LIBRARY" { def::ModuleKind::Library } else { def::ModuleKind::Name });
+                def.name = p.next_if_name();
+                if p.next_if_keyword("BASE") {
+                    p.expect(Token::Equal, "'=' after BASE")?;
+                    def.base = Some(p.number("BASE address")?);
+                }
+            },
+            Some("DESCRIPTION") => def.description = Some(p.name("DESCRIPTION text")?),
+            Some("VERSION") => {
+                let version = p.word("VERSION number")?;
+                let mut parts = version.splitn(2, '.').map(|n| n.parse::<u16>());
+                let major = parts.next().and_then(|p| p.ok());
+                let minor = parts.next().map_or(Some(0), |p| p.ok());
+                def.version = Some(major.zip(minor).ok_or_else(|| p.error(format!("invalid VERSION {:?}", version)))?);
+            },
+            Some(statement @ "HEAPSIZE") | Some(statement @ "STACKSIZE") => {
+                let reserve = p.number("reserve size")?;
+                let commit = if p.next_if(&Token::Comma) { Some(p.number("commit size")?) } else { None };
+                let size = Some(def::Size { reserve, commit });
+                if statement == "HEAPSIZE" { def.heap_size = size } else { def.stack_size = size }
+            },
+            Some("SECTIONS") | Some("SEGMENTS") => while let Some(name) = p.next_if_name() {
+                let mut characteristics = pe::SectionCharacteristics::empty();
+                loop {
+                    if p.next_if_keyword("CLASS") {
+                        let _class = p.name("CLASS name")?;
+                    } else if let Some((_, flag)) = def::Section::ATTRIBUTES.iter().find(|(a, _)| p.next_if_keyword(a)) {
+                        characteristics |= *flag;
+                    } else {
+                        break;
+                    }
+                }
+                def.sections.push(def::Section { name, characteristics });
+            },
+            Some("EXPORTS") => while let Some(name) = p.next_if_name() {
+                let mut export = def::Export::new(name);
+                if p.next_if(&Token::Equal) { export.internal_name = Some(p.name("internal name after '='")?) }
+                if let Some(Token::Word(w)) = p.peek().filter(|t| matches!(t, Token::Word(w) if w.starts_with('@'))).cloned() {
+                    p.next();
+                    let ordinal = if w == "@" { p.word("ordinal after '@'")? } else { w[1..].into() };
+                    let ordinal = parse_number(&ordinal).and_then(|o| u16::try_from(o).ok()).ok_or_else(|| p.error(format!("invalid ordinal {:?}", ordinal)))?;
+                    export.ordinal = Some(ordinal);
+                }
+                loop {
+                    if      p.next_if_keyword("NONAME")     { export.noname     = true }
+                    else if p.next_if_keyword("PRIVATE")    { export.private    = true }
+                    else if p.next_if_keyword("DATA")       { export.data       = true }
+                    else if p.next_if_keyword("CONSTANT")   { export.constant   = true }
+                    else { break }
+                }
+                def.exports.push(export);
+            },
+            _ => return Err(p.error(format!("unexpected {:?}", token))),
+        }
+    }
+    Ok(def)
+}
+
+impl<I: Iterator<Item = (usize, Token)>> Parser<I> {
+    fn next(&mut self) -> Option<Token> {
+        let (line, token) = self.tokens.next()?;
+        self.line = line;
+        Some(token)
+    }
+
+    fn peek(&mut self) -> Option<&Token> { self.tokens.peek().map(|(_, t)| t) }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches { self.next(); }
+        matches
+    }
+
+    fn next_if_keyword(&mut self, kw: &str) -> bool {
+        let matches = self.peek().and_then(keyword) == Some(kw);
+        if matches { self.next(); }
+        matches
+    }
+
+    fn next_if_name(&mut self) -> Option<String> {
+        match self.peek()? {
+            Token::Word(w) if !is_keyword(w) && !w.starts_with('@') => {},
+            Token::Quoted(_) => {},
+            _ => return None,
+        }
+        match self.next()? { Token::Word(n) | Token::Quoted(n) => Some(n), _ => None }
+    }
+
+    fn name(&mut self, what: &str) -> io::Result<String> {
+        match self.next() {
+            Some(Token::Word(n)) | Some(Token::Quoted(n)) => Ok(n),
+            other => Err(self.error(format!("expected {}, found {:?}", what, other))),
+        }
+    }
+
+    fn word(&mut self, what: &str) -> io::Result<String> {
+        match self.next() {
+            Some(Token::Word(w)) => Ok(w),
+            other => Err(self.error(format!("expected {}, found {:?}", what, other))),
+        }
+    }
+
+    fn number(&mut self, what: &str) -> io::Result<u64> {
+        let word = self.word(what)?;
+        parse_number(&word).ok_or_else(|| self.error(format!("expected {}, found {:?}", what, word)))
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> io::Result<()> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            other => Err(self.error(format!("expected {}, found {:?}", what, other))),
+        }
+    }
+
+    fn error(&self, message: String) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("def: line {}: {}", self.line, message))
+    }
+}
+
+fn keyword(token: &Token) -> Option<&str> {
+    match token {
+        Token::Word(w) if is_keyword(w) => Some(&w[..]),
+        _ => None,
+    }
+}
+
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex)   => u64::from_str_radix(hex, 16).ok(),
+        None        => s.parse().ok(),
+    }
+}
+
+fn tokenize(text: &str) -> io::Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    for (line, text) in text.lines().enumerate() {
+        let line = line + 1;
+        let mut rest = text;
+        loop {
+            rest = rest.trim_start();
+            let c = match rest.chars().next() { None | Some(';') => break, Some(c) => c };
+            match c {
+                '=' => { tokens.push((line, Token::Equal)); rest = &rest[1..]; },
+                ',' => { tokens.push((line, Token::Comma)); rest = &rest[1..]; },
+                '"' | '\'' => {
+                    let end = rest[1..].find(c).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("def: line {}: unterminated quote", line)))?;
+                    tokens.push((line, Token::Quoted(rest[1..1+end].into())));
+                    rest = &rest[end+2..];
+                },
+                _ => {
+                    let end = rest.find(|c: char| c.is_whitespace() || "=,;\"'".contains(c)).unwrap_or(rest.len());
+                    tokens.push((line, Token::Word(rest[..end].into())));
+                    rest = &rest[end..];
+                },
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+
+
+#[test] fn module_definition_roundtrip() {
+    let text = r#"
+        ; comment
+        LIBRARY "example.dll" BASE=0x10000000
+        HEAPSIZE 0x100000,4096
+        STACKSIZE 0x200000
+        VERSION 1.2
+        SECTIONS
+            .shared READ WRITE SHARED
+        EXPORTS
+            foo
+            _Sleep@4 @2
+            bar=bar_impl @ 3 NONAME PRIVATE
+            baz DATA
+            HeapAlloc=NTDLL.RtlAllocateHeap
+            "DATA" CONSTANT
+    "#;
+    let def = module_definition(text).unwrap();
+    assert_eq!((def.kind, def.name.as_deref(), def.base), (Some(def::ModuleKind::Library), Some("example.dll"), Some(0x10000000)));
+    assert_eq!(def.heap_size, Some(def::Size { reserve: 0x100000, commit: Some(4096) }));
+    assert_eq!(def.stack_size, Some(def::Size { reserve: 0x200000, commit: None }));
+    assert_eq!(def.version, Some((1, 2)));
+    assert_eq!(def.sections, vec![def::Section { name: ".shared".into(), characteristics: pe::SectionCharacteristics::MEM_READ | pe::SectionCharacteristics::MEM_WRITE | pe::SectionCharacteristics::MEM_SHARED }]);
+    assert_eq!(def.exports.len(), 6);
+    assert_eq!(def.exports[1], def::Export { ordinal: Some(2), ..def::Export::new("_Sleep@4") });
+    assert_eq!(def.exports[2], def::Export { internal_name: Some("bar_impl".into()), ordinal: Some(3), noname: true, private: true, ..def::Export::new("bar") });
+    assert!(def.exports[3].data);
+    assert_eq!(def.exports[4].forwarder(), Some("NTDLL.RtlAllocateHeap"));
+    assert_eq!(def.exports[5], def::Export { constant: true, ..def::Export::new("DATA") });
+
+    assert_eq!(module_definition(&def.to_string()).unwrap(), def);
+    assert!(module_definition("EXPORTS\n    foo @bar\n").unwrap_err().to_string().contains("line 2"));
+}
+
+#[test] fn module_definition_quotes() {
+    let mut def = def::ModuleDefinition { description: Some("say \"hi\"".into()), ..Default::default() };
+    def.exports.push(def::Export::new("it's"));
+    def.exports.push(def::Export { internal_name: Some("\"quoted\"".into()), ..def::Export::new("plain") });
+    let text = def.to_string();
+    assert!(text.contains("DESCRIPTION 'say \"hi\"'"));
+    assert_eq!(module_definition(&text).unwrap(), def);
+
+    use std::fmt::Write;
+    for unquotable in ["both \" and '", "line\nbreak"].iter() {
+        let def = def::ModuleDefinition { description: Some(unquotable.to_string()), ..Default::default() };
+        assert!(write!(String::new(), "{}", def).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+
+
+/// ⨯ { [name](Self::name), [characteristics](Self::characteristics) }<br>
+/// A `SECTIONS` entry of a `.def` file: `name [READ] [WRITE] [EXECUTE] [SHARED]`<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/cpp/build/reference/sections-c-cpp>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section {
+    pub name:               String,
+    /// Some combination of [`MEM_READ`](pe::SectionCharacteristics::MEM_READ), [`MEM_WRITE`](pe::SectionCharacteristics::MEM_WRITE),
+    /// [`MEM_EXECUTE`](pe::SectionCharacteristics::MEM_EXECUTE), and [`MEM_SHARED`](pe::SectionCharacteristics::MEM_SHARED)
+    pub characteristics:    pe::SectionCharacteristics,
+}
+
+impl Section {
+    pub(crate) const ATTRIBUTES : &'static [(&'static str, pe::SectionCharacteristics)] = &[
+        ("READ",    pe::SectionCharacteristics::MEM_READ),
+        ("WRITE",   pe::SectionCharacteristics::MEM_WRITE),
+        ("EXECUTE", pe::SectionCharacteristics::MEM_EXECUTE),
+        ("SHARED",  pe::SectionCharacteristics::MEM_SHARED),
+    ];
+}
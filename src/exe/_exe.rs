@@ -0,0 +1,16 @@
+//! Any MS-DOS derived executable: sniffs the signature at [`mz::Header::pe_header_start`] to pick a reader.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Format`]        | Which executable format a file is: MZ, NE, LE, LX, or PE.
+//! | [`Reader`]        | A reader for whichever [`Format`] was found.
+//! | [`open`]          | Sniff a file's [`Format`] and open the matching [`Reader`].
+//!
+//! ## References
+//! *   <https://wiki.osdev.org/MZ>
+//! *   <https://en.wikipedia.org/wiki/DOS_MZ_executable#Header>
+
+mod format;                         pub use format::*;
+mod reader;                         pub use reader::*;
+
+#[cfg(doc)] use crate::*;
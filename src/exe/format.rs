@@ -0,0 +1,95 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::ReadAt;
+
+
+
+/// ∑ [Mz](Self::Mz) | [Ne](Self::Ne) | [Le](Self::Le) | [Lx](Self::Lx) | [Pe](Self::Pe)<br>
+/// Which executable format a file is, based on the signature found at [`mz::Header::pe_header_start`].<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// A pure MS-DOS executable, with no recognized signature at [`mz::Header::pe_header_start`]
+    Mz,
+    /// `"NE"` - a 16-bit Windows 1.x - 3.x or OS/2 1.x New Executable
+    Ne,
+    /// `"LE"` - a mixed 16/32-bit Linear Executable (Windows 3.x / 9x VxDs, DOS extenders)
+    Le,
+    /// `"LX"` - a 32-bit Linear eXecutable (OS/2 2.0+)
+    Lx,
+    /// `"PE\0\0"` - a Windows NT / 95+ Portable Executable
+    Pe,
+}
+
+impl Format {
+    /// Sniff the [`Format`] of `read_at`.
+    ///
+    /// Genuine MS-DOS programs don't have [`mz::Header::pe_header_start`], so whatever bytes happen to be there may point anywhere:
+    /// a missing, truncated, or unrecognized signature is treated as [`Format::Mz`] rather than an error.
+    /// [`mz::Header::pe_header_start`] is also only followed if [`mz::Header::relocs`] (`e_lfarlc`) is at least `0x40`, as set by NE/LE/PE linkers:
+    /// MS-DOS programs typically place their relocations at `0x1C`, overlapping the extended header.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if `read_at` is too short for even a basic 28 byte [`mz::Header`]
+    /// *   [`io::ErrorKind::InvalidData`] if [`mz::Header::signature`] ≠ `"MZ"` (or `"ZM"`)
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_at`]
+    pub fn sniff(read_at: &impl ReadAt) -> io::Result<Self> {
+        let mut raw = [0u8; 64];
+        let n = read_at.read_up_to_at(&mut raw[..], 0)?;
+        if n < 0x1C { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "file too small for mz::Header")) }
+        if !matches!(&raw[..2], b"MZ" | b"ZM") { return Err(io::Error::new(io::ErrorKind::InvalidData, "mz::Header::signature != \"MZ\"")) }
+        if n < raw.len() { return Ok(Format::Mz) }
+
+        let header = mz::Header::from_memory(&mut &raw[..])?;
+        if header.relocs < 0x40 { return Ok(Format::Mz) }
+        let mut signature = [0u8; 4];
+        let n = read_at.read_up_to_at(&mut signature[..], header.pe_header_start.into())?;
+        Ok(match &signature[..n] {
+            b"PE\0\0"       => Format::Pe,
+            [b'N', b'E', ..] => Format::Ne,
+            [b'L', b'E', ..] => Format::Le,
+            [b'L', b'X', ..] => Format::Lx,
+            _               => Format::Mz,
+        })
+    }
+}
+
+
+
+/// A 64 byte [`mz::Header`] pointing just past itself at `signature`, zero padded to 512 bytes
+#[cfg(test)] pub(crate) fn fixture(signature: &[u8]) -> Vec<u8> {
+    let mut image = vec![0u8; 0x40];
+    image[0 .. 2].copy_from_slice(b"MZ");
+    image[2 .. 4].copy_from_slice(&0x200u16.to_le_bytes());        // last_page
+    image[4 .. 6].copy_from_slice(&1u16.to_le_bytes());            // pages
+    image[8 .. 10].copy_from_slice(&4u16.to_le_bytes());           // hdrsize
+    image[0x18 .. 0x1A].copy_from_slice(&0x40u16.to_le_bytes());   // relocs
+    image[0x3C .. 0x40].copy_from_slice(&0x40u32.to_le_bytes());   // pe_header_start
+    image.extend_from_slice(signature);
+    image.resize(0x200, 0);
+    image
+}
+
+#[test] fn sniff() {
+    let sniff = |image: Vec<u8>| Format::sniff(&MemoryReadAt(image));
+    assert_eq!(sniff(fixture(b"")).unwrap(),            Format::Mz);
+    assert_eq!(sniff(fixture(b"??")).unwrap(),          Format::Mz);
+    assert_eq!(sniff(fixture(b"NE")).unwrap(),          Format::Ne);
+    assert_eq!(sniff(fixture(b"LE")).unwrap(),          Format::Le);
+    assert_eq!(sniff(fixture(b"LX")).unwrap(),          Format::Lx);
+    assert_eq!(sniff(fixture(b"PE\0\0")).unwrap(),      Format::Pe);
+
+    // Truncated: no pe_header_start, or nothing at pe_header_start
+    assert_eq!(sniff(fixture(b"PE\0\0")[.. 0x3C].to_vec()).unwrap(), Format::Mz);
+    assert_eq!(sniff(fixture(b"PE\0\0")[.. 0x42].to_vec()).unwrap(), Format::Mz);
+    assert_eq!(sniff(fixture(b"")[.. 0x40].to_vec()).unwrap(),       Format::Mz);
+
+    // MS-DOS relocations overlapping the extended header: pe_header_start isn't followed
+    let mut dos = fixture(b"PE\0\0");
+    dos[0x18 .. 0x1A].copy_from_slice(&0x1Cu16.to_le_bytes());
+    assert_eq!(sniff(dos).unwrap(), Format::Mz);
+
+    assert_eq!(sniff(fixture(b"")[.. 0x1B].to_vec()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(sniff([&b"XX"[..], &fixture(b"")[2..]].concat()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,104 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, ReadAtRef, SeeklessFile};
+
+use std::fs::File;
+use std::path::PathBuf;
+
+
+
+/// ∑ [Mz](Self::Mz) | [Ne](Self::Ne) | [Le](Self::Le) | [Pe](Self::Pe)<br>
+/// A reader for whichever [`exe::Format`] was sniffed by [`exe::open`] / [`exe::read`].<br>
+/// <br>
+#[non_exhaustive]
+#[allow(clippy::large_enum_variant)] // a handful of these are created per file, boxing isn't worth the indirection
+pub enum Reader<R> {
+    /// [`exe::Format::Mz`]
+    Mz(mz::Reader<R>),
+    /// [`exe::Format::Ne`]
+    Ne(ne::Reader<R>),
+    /// [`exe::Format::Le`] or [`exe::Format::Lx`]
+    Le(le::Reader<R>),
+    /// [`exe::Format::Pe`]
+    Pe(pe::Reader<R>),
+}
+
+/// Open an MZ, NE, LE, LX, or PE executable, sniffing the [`exe::Format`] to pick the matching [`exe::Reader`].
+///
+/// If the NE, LE, LX, or PE headers fail to parse, [`mz::Header::pe_header_start`] is assumed to be garbage from a genuine MS-DOS program,
+/// and an [`exe::Reader::Mz`] is returned instead.
+///
+/// ### Errors
+/// *   [`io::Error`] if the file couldn't be opened
+/// *   [`io::Error`] forwarded from [`exe::Format::sniff`] if the file isn't an MZ-derived executable
+/// *   [`io::Error`] forwarded from [`mz::Reader`] if the file isn't a valid MS-DOS executable either
+pub fn open(path: impl Into<PathBuf>) -> io::Result<Reader<SeeklessFile>> {
+    let path = path.into();
+    let file = File::open(&path);
+    let src = Src::PathBuf(path);
+    read_src(SeeklessFile::from(src.anno(file, "error opening exe::Reader")?), src)
+}
+
+/// Read an MZ, NE, LE, LX, or PE executable, sniffing the [`exe::Format`] to pick the matching [`exe::Reader`].  Prefer [`exe::open`] for better error messages.
+pub fn read<R: ReadAt>(reader: R) -> io::Result<Reader<R>> { read_src(reader, Src::Unknown) }
+
+fn read_src<R: ReadAt>(reader: R, src: Src) -> io::Result<Reader<R>> {
+    let format = src.anno(exe::Format::sniff(&reader), "error sniffing exe::Format")?;
+    // The format-specific readers consume `reader` even on error: probe by reference first so we can still fall back to mz::Reader
+    Ok(match format {
+        exe::Format::Ne                     if ne::Reader::read_src(ReadAtRef(&reader), Src::Unknown).is_ok() => Reader::Ne(ne::Reader::read_src(reader, src)?),
+        exe::Format::Le | exe::Format::Lx   if le::Reader::read_src(ReadAtRef(&reader), Src::Unknown).is_ok() => Reader::Le(le::Reader::read_src(reader, src)?),
+        exe::Format::Pe                     if pe::Reader::read_src(ReadAtRef(&reader), Src::Unknown).is_ok() => Reader::Pe(pe::Reader::read_src(reader, src)?),
+        _                                   => Reader::Mz(mz::Reader::read_src(reader, src)?),
+    })
+}
+
+impl<R> Reader<R> {
+    /// The [`exe::Format`] that was sniffed
+    pub fn format(&self) -> exe::Format {
+        match self {
+            Reader::Mz(_)                       => exe::Format::Mz,
+            Reader::Ne(_)                       => exe::Format::Ne,
+            Reader::Le(r) if r.header().is_lx() => exe::Format::Lx,
+            Reader::Le(_)                       => exe::Format::Le,
+            Reader::Pe(_)                       => exe::Format::Pe,
+        }
+    }
+
+    /// The [`mz::Header`] common to every format
+    pub fn mz_header(&self) -> &mz::Header {
+        match self {
+            Reader::Mz(r)   => r.header(),
+            Reader::Ne(r)   => r.mz_header(),
+            Reader::Le(r)   => r.mz_header(),
+            Reader::Pe(r)   => r.mz_header(),
+        }
+    }
+}
+
+
+
+#[test] fn read_formats() {
+    use super::format::fixture;
+    let format = |image: Vec<u8>| exe::read(MemoryReadAt(image)).unwrap().format();
+    assert_eq!(format(fixture(b"")),                    exe::Format::Mz);
+    assert_eq!(format(fixture(b"")[.. 0x30].to_vec()),  exe::Format::Mz); // truncated, but still a valid MS-DOS program
+    assert_eq!(format(fixture(b"NE")),                  exe::Format::Ne);
+    assert_eq!(format(fixture(b"LE")),                  exe::Format::Le);
+    assert_eq!(format(fixture(b"LX")),                  exe::Format::Lx);
+    assert_eq!(format(fixture(b"LE\x01")),              exe::Format::Mz); // big endian LE isn't supported: fall back
+    assert_eq!(format(fixture(b"PE\0\0")[.. 0x48].to_vec()), exe::Format::Mz); // truncated pe::FileHeader: fall back
+
+    let mut w = pe::Writer::new_pe32(pe::Machine::I386);
+    w.add_section(".text", pe::SectionCharacteristics::CNT_CODE | pe::SectionCharacteristics::MEM_EXECUTE | pe::SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let pe = w.to_bytes().unwrap();
+    assert_eq!(format(pe.clone()), exe::Format::Pe);
+
+    let path = std::env::temp_dir().join(format!("maulingmonkey-format-exe-test-{}.exe", std::process::id()));
+    std::fs::write(&path, &pe[..]).unwrap();
+    let r = exe::open(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(r.unwrap().format(), exe::Format::Pe);
+    assert!(exe::open(&path).is_err());
+}
@@ -16,6 +16,7 @@ pub trait FromMemory : Sized {
     type Error  : Into<io::Error> + From<error::Eof>;
 
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error>;
+    fn to_raw(&self) -> Self::Raw;
 
     fn from_memory(mem: &mut &[u8]) -> Result<Self, Self::Error> {
         let mut raw = Self::Raw::default();
@@ -48,6 +49,14 @@ pub trait FromMemory : Sized {
         *offset += std::mem::size_of::<Self::Raw>() as u64;
         Ok(s)
     }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        bytes_of(&self.to_raw()).to_vec()
+    }
+
+    fn to_io(&self, write: &mut impl io::Write) -> io::Result<()> {
+        write.write_all(bytes_of(&self.to_raw()))
+    }
 }
 
 
@@ -56,12 +65,21 @@ impl FromMemory for () {
     type Raw    = [u8; 0];
     type Error  = std::io::Error;
     fn from_raw(_: Self::Raw) -> Result<(), Self::Error> { Ok(()) }
+    fn to_raw(&self) -> Self::Raw { [] }
+}
+
+impl FromMemory for [u8; 16] { // GUIDs
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
 }
 
 impl<B: Default + Pod> FromMemory for CStrBuf<B> {
     type Raw    = Self;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
 }
 
 macro_rules! from_memory_le_integers {
@@ -71,6 +89,7 @@ macro_rules! from_memory_le_integers {
                 type Raw    = $le;
                 type Error  = std::io::Error;
                 fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw.to_le()) }
+                fn to_raw(&self) -> Self::Raw { <$le>::new(*self) }
             }
         )*
     };
@@ -96,6 +115,7 @@ macro_rules! from_memory_le_opt_nz_integers {
                 type Raw    = $le;
                 type Error  = std::io::Error;
                 fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(<$nz>::new(raw.to_le())) }
+                fn to_raw(&self) -> Self::Raw { <$le>::new(self.map_or(0, |nz| nz.get())) }
             }
         )*
     };
@@ -3,3 +3,4 @@
 #[doc(hidden)] pub use std::io::*;
 
 mod read_at_ext;                        pub use read_at_ext::*;
+mod src;                                pub(crate) use src::*;
@@ -23,6 +23,63 @@ pub trait ReadAtExt : ReadAt {
         Ok(())
     }
 
+    /// Read up to `buf.len()` bytes, overwriting `buf`, from `offset`.  Unlike [`ReadAt::read_exact_at`], a short read at end of file is not an error.
+    ///
+    /// *   `buf[..]` *may* have been modified on [`io::Error`]
+    /// *   Returns the number of bytes read, which is less than `buf.len()` only at end of file
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if `offset` would overflow
+    /// *   [`io::Error`] if the underlying [`ReadAt::read_at`]s failed
+    fn read_up_to_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let mut n = 0;
+        while n < buf.len() {
+            let o = offset.checked_add(n as u64).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "offset exceeded u64 range"))?;
+            match self.read_at(&mut buf[n..], o) {
+                Ok(0) => break,
+                Ok(read) => n += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(n)
+    }
+
+    /// Read exactly `len` bytes from `offset` into a new [`Vec`].
+    ///
+    /// Unlike `vec![0; len]` + [`ReadAt::read_exact_at`], the buffer grows in chunks as data is actually read,
+    /// so an untrusted `len` (e.g. a header field) can't allocate much more than the underlying data contains.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if fewer than `len` bytes could be read, or `offset` would overflow
+    /// *   [`io::Error`] if the underlying [`ReadAt::read_at`]s failed
+    fn read_exact_vec_at(&self, len: usize, offset: u64) -> io::Result<Vec<u8>> {
+        let buf = self.read_up_to_vec_at(len, offset)?;
+        if buf.len() < len { return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("expected {} bytes, found only {}", len, buf.len()))) }
+        Ok(buf)
+    }
+
+    /// Read up to `len` bytes from `offset` into a new [`Vec`].  Like [`read_up_to_at`](Self::read_up_to_at), a short read at end of file is not an error.
+    ///
+    /// Like [`read_exact_vec_at`](Self::read_exact_vec_at), the buffer grows in chunks as data is actually read.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if `offset` would overflow
+    /// *   [`io::Error`] if the underlying [`ReadAt::read_at`]s failed
+    fn read_up_to_vec_at(&self, len: usize, offset: u64) -> io::Result<Vec<u8>> {
+        const CHUNK : usize = 64 * 1024;
+        let mut buf = Vec::with_capacity(len.min(CHUNK));
+        while buf.len() < len {
+            let start = buf.len();
+            let chunk = (len - start).min(CHUNK.max(start)); // double, at most, to amortize reallocation
+            let o = offset.checked_add(start as u64).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "offset exceeded u64 range"))?;
+            buf.resize(start + chunk, 0u8);
+            let n = self.read_up_to_at(&mut buf[start..], o)?;
+            if n < chunk { buf.truncate(start + n); break }
+        }
+        Ok(buf)
+    }
+
     /// Append read bytes into `buf`, from `offset`, until `byte` is encountered.
     ///
     /// *   The original contents of `buf` are not cleared
@@ -80,3 +137,33 @@ pub trait ReadAtExt : ReadAt {
 }
 
 impl<R: ReadAt> ReadAtExt for R {}
+
+
+
+/// An in-memory [`ReadAt`] for tests
+#[cfg(test)] pub(crate) struct MemoryReadAt(pub Vec<u8>);
+
+#[cfg(test)] impl ReadAt for MemoryReadAt {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let data = self.0.get(usize::try_from(offset).unwrap_or(usize::MAX) ..).unwrap_or(&[]);
+        let n = buf.len().min(data.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        Ok(n)
+    }
+}
+
+#[test] fn read_exact_vec_at() {
+    let r = MemoryReadAt((0 ..= 255).cycle().take(200_000).collect());
+    assert_eq!(r.read_exact_vec_at(4, 254).unwrap(), [254, 255, 0, 1]);
+    assert_eq!(r.read_exact_vec_at(199_990, 10).unwrap()[..], r.0[10..]);
+    assert_eq!(r.read_exact_vec_at(0, 1_000_000).unwrap().len(), 0);
+    assert_eq!(r.read_exact_vec_at(199_991, 10).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(r.read_exact_vec_at(usize::MAX, 0).unwrap_err().kind(), io::ErrorKind::UnexpectedEof); // doesn't try to allocate usize::MAX bytes
+}
+
+#[test] fn read_up_to_vec_at() {
+    let r = MemoryReadAt((0 ..= 255).cycle().take(200_000).collect());
+    assert_eq!(r.read_up_to_vec_at(4, 254).unwrap(), [254, 255, 0, 1]);
+    assert_eq!(r.read_up_to_vec_at(usize::MAX, 10).unwrap()[..], r.0[10..]);
+    assert_eq!(r.read_up_to_vec_at(10, 1_000_000).unwrap().len(), 0);
+}
@@ -0,0 +1,40 @@
+use crate::io;
+
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+
+
+/// Where a reader's data came from, for annotating [`io::Error`]s
+#[derive(Clone)]
+pub(crate) enum Src {
+    Unknown,
+    PathBuf(PathBuf),
+    ArMember(Box<Src>, String),
+}
+
+impl Src {
+    pub fn anno<T>(&self, r: io::Result<T>, note: &str) -> io::Result<T> {
+        if let Err(e) = r {
+            Err(io::Error::new(e.kind(), format!("{}: {}: {}", self, note, e)))
+        } else {
+            r
+        }
+    }
+}
+
+impl Default for Src {
+    fn default() -> Self {
+        Src::Unknown
+    }
+}
+
+impl Display for Src {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            Src::Unknown    => write!(fmt, "unknown"),
+            Src::PathBuf(p) => write!(fmt, "`{}`", p.display()),
+            Src::ArMember(a, m) => write!(fmt, "{}({})", a, m),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! The 32-bit **L**inear **E**xecutable (Windows 3.x / 9x VxDs) and **L**inear e**X**ecutable (OS/2 2.0+) formats.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Header`]            | The LE/LX header, found at [`mz::Header::pe_header_start`].  Offsets of every other table.
+//! | [`Reader`]            | Reads the [`Header`], [`ObjectEntry`]s and [`PageMapEntry`]s, and the other tables on demand.
+//! | [`ObjectEntry`]       | An object (≈ segment / section) and the range of [`PageMapEntry`]s holding its data.
+//! | [`PageMapEntry`]      | Where a single page lives in the file.
+//! | [`Fixup`]             | A per-page fixup record, targeting another object, an import, or an entry point.
+//! | [`EntryPoint`]        | An entry of the entry table: the object and offset of an ordinal, or a forwarder.
+//!
+//! The resident and non-resident name tables share the NE format, and are read as [`ne::Name`]s.
+//!
+//! ## References
+//! *   <https://wiki.osdev.org/LE>
+//! *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description>
+//! *   `exe386.h` from the OS/2 toolkit, `vmm.h` from the Windows 95 DDK
+
+mod entry_point;                    pub use entry_point::*;
+mod fixup;                          pub use fixup::*;
+mod header;                         pub use header::*;
+mod object_entry;                   pub use object_entry::*;
+mod page_map_entry;                 pub use page_map_entry::*;
+mod reader;                         pub use reader::*;
+
+#[cfg(doc)] use crate::*;
@@ -0,0 +1,102 @@
+use crate::*;
+
+use std::io;
+
+
+
+/// ⨯ { [ordinal](Self::ordinal), [object](Self::object), [flags](Self::flags), [target](Self::target) }<br>
+/// A used entry of the LE / LX entry table<br>
+/// <br>
+///
+/// Unused entries are skipped, but still consume ordinals.
+///
+/// ## References
+/// *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description#Entry_Table>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    /// 1-based ordinal, as referenced by [`ne::Name::ordinal`] and importing modules
+    pub ordinal:    u16,
+    /// 1-based object number ([`le::Reader::object`]), or 0 for [`EntryTarget::Forwarder`]s
+    pub object:     u16,
+    /// Always empty for [`EntryTarget::Forwarder`]s
+    pub flags:      ne::EntryFlags,
+    pub target:     EntryTarget,
+}
+
+/// ∑ [Entry16](Self::Entry16) | [CallGate](Self::CallGate) | [Entry32](Self::Entry32) | [Forwarder](Self::Forwarder)<br>
+/// Where an [`EntryPoint`] leads<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryTarget {
+    /// A 16-bit offset within [`EntryPoint::object`]
+    Entry16 { offset: u16 },
+    /// A 16-bit offset within [`EntryPoint::object`], reached through an 80286 call gate
+    CallGate { offset: u16, /** filled in by the loader */ selector: u16 },
+    /// A 32-bit offset within [`EntryPoint::object`]
+    Entry32 { offset: u32 },
+    /// Forwarded to another module's entry point
+    Forwarder {
+        /// 1-based index into [`le::Reader::read_import_modules`]
+        module:     u16,
+        /// If `true`, [`value`](Self::Forwarder::value) is an ordinal.  If `false`, it's an offset into the import procedure name table.
+        by_ordinal: bool,
+        value:      u32,
+    },
+}
+
+impl EntryPoint {
+    /// Parse an entry table, stopping at the zero count terminator (or the end of `data`.)
+    pub(crate) fn parse_table(mut data: &[u8]) -> io::Result<Vec<Self>> {
+        let eof = || io::Error::new(io::ErrorKind::UnexpectedEof, "le::EntryPoint extends past end of entry table");
+        let mut entries = Vec::new();
+        let mut ordinal = 1u16;
+        while let [count, kind, rest @ ..] = data {
+            if *count == 0 { break }
+            let kind = kind & 0x7F; // 0x80: parameter typing information present
+            if kind == 0 { ordinal = ordinal.wrapping_add(u16::from(*count)); data = rest; continue }
+
+            let size = match kind {
+                1 => 3,
+                2 => 5,
+                3 => 5,
+                4 => 7,
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("le::EntryPoint: unknown bundle type 0x{:02x}", kind))),
+            };
+            let object = rest.get(..2).map(|o| u16::from_le_bytes([o[0], o[1]])).ok_or_else(eof)?;
+            let bundle = rest.get(2 .. 2 + usize::from(*count) * size).ok_or_else(eof)?;
+            for e in bundle.chunks_exact(size) {
+                let w = |i: usize| u16::from_le_bytes([e[i], e[i+1]]);
+                let d = |i: usize| u32::from_le_bytes([e[i], e[i+1], e[i+2], e[i+3]]);
+                let flags = ne::EntryFlags::from_bits_truncate(e[0]);
+                entries.push(match kind {
+                    1 => EntryPoint { ordinal, object, flags, target: EntryTarget::Entry16 { offset: w(1) } },
+                    2 => EntryPoint { ordinal, object, flags, target: EntryTarget::CallGate { offset: w(1), selector: w(3) } },
+                    3 => EntryPoint { ordinal, object, flags, target: EntryTarget::Entry32 { offset: d(1) } },
+                    _ => EntryPoint { ordinal, object: 0, flags: ne::EntryFlags::empty(), target: EntryTarget::Forwarder { module: w(1), by_ordinal: e[0] & 0x01 != 0, value: d(3) } },
+                });
+                ordinal = ordinal.wrapping_add(1);
+            }
+            data = &rest[2 + bundle.len()..];
+        }
+        Ok(entries)
+    }
+}
+
+
+
+#[test] fn parse_table() {
+    let entries = EntryPoint::parse_table(&[
+        0x01, 0x00,                                             // 1 unused
+        0x02, 0x03, 0x02, 0x00,                                 // 2 32-bit in object 2
+        0x03, 0x10, 0x00, 0x00, 0x00,
+        0x00, 0x20, 0x00, 0x00, 0x00,
+        0x01, 0x04, 0x00, 0x00,                                 // 1 forwarder
+        0x01, 0x01, 0x00, 0x2A, 0x00, 0x00, 0x00,
+        0x00,
+    ]).unwrap();
+    assert_eq!(entries, vec![
+        EntryPoint { ordinal: 2, object: 2, flags: ne::EntryFlags::EXPORTED | ne::EntryFlags::SHARED_DATA, target: EntryTarget::Entry32 { offset: 0x10 } },
+        EntryPoint { ordinal: 3, object: 2, flags: ne::EntryFlags::empty(), target: EntryTarget::Entry32 { offset: 0x20 } },
+        EntryPoint { ordinal: 4, object: 0, flags: ne::EntryFlags::empty(), target: EntryTarget::Forwarder { module: 1, by_ordinal: true, value: 42 } },
+    ]);
+}
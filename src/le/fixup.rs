@@ -0,0 +1,146 @@
+#[cfg(doc)] use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+
+
+
+/// ⨯ { [source](Self::source), [source_offsets](Self::source_offsets), [target](Self::target), [additive](Self::additive), ... }<br>
+/// A fixup record from the LE / LX fixup record table, applying to a single page<br>
+/// <br>
+///
+/// ## References
+/// *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description#Fixup_Record_Table>
+/// *   `struct r32_rlc` in `exe386.h`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixup {
+    /// What kind of location is being patched
+    pub source:         FixupSource,
+    /// The fixup targets a 16:16 alias of the target object
+    pub alias16:        bool,
+    /// Offsets within the page of each location to patch.  Usually one, but may be several for source lists.
+    /// May be negative (or extend past the end of the page) for locations straddling a page boundary.
+    pub source_offsets: Vec<i16>,
+    pub target:         FixupTarget,
+    /// Value added to the target, if any
+    pub additive:       Option<u32>,
+}
+
+/// ∑ [Internal](Self::Internal) | [ImportOrdinal](Self::ImportOrdinal) | [ImportName](Self::ImportName) | [Entry](Self::Entry)<br>
+/// What a [`Fixup`] refers to<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixupTarget {
+    /// An offset within an object of this module
+    Internal { /** 1-based object number */ object: u16, offset: u32 },
+    /// An entry point of another module, by ordinal
+    ImportOrdinal { /** 1-based index into [`le::Reader::read_import_modules`] */ module: u16, ordinal: u32 },
+    /// An entry point of another module, by name
+    ImportName { /** 1-based index into [`le::Reader::read_import_modules`] */ module: u16, /** see [`le::Reader::read_import_procedure_name`] */ name: u32 },
+    /// An entry point of this module, via the entry table
+    Entry { /** [`le::EntryPoint::ordinal`] */ ordinal: u16 },
+}
+
+/// ∑
+/// [BYTE](Self::BYTE) |
+/// [SELECTOR16](Self::SELECTOR16) |
+/// [POINTER16](Self::POINTER16) |
+/// [OFFSET32](Self::OFFSET32) |
+/// ...<br>
+/// [le::Fixup::source]: the size and kind of location being patched<br>
+/// <br>
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct FixupSource(u8);
+
+impl FixupSource {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    #[doc = "8-bit byte"                                                        ] pub const BYTE        : FixupSource = FixupSource::new(0x0);
+    #[doc = "16-bit selector"                                                   ] pub const SELECTOR16  : FixupSource = FixupSource::new(0x2);
+    #[doc = "16:16 far pointer"                                                 ] pub const POINTER16   : FixupSource = FixupSource::new(0x3);
+    #[doc = "16-bit offset"                                                     ] pub const OFFSET16    : FixupSource = FixupSource::new(0x5);
+    #[doc = "16:32 far pointer"                                                 ] pub const POINTER32   : FixupSource = FixupSource::new(0x6);
+    #[doc = "32-bit offset"                                                     ] pub const OFFSET32    : FixupSource = FixupSource::new(0x7);
+    #[doc = "32-bit self-relative offset"                                       ] pub const RELATIVE32  : FixupSource = FixupSource::new(0x8);
+}
+
+impl Debug for FixupSource {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::BYTE          => write!(fmt, "FixupSource::BYTE"),
+            Self::SELECTOR16    => write!(fmt, "FixupSource::SELECTOR16"),
+            Self::POINTER16     => write!(fmt, "FixupSource::POINTER16"),
+            Self::OFFSET16      => write!(fmt, "FixupSource::OFFSET16"),
+            Self::POINTER32     => write!(fmt, "FixupSource::POINTER32"),
+            Self::OFFSET32      => write!(fmt, "FixupSource::OFFSET32"),
+            Self::RELATIVE32    => write!(fmt, "FixupSource::RELATIVE32"),
+            other               => write!(fmt, "FixupSource(0x{:02x})", other.0),
+        }
+    }
+}
+
+impl Fixup {
+    /// Parse the fixup records for a single page
+    pub(crate) fn parse_records(mut data: &[u8]) -> io::Result<Vec<Self>> {
+        let mut fixups = Vec::new();
+        while !data.is_empty() {
+            fixups.push(Self::parse_record(&mut data)?);
+        }
+        Ok(fixups)
+    }
+
+    fn parse_record(data: &mut &[u8]) -> io::Result<Self> {
+        let source  = take(data, 1)? as u8;
+        let flags   = take(data, 1)? as u8;
+        let source_list = source & 0x20 != 0;
+
+        let mut source_offsets = Vec::new();
+        let nsources = if source_list { take(data, 1)? } else { source_offsets.push(take(data, 2)? as u16 as i16); 0 };
+
+        let object_or_module    = take(data, if flags & 0x40 != 0 { 2 } else { 1 })? as u16;
+        let offset32            = flags & 0x10 != 0;
+        let target = match flags & 0x03 {
+            0 if source & 0x0F == 0x2   => FixupTarget::Internal { object: object_or_module, offset: 0 },
+            0                           => FixupTarget::Internal { object: object_or_module, offset: take(data, if offset32 { 4 } else { 2 })? },
+            1                           => FixupTarget::ImportOrdinal { module: object_or_module, ordinal: take(data, if flags & 0x80 != 0 { 1 } else if offset32 { 4 } else { 2 })? },
+            2                           => FixupTarget::ImportName { module: object_or_module, name: take(data, if offset32 { 4 } else { 2 })? },
+            _                           => FixupTarget::Entry { ordinal: object_or_module },
+        };
+        let additive = if flags & 0x04 != 0 { Some(take(data, if flags & 0x20 != 0 { 4 } else { 2 })?) } else { None };
+
+        for _ in 0 .. nsources {
+            source_offsets.push(take(data, 2)? as u16 as i16);
+        }
+
+        Ok(Fixup { source: FixupSource::new(source & 0x0F), alias16: source & 0x10 != 0, source_offsets, target, additive })
+    }
+}
+
+/// Take a 1, 2, or 4 byte little endian value off the front of `data`
+fn take(data: &mut &[u8], n: usize) -> io::Result<u32> {
+    let bytes = data.get(..n).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "le::Fixup extends past end of fixup record table"))?;
+    *data = &data[n..];
+    Ok(bytes.iter().rev().fold(0, |v, b| (v << 8) | u32::from(*b)))
+}
+
+
+
+#[test] fn parse_records() {
+    let fixups = Fixup::parse_records(&[
+        0x07, 0x10, 0xFC, 0xFF, 0x02, 0x78, 0x56, 0x34, 0x12,   // OFFSET32 @ -4 -> object 2 + 0x12345678
+        0x27, 0x01, 0x02, 0x01, 0x05, 0x00, 0x10, 0x00, 0x20, 0x00, // OFFSET32 list @ 0x10, 0x20 -> module 1 ordinal 5
+        0x02, 0x00, 0x00, 0x00, 0x03,                           // SELECTOR16 @ 0 -> object 3
+    ]).unwrap();
+    assert_eq!(fixups, vec![
+        Fixup { source: FixupSource::OFFSET32, alias16: false, source_offsets: vec![-4], target: FixupTarget::Internal { object: 2, offset: 0x12345678 }, additive: None },
+        Fixup { source: FixupSource::OFFSET32, alias16: false, source_offsets: vec![0x10, 0x20], target: FixupTarget::ImportOrdinal { module: 1, ordinal: 5 }, additive: None },
+        Fixup { source: FixupSource::SELECTOR16, alias16: false, source_offsets: vec![0], target: FixupTarget::Internal { object: 3, offset: 0 }, additive: None },
+    ]);
+    assert!(Fixup::parse_records(&[0x07, 0x10, 0xFC]).is_err());
+}
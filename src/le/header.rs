@@ -0,0 +1,224 @@
+use crate::*;
+
+use bytemuck::*;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [signature](Self::signature),
+    ///     [cpu](Self::cpu),
+    ///     [os](Self::os),
+    ///     [flags](Self::flags),
+    ///     [npages](Self::npages),
+    ///     [object_table](Self::object_table),
+    ///     ...
+    /// }<br>
+    /// The LE / LX header, found at [`mz::Header::pe_header_start`]<br>
+    /// <br>
+    ///
+    /// Unless otherwise noted, table offsets are relative to the start of this header.
+    ///
+    /// ## References
+    /// *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description#LX_Header>
+    /// *   `struct e32_exe` in `exe386.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Header {
+        /// `b"LE"` or `b"LX"`.
+        pub signature:                  abistr::CStrBuf<[u8; 2]>,
+        /// 0 for little endian
+        pub byte_order:                 u8,
+        /// 0 for little endian
+        pub word_order:                 u8,
+        /// Format level, 0
+        pub format_level:               u32,
+        pub cpu:                        Cpu,
+        /// Target operating system (same values as [`ne::TargetOs`]: 1 for OS/2, 2 for Windows, 4 for Windows 386 / VxDs)
+        pub os:                         u16,
+        pub module_version:             u32,
+        pub flags:                      ModuleFlags,
+        /// Number of pages in the module ([`le::PageMapEntry`]s in the object page map)
+        pub npages:                     u32,
+        /// 1-based object number of the initial `EIP`
+        pub eip_object:                 u32,
+        pub eip:                        u32,
+        /// 1-based object number of the initial `ESP`
+        pub esp_object:                 u32,
+        pub esp:                        u32,
+        /// Page size in bytes, typically 4096
+        pub page_size:                  u32,
+        /// **LX:** shift applied to [`le::PageMapEntry`] offsets.<br>
+        /// **LE:** size of the last page in bytes.
+        pub page_shift:                 u32,
+        pub fixup_section_size:         u32,
+        pub fixup_section_checksum:     u32,
+        pub loader_section_size:        u32,
+        pub loader_section_checksum:    u32,
+        /// Offset of the object table (see [`le::ObjectEntry`])
+        pub object_table:               u32,
+        /// Number of [`le::ObjectEntry`]s in the object table
+        pub nobjects:                   u32,
+        /// Offset of the object page map (see [`le::PageMapEntry`])
+        pub object_page_map:            u32,
+        /// File offset of iterated page data (LX), often 0
+        pub iterated_data_map:          u32,
+        /// Offset of the resource table (OS/2)
+        pub resource_table:             u32,
+        pub nresources:                 u32,
+        /// Offset of the resident name table (see [`ne::Name`])
+        pub resident_names:             u32,
+        /// Offset of the entry table (see [`le::EntryPoint`])
+        pub entry_table:                u32,
+        pub module_directives:          u32,
+        pub nmodule_directives:         u32,
+        /// Offset of the fixup page table: `npages + 1` [`u32`] offsets into the fixup record table
+        pub fixup_page_table:           u32,
+        /// Offset of the fixup record table (see [`le::Fixup`])
+        pub fixup_record_table:         u32,
+        /// Offset of the import module table: length-prefixed strings
+        pub import_modules:             u32,
+        pub nimport_modules:            u32,
+        /// Offset of the import procedure name table: length-prefixed strings
+        pub import_procedures:          u32,
+        pub per_page_checksums:         u32,
+        /// File offset (*not* relative to this header!) of the first page of data
+        pub data_pages:                 u32,
+        pub npreload_pages:             u32,
+        /// File offset (*not* relative to this header!) of the non-resident name table (see [`ne::Name`])
+        pub nonresident_names:          u32,
+        /// Size of the non-resident name table in bytes
+        pub nonresident_names_size:     u32,
+        pub nonresident_names_checksum: u32,
+        /// 1-based object number of the automatic data object
+        pub auto_data_object:           u32,
+        /// File offset of debug information
+        pub debug_info:                 u32,
+        pub debug_info_size:            u32,
+        pub ninstance_preload_pages:    u32,
+        pub ninstance_demand_pages:     u32,
+        pub heap_size:                  u32,
+        pub stack_size:                 u32,
+        // Reserved
+        #[doc(hidden)] pub _reserved:   Reserved<8>,
+        /// **LE VxDs:** file offset of the Windows version resource
+        pub win_res_offset:             u32,
+        /// **LE VxDs:** size of the Windows version resource
+        pub win_res_size:               u32,
+        /// **LE VxDs:** device ID (e.g. `0x0001` for `VMM`, `0x0006` for `V86MMGR`)
+        pub device_id:                  u16,
+        /// **LE VxDs:** DDK version, e.g. `0x030A`
+        pub ddk_version:                u16,
+    }
+}
+
+impl Header {
+    /// `true` for `"LX"` modules, `false` for `"LE"` modules
+    pub fn is_lx(&self) -> bool { self.signature.buffer() == b"LX" }
+}
+
+/// ∑
+/// [I286](Self::I286) |
+/// [I386](Self::I386) |
+/// [I486](Self::I486) |
+/// ...<br>
+/// [le::Header::cpu]: the minimum CPU required<br>
+/// <br>
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct Cpu(u16le);
+
+impl Cpu {
+    pub const fn new(value: u16) -> Self {
+        Self(u16le::new(value))
+    }
+
+    #[doc = "Intel 80286"                                                       ] pub const I286        : Cpu = Cpu::new(0x01);
+    #[doc = "Intel 80386"                                                       ] pub const I386        : Cpu = Cpu::new(0x02);
+    #[doc = "Intel 80486"                                                       ] pub const I486        : Cpu = Cpu::new(0x03);
+    #[doc = "Intel Pentium"                                                     ] pub const I586        : Cpu = Cpu::new(0x04);
+    #[doc = "Intel i860 (N10)"                                                  ] pub const I860        : Cpu = Cpu::new(0x20);
+    #[doc = "Intel i860 (N11)"                                                  ] pub const N11         : Cpu = Cpu::new(0x21);
+    #[doc = "MIPS R2000 / R3000"                                                ] pub const MIPS1       : Cpu = Cpu::new(0x40);
+    #[doc = "MIPS R6000"                                                        ] pub const MIPS2       : Cpu = Cpu::new(0x41);
+    #[doc = "MIPS R4000"                                                        ] pub const MIPS3       : Cpu = Cpu::new(0x42);
+}
+
+impl std::fmt::Debug for Cpu {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Self::I286      => write!(fmt, "Cpu::I286"),
+            Self::I386      => write!(fmt, "Cpu::I386"),
+            Self::I486      => write!(fmt, "Cpu::I486"),
+            Self::I586      => write!(fmt, "Cpu::I586"),
+            Self::I860      => write!(fmt, "Cpu::I860"),
+            Self::N11       => write!(fmt, "Cpu::N11"),
+            Self::MIPS1     => write!(fmt, "Cpu::MIPS1"),
+            Self::MIPS2     => write!(fmt, "Cpu::MIPS2"),
+            Self::MIPS3     => write!(fmt, "Cpu::MIPS3"),
+            other           => write!(fmt, "Cpu(0x{:04x})", other.0.to_le()),
+        }
+    }
+}
+
+impl FromMemory for Cpu {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
+
+from_memory_flags! {
+    /// [PER_PROCESS_INIT](Self::PER_PROCESS_INIT) |
+    /// [NO_INTERNAL_FIXUPS](Self::NO_INTERNAL_FIXUPS) |
+    /// [LIBRARY](Self::LIBRARY) |
+    /// [VIRTUAL_DEVICE_DRIVER](Self::VIRTUAL_DEVICE_DRIVER) |
+    /// ...<br>
+    /// [le::Header::flags]: module type and requirements<br>
+    /// <br>
+    ///
+    /// The module type is a 3-bit field (`0x38000`): [`LIBRARY`](Self::LIBRARY), [`PROTECTED_MEMORY_LIBRARY`](Self::PROTECTED_MEMORY_LIBRARY),
+    /// [`PHYSICAL_DEVICE_DRIVER`](Self::PHYSICAL_DEVICE_DRIVER), and [`VIRTUAL_DEVICE_DRIVER`](Self::VIRTUAL_DEVICE_DRIVER) overlap, so compare the masked value
+    /// rather than using [`contains`](Self::contains).
+    ///
+    /// ## References
+    /// *   `E32*` flags in `exe386.h`
+    #[repr(transparent)]
+    pub struct ModuleFlags : u32 {
+        /// Zero / no flags set: a program module.
+        const NONE                      = 0;
+        /// Per-process library initialization
+        const PER_PROCESS_INIT          = 0x0000_0004;
+        /// Internal fixups have been applied and removed (the module must load at its preferred base)
+        const NO_INTERNAL_FIXUPS        = 0x0000_0010;
+        /// External fixups have been removed
+        const NO_EXTERNAL_FIXUPS        = 0x0000_0020;
+        /// Presentation Manager type: full screen, not compatible with the window API
+        const NOT_WINDOW_COMPATIBLE     = 0x0000_0100;
+        /// Presentation Manager type: compatible with the window API
+        const WINDOW_COMPATIBLE         = 0x0000_0200;
+        /// Presentation Manager type: uses the window API
+        const USES_WINDOW_API           = 0x0000_0300;
+        /// The linker reported errors; the module isn't loadable
+        const NOT_LOADABLE              = 0x0000_2000;
+        /// Module type: library (DLL)
+        const LIBRARY                   = 0x0000_8000;
+        /// Module type: protected memory library
+        const PROTECTED_MEMORY_LIBRARY  = 0x0001_8000;
+        /// Module type: physical device driver
+        const PHYSICAL_DEVICE_DRIVER    = 0x0002_0000;
+        /// Module type: virtual device driver (VxD)
+        const VIRTUAL_DEVICE_DRIVER     = 0x0002_8000;
+        /// Mask of the module type field
+        const MODULE_TYPE_MASK          = 0x0003_8000;
+        /// Per-process library termination
+        const PER_PROCESS_TERM          = 0x4000_0000;
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(0xC4, std::mem::size_of::<<Header as FromMemory>::Raw>());
+}
@@ -0,0 +1,89 @@
+#[cfg(doc)] use crate::*;
+
+use std::ops::Range;
+
+
+
+from_memory_struct! {
+    /// { [virtual_size](Self::virtual_size), [base_address](Self::base_address), [flags](Self::flags), [page_map_index](Self::page_map_index), [npages](Self::npages) }<br>
+    /// An entry of the LE / LX object table: an object (≈ segment / section) and the range of pages holding its data<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description#Object_Table>
+    /// *   `struct o32_obj` in `exe386.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ObjectEntry {
+        /// Size of the object once loaded, in bytes
+        pub virtual_size:               u32,
+        /// Preferred base address of the object
+        pub base_address:               u32,
+        pub flags:                      ObjectFlags,
+        /// 1-based index of the first [`le::PageMapEntry`] of this object
+        pub page_map_index:             u32,
+        /// Number of [`le::PageMapEntry`]s for this object
+        pub npages:                     u32,
+        // Reserved
+        #[doc(hidden)] pub _reserved:   u32,
+    }
+}
+
+impl ObjectEntry {
+    /// The 0-based indicies of this object's pages in [`le::Reader::page_map`]
+    pub fn page_map_range(&self) -> Range<usize> {
+        let start = (self.page_map_index as usize).saturating_sub(1);
+        start .. start + self.npages as usize
+    }
+}
+
+from_memory_flags! {
+    /// [READABLE](Self::READABLE) |
+    /// [WRITABLE](Self::WRITABLE) |
+    /// [EXECUTABLE](Self::EXECUTABLE) |
+    /// [BIG](Self::BIG) |
+    /// ...<br>
+    /// [le::ObjectEntry::flags]<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   `OBJ*` flags in `exe386.h`
+    #[repr(transparent)]
+    pub struct ObjectFlags : u32 {
+        /// Zero / no flags set.
+        const NONE                      = 0;
+        const READABLE                  = 0x0001;
+        const WRITABLE                  = 0x0002;
+        const EXECUTABLE                = 0x0004;
+        /// Contains resources
+        const RESOURCE                  = 0x0008;
+        const DISCARDABLE               = 0x0010;
+        const SHARED                    = 0x0020;
+        /// Has preload pages
+        const PRELOAD                   = 0x0040;
+        /// Has invalid pages
+        const INVALID                   = 0x0080;
+        /// Has zero-filled pages
+        const ZERO_FILLED               = 0x0100;
+        /// Resident (valid for VxDs / physical device drivers only)
+        const RESIDENT                  = 0x0200;
+        /// Resident and contiguous
+        const RESIDENT_CONTIGUOUS       = 0x0300;
+        /// Resident and long-lockable
+        const RESIDENT_LONG_LOCKABLE    = 0x0400;
+        /// Requires a 16:16 alias
+        const ALIAS16                   = 0x1000;
+        /// 32-bit ("big") default operand / address size
+        const BIG                       = 0x2000;
+        /// Conforming code
+        const CONFORMING                = 0x4000;
+        /// I/O privilege level
+        const IOPL                      = 0x8000;
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(24, std::mem::size_of::<<ObjectEntry as crate::FromMemory>::Raw>());
+}
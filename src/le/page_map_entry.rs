@@ -0,0 +1,76 @@
+#[cfg(doc)] use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Range;
+
+
+
+/// ⨯ { [offset](Self::offset), [size](Self::size), [kind](Self::kind) }<br>
+/// An entry of the LE / LX object page map: where a single page lives in the file<br>
+/// <br>
+///
+/// LE and LX encode these differently (LE stores a page number, LX an offset and size), so [`le::Reader`] resolves both to file offsets.
+///
+/// ## References
+/// *   <http://www.edm2.com/index.php/LX_-_Linear_eXecutable_Module_Format_Description#Object_Page_Table>
+/// *   `struct o32_map` in `exe386.h`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageMapEntry {
+    /// File offset of the page data
+    pub offset:     u64,
+    /// Size of the page data in the file, in bytes (may be less than [`le::Header::page_size`], with the remainder zero-filled)
+    pub size:       u32,
+    pub kind:       PageKind,
+}
+
+impl PageMapEntry {
+    /// The file offsets of the page data (empty for [`PageKind::ZEROED`] and [`PageKind::INVALID`] pages)
+    pub fn file_range(&self) -> Range<u64> {
+        match self.kind {
+            PageKind::ZEROED | PageKind::INVALID    => self.offset .. self.offset,
+            _                                       => self.offset .. self.offset + u64::from(self.size),
+        }
+    }
+}
+
+/// ∑
+/// [LEGAL](Self::LEGAL) |
+/// [ITERATED](Self::ITERATED) |
+/// [INVALID](Self::INVALID) |
+/// [ZEROED](Self::ZEROED) |
+/// ...<br>
+/// [le::PageMapEntry::kind]: how the page data is stored<br>
+/// <br>
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct PageKind(u16);
+
+impl PageKind {
+    pub const fn new(value: u16) -> Self {
+        Self(value)
+    }
+
+    #[doc = "Raw page data"                                                     ] pub const LEGAL       : PageKind = PageKind::new(0);
+    #[doc = "Iterated (run-length encoded) page data"                           ] pub const ITERATED    : PageKind = PageKind::new(1);
+    #[doc = "Invalid page: accessing it faults"                                 ] pub const INVALID     : PageKind = PageKind::new(2);
+    #[doc = "Zero-filled page with no file data"                                ] pub const ZEROED      : PageKind = PageKind::new(3);
+    #[doc = "A range of pages (LX)"                                             ] pub const RANGE       : PageKind = PageKind::new(4);
+    #[doc = "Compressed page data (LX, `EXEPACK:2`)"                            ] pub const COMPRESSED  : PageKind = PageKind::new(5);
+}
+
+impl Debug for PageKind {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::LEGAL         => write!(fmt, "PageKind::LEGAL"),
+            Self::ITERATED      => write!(fmt, "PageKind::ITERATED"),
+            Self::INVALID       => write!(fmt, "PageKind::INVALID"),
+            Self::ZEROED        => write!(fmt, "PageKind::ZEROED"),
+            Self::RANGE         => write!(fmt, "PageKind::RANGE"),
+            Self::COMPRESSED    => write!(fmt, "PageKind::COMPRESSED"),
+            other               => write!(fmt, "PageKind(0x{:04x})", other.0),
+        }
+    }
+}
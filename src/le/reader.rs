@@ -0,0 +1,242 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, SeeklessFile};
+
+use std::convert::*;
+use std::fs::File;
+use std::path::PathBuf;
+
+
+
+/// Wraps a [`ReadAt`] for ease of reading an LE or LX executable by caching [`le::Header`], [`le::ObjectEntry`]s, and [`le::PageMapEntry`]s.<br>
+/// <br>
+///
+/// The remaining tables are read on demand.
+pub struct Reader<R> {
+    src:                        Src,
+    reader:                     R,
+    mz_header:                  mz::Header,
+    header:                     le::Header,
+    objects:                    Vec<le::ObjectEntry>,
+    page_map:                   Vec<le::PageMapEntry>,
+}
+
+impl Reader<SeeklessFile> {
+    /// Open an LE or LX executable and parse the header, object table, and object page map.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path);
+        let src = Src::PathBuf(path);
+        Reader::read_src(SeeklessFile::from(src.anno(file, "error opening le::Reader")?), src)
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    /// Read an LE or LX executable and parse the header, object table, and object page map.  Prefer [`Reader::open`] for better error messages.
+    pub fn read(reader: R) -> io::Result<Self> { Self::read_src(reader, Src::Unknown) }
+
+    /// Read the data of the 1-based page number `page`, as stored in the file.
+    /// [`le::PageKind::ZEROED`] and [`le::PageKind::INVALID`] pages read as empty, and iterated or compressed pages are *not* expanded.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `page` isn't a valid page number
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_page(&self, page: u32) -> io::Result<Vec<u8>> {
+        let range = self.page_or_err(page)?.file_range();
+        self.src.anno(self.reader.read_exact_vec_at((range.end - range.start) as usize, range.start), "error reading le page")
+    }
+
+    /// Read the data of the 1-based object number `object`: every page, zero-padded to [`le::Header::page_size`], and truncated to [`le::ObjectEntry::virtual_size`].
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `object` isn't a valid object number
+    /// *   [`io::ErrorKind::InvalidData`] if the object has iterated or compressed pages (not supported) or out of range pages
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_object_data(&self, object: u32) -> io::Result<Vec<u8>> {
+        let entry = self.object(object).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{}: le object number {} out of range 1 ..= {}", self.src, object, self.objects.len())))?;
+        let pages = self.page_map.get(entry.page_map_range()).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{}: le::ObjectEntry pages out of range of the object page map", self.src)))?;
+        let page_size = self.header.page_size as usize;
+        let virtual_size = entry.virtual_size as usize;
+
+        // page_size is untrusted: pad pages only up to virtual_size, and read page data without preallocating
+        let mut data = Vec::new();
+        for page in pages {
+            if data.len() >= virtual_size { break } // remaining pages would be truncated anyway
+            match page.kind {
+                le::PageKind::LEGAL | le::PageKind::ZEROED | le::PageKind::INVALID => {},
+                other => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: le object {} contains {:?} pages, which aren't supported", self.src, object, other))),
+            }
+            let range = page.file_range();
+            let start = data.len();
+            let page_data = self.src.anno(self.reader.read_exact_vec_at((range.end - range.start) as usize, range.start), "error reading le page")?;
+            data.extend_from_slice(&page_data[.. page_data.len().min(page_size)]);
+            data.resize(start.saturating_add(page_size).min(virtual_size), 0u8);
+        }
+        data.resize(virtual_size, 0u8);
+        Ok(data)
+    }
+
+    /// Read the fixups of the 1-based page number `page`.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `page` isn't a valid page number
+    /// *   [`io::ErrorKind::InvalidData`] if the fixup page table is out of order
+    /// *   [`io::ErrorKind::UnexpectedEof`] if a fixup record was truncated
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_fixups(&self, page: u32) -> io::Result<Vec<le::Fixup>> {
+        let _ = self.page_or_err(page)?;
+        let mut offset = self.header_offset(self.header.fixup_page_table) + 4 * u64::from(page - 1);
+        let start = self.src.anno(u32::from_read_at_advance(&self.reader, &mut offset), "error reading le fixup page table")?;
+        let end   = self.src.anno(u32::from_read_at_advance(&self.reader, &mut offset), "error reading le fixup page table")?;
+        if end < start { return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "le fixup page table out of order")), "error reading le fixup page table") }
+
+        let records = self.src.anno(self.reader.read_exact_vec_at((end - start) as usize, self.header_offset(self.header.fixup_record_table) + u64::from(start)), "error reading le::Fixup")?;
+        self.src.anno(le::Fixup::parse_records(&records[..]), "error decoding le::Fixup")
+    }
+
+    /// Read the resident name table.  The first entry is the module name.
+    pub fn read_resident_names(&self) -> io::Result<Vec<ne::Name>> {
+        let table = self.read_loader_table(self.header.resident_names, "error reading le resident name table")?;
+        self.src.anno(ne::Name::parse_table(&table[..]), "error decoding le resident name table")
+    }
+
+    /// Read the non-resident name table.  The first entry is the module description.
+    pub fn read_nonresident_names(&self) -> io::Result<Vec<ne::Name>> {
+        let table = self.src.anno(self.reader.read_exact_vec_at(self.header.nonresident_names_size as usize, self.header.nonresident_names.into()), "error reading le non-resident name table")?;
+        self.src.anno(ne::Name::parse_table(&table[..]), "error decoding le non-resident name table")
+    }
+
+    /// Read the entry table.
+    pub fn read_entry_points(&self) -> io::Result<Vec<le::EntryPoint>> {
+        let table = self.read_loader_table(self.header.entry_table, "error reading le entry table")?;
+        self.src.anno(le::EntryPoint::parse_table(&table[..]), "error decoding le entry table")
+    }
+
+    /// Read the import module table: the names of imported modules (e.g. `"DOSCALLS"`.)
+    ///
+    /// [`le::FixupTarget::ImportOrdinal::module`] and [`le::FixupTarget::ImportName::module`] are 1-based indicies into this list.
+    pub fn read_import_modules(&self) -> io::Result<Vec<String>> {
+        let mut offset = self.header_offset(self.header.import_modules);
+        (0 .. self.header.nimport_modules).map(|_| {
+            let name = self.read_pascal_at(offset, "error reading le import module table")?;
+            offset += 1 + name.len() as u64;
+            Ok(ne::latin1(&name[..]))
+        }).collect()
+    }
+
+    /// Read a length-prefixed string from the import procedure name table, at `offset` relative to the start of said table.
+    pub fn read_import_procedure_name(&self, offset: u32) -> io::Result<String> {
+        let name = self.read_pascal_at(self.header_offset(self.header.import_procedures) + u64::from(offset), "error reading le import procedure name")?;
+        Ok(ne::latin1(&name[..]))
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
+        let mz_header = src.anno(mz::Header::from_read_at(&reader, 0), "error reading mz::Header")?;
+        let base = u64::from(mz_header.pe_header_start);
+
+        let header = src.anno(le::Header::from_read_at(&reader, base), "error reading le::Header")?;
+        if !matches!(header.signature.buffer(), b"LE" | b"LX") {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "le::Header::signature != \"LE\" or \"LX\"")), "error reading le::Header");
+        }
+        if header.byte_order != 0 || header.word_order != 0 {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "le::Header: big endian modules aren't supported")), "error reading le::Header");
+        }
+        if header.is_lx() && header.page_shift >= 32 {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "le::Header::page_shift >= 32")), "error reading le::Header");
+        }
+
+        // nobjects and npages are untrusted u32s: grow as entries are actually read instead of preallocating
+        let mut offset = base + u64::from(header.object_table);
+        let mut objects = Vec::new();
+        for _ in 0 .. header.nobjects {
+            objects.push(src.anno(le::ObjectEntry::from_read_at_advance(&reader, &mut offset), "error reading le::ObjectEntry")?);
+        }
+
+        let mut offset = base + u64::from(header.object_page_map);
+        let mut page_map = Vec::new();
+        for page in 1 ..= header.npages {
+            if header.is_lx() {
+                let data_offset = src.anno(u32::from_read_at_advance(&reader, &mut offset), "error reading le::PageMapEntry")?;
+                let size        = src.anno(u16::from_read_at_advance(&reader, &mut offset), "error reading le::PageMapEntry")?;
+                let kind        = src.anno(u16::from_read_at_advance(&reader, &mut offset), "error reading le::PageMapEntry")?;
+                let kind = le::PageKind::new(kind);
+                let data = if kind == le::PageKind::ITERATED && header.iterated_data_map != 0 { header.iterated_data_map } else { header.data_pages };
+                let offset = u64::from(data) + (u64::from(data_offset) << header.page_shift);
+                page_map.push(le::PageMapEntry { offset, size: size.into(), kind });
+            } else {
+                let mut e = [0u8; 4];
+                src.anno(reader.read_exact_at_advance(&mut e[..], &mut offset), "error reading le::PageMapEntry")?;
+                let number = u32::from_be_bytes([0, e[0], e[1], e[2]]);
+                let size = if page == header.npages && header.page_shift != 0 { header.page_shift } else { header.page_size };
+                let offset = u64::from(header.data_pages) + u64::from(number.saturating_sub(1)) * u64::from(header.page_size);
+                page_map.push(le::PageMapEntry { offset, size, kind: le::PageKind::new(e[3].into()) });
+            }
+        }
+
+        Ok(Self { src, reader, mz_header, header, objects, page_map })
+    }
+
+    /// Read from `start` to the end of the loader section (object table, page map, resident names, entry table, ...)
+    fn read_loader_table(&self, start: u32, note: &str) -> io::Result<Vec<u8>> {
+        let end = if self.header.loader_section_size != 0 {
+            self.header.object_table.saturating_add(self.header.loader_section_size)
+        } else {
+            self.header.fixup_page_table
+        };
+        self.src.anno(self.reader.read_up_to_vec_at(end.saturating_sub(start) as usize, self.header_offset(start)), note)
+    }
+
+    fn read_pascal_at(&self, offset: u64, note: &str) -> io::Result<Vec<u8>> {
+        let mut buf = [0u8; 256];
+        let n = self.src.anno(self.reader.read_up_to_at(&mut buf[..], offset), note)?;
+        let (name, _) = self.src.anno(ne::split_pascal(&buf[..n]), note)?;
+        Ok(name.to_vec())
+    }
+
+    fn page_or_err(&self, page: u32) -> io::Result<&le::PageMapEntry> {
+        self.page(page).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{}: le page number {} out of range 1 ..= {}", self.src, page, self.page_map.len())))
+    }
+}
+
+impl<R> Reader<R> {
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn mz_header(&self) -> &mz::Header { &self.mz_header }
+    #[allow(missing_docs)] pub fn header(&self) -> &le::Header { &self.header }
+    #[allow(missing_docs)] pub fn objects(&self) -> &[le::ObjectEntry] { &self.objects[..] }
+    #[allow(missing_docs)] pub fn page_map(&self) -> &[le::PageMapEntry] { &self.page_map[..] }
+
+    /// Get an object by 1-based object number, as used by [`le::Header::eip_object`], [`le::EntryPoint::object`], etc.
+    pub fn object(&self, object: impl TryInto<usize>) -> Option<&le::ObjectEntry> {
+        object.try_into().ok().and_then(|o| o.checked_sub(1)).and_then(|idx| self.objects.get(idx))
+    }
+
+    /// Get a page by 1-based page number, as used by [`le::ObjectEntry::page_map_index`] and [`le::Reader::read_fixups`]
+    pub fn page(&self, page: impl TryInto<usize>) -> Option<&le::PageMapEntry> {
+        page.try_into().ok().and_then(|p| p.checked_sub(1)).and_then(|idx| self.page_map.get(idx))
+    }
+
+    fn header_offset(&self, offset: u32) -> u64 { u64::from(self.mz_header.pe_header_start) + u64::from(offset) }
+}
+
+
+
+#[test] fn read_untrusted_counts() {
+    let mut image = vec![0u8; 0x200];
+    image[0 .. 2].copy_from_slice(b"MZ");
+    image[0x3C .. 0x40].copy_from_slice(&0x40u32.to_le_bytes());   // pe_header_start
+    image[0x40 .. 0x42].copy_from_slice(b"LE");
+    let set = |image: &mut Vec<u8>, field: usize, value: u32| image[0x40 + field .. 0x40 + field + 4].copy_from_slice(&value.to_le_bytes());
+    assert_eq!(Reader::read(MemoryReadAt(image.clone())).unwrap().objects().len(), 0);
+
+    let mut objects = image.clone();
+    set(&mut objects, 68, u32::MAX); // nobjects
+    assert_eq!(Reader::read(MemoryReadAt(objects)).map(|_| ()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    let mut pages = image.clone();
+    set(&mut pages, 20, u32::MAX); // npages
+    assert_eq!(Reader::read(MemoryReadAt(pages)).map(|_| ()).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+
+    set(&mut image, 0x8C, u32::MAX); // nonresident_names_size
+    assert_eq!(Reader::read(MemoryReadAt(image)).unwrap().read_nonresident_names().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
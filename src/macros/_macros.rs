@@ -42,6 +42,13 @@ macro_rules! from_memory_struct {
                                 ),*
                             })
                         }
+                        fn to_raw(&self) -> Self::Raw {
+                            Raw {
+                                $(
+                                    $field_ident : <$field_ty as $crate::FromMemory>::to_raw(&self.$field_ident)
+                                ),*
+                            }
+                        }
                     }
                 }
             }
@@ -83,6 +90,9 @@ macro_rules! from_memory_flags {
                 fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> {
                     Ok(Self::from_bits_truncate(<$struct_inner as $crate::FromMemory>::from_raw(raw)?))
                 }
+                fn to_raw(&self) -> Self::Raw {
+                    <$struct_inner as $crate::FromMemory>::to_raw(&self.bits())
+                }
             }
         )*
     };
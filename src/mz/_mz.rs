@@ -0,0 +1,371 @@
+//! The MS-DOS EXE/DLL format, also known as MZ after its signature (the initials of Microsoft engineer **M**ark **Z**bykowski)
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Header`]        | The basic MZ/DOS header, found at offset 0 of `.exe` and `.dll` files.<br>For modern binaries, the main field of note is [`pe_header_start`](struct.Header.html#structfield.pe_header_start) - the [`pe::Header`] offset.
+//! | [`Reader`]        | Reads a pure MS-DOS executable: [`Header`], [`Relocation`]s, and the load module.
+//! | [`Relocation`]    | A relocation entry for 16-bit MS-DOS binaries.  Ignorable for modern 32/64-bit binaries.
+//! | [`FarPointer`]    | A 16-bit `segment:offset` pair, such as the entry point `CS:IP`.
+//! | [`Stub`]          | The MS-DOS program preceeding the [`pe::Header`], typically printing "This program cannot be run in DOS mode."
+//! | [`Bytes`]         | MZ binaries have many [`u16`] values in different units, these newtypes help avoid confusion.
+//! | [`Pages`]         | <code>[Pages]\(1\) = [Bytes]\(16\)</code>
+//! | [`Paragraphs`]    | <code>[Paragraphs]\(1\) = [Pages]\(32\) = [Bytes]\(512\)</code>
+//!
+//! ## References
+//! *   <https://wiki.osdev.org/MZ>
+//! *   <https://en.wikipedia.org/wiki/DOS_MZ_executable>
+
+mod reader;                         pub use reader::*;
+
+use crate::*;
+use crate::io::ReadAtExt;
+
+use bytemuck::*;
+use maulingmonkey_io_adapters::ReadAt;
+
+use std::convert::*;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Read};
+
+
+
+from_memory_struct! {
+    /// The basic MZ/DOS header, found at offset 0 of `.exe` and `.dll` files.<br>
+    /// For modern binaries, the main field of note is [`pe_header_start`](struct.Header.html#structfield.pe_header_start) - the [`pe::Header`] offset.
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Header {
+        /// `b"MZ"` for valid MZ/DOS modules.
+        pub signature:          abistr::CStrBuf<[u8; 2]>,
+        /// Size of the last page.
+        pub last_page:          Bytes,
+        /// Number of 512-byte pages.
+        pub pages:              Pages,
+        /// Number of 16-bit [`mz::Relocation`]s.
+        pub nrelocs:            u16,
+        /// Size of this [`mz::Header`] (+ any custom header data, [`mz::Relocation`]s?).  Does *not* include [`pe::Header`].
+        pub hdrsize:            Paragraphs,
+        /// Memory *required* by the program, excluding the [PSP](https://en.wikipedia.org/wiki/Program_Segment_Prefix) and image.
+        pub minalloc:           Paragraphs,
+        /// Memory *requested* by the program.
+        pub maxalloc:           Paragraphs,
+        /// Initial value of the 16-bit [Stack Segment](https://en.wikipedia.org/wiki/X86_memory_segmentation) register.
+        pub ss:                 u16,
+        /// Initial value of the 16-bit [Stack Pointer](https://en.wikipedia.org/wiki/X86_memory_segmentation) register.
+        pub sp:                 u16,
+        /// When added to the sum of all other [`u16`]s in the file, the result should be zero.
+        pub checksum:           u16,
+        /// Initial value of the 16-bit [Instruction Pointer](https://en.wikipedia.org/wiki/X86_memory_segmentation) register.
+        pub ip:                 u16,
+        /// Initial value of the 16-bit [Code Segment](https://en.wikipedia.org/wiki/X86_memory_segmentation) register.
+        pub cs:                 u16,
+        /// Offset from the start of this [`mz::Header`] (typically @ offset 0) to the first [`mz::Relocation`].
+        pub relocs:             u16,
+        /// Typically 0 for "the main executable.  See <https://retrocomputing.stackexchange.com/a/25742>.
+        pub overlay:            u16,
+
+        /// The fields below are [`pe`] extensions to the [`mz`] format, and may not be present in a more basic MS-DOS or Windows 3.1 executable.
+        pub _pe_extensions:     (),
+
+        // Reserved
+        #[doc(hidden)] pub _reserved_a: Reserved<8>,
+        /// Typically 0.
+        pub oem_id:             u16,
+        /// Typically 0.
+        pub oem_info:           u16,
+        // Reserved
+        #[doc(hidden)] pub _reserved_b: Reserved<20>,
+        /// Offset from the start of this [`mz::Header`] (typically @ offset 0) to the [`pe::Header`].
+        pub pe_header_start:    u32,
+
+        #[doc(hidden)] pub _non_exhaustive:    (),
+    }
+
+    /// A relocation entry for 16-bit MS-DOS binaries.
+    /// Ignorable for modern 32/64-bit binaries.<br>
+    /// <code>struct { pub [offset](struct.Relocation.html#structfield.offset): [u16], pub [segment](struct.Relocation.html#structfield.segment): [u16] }</code>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Relocation {
+        pub offset:             u16,
+        pub segment:            u16,
+    }
+}
+
+/// ⨯ { [offset](Self::offset), [data](Self::data) }<br>
+/// The 16-bit MS-DOS program between the [`mz::Header`] and the [`pe::Header`].<br>
+/// <br>
+///
+/// Linkers emit a standard stub that prints "This program cannot be run in DOS mode." and exits.
+/// Anything else ([`StubKind::Nonstandard`]) suggests a packed, hand-crafted, or genuinely dual-mode binary.
+/// Note that MSVC's undocumented "Rich" header ([`pe::RichHeader`]), if present, trails the stub program and is included in [`data`](Self::data).
+#[derive(Clone, Debug, Default)]
+pub struct Stub {
+    /// File offset of [`data`](Self::data): the end of the header paragraphs ([`Header::hdrsize`])
+    pub offset:             u32,
+    /// Everything from [`offset`](Self::offset) up to [`Header::pe_header_start`]
+    pub data:               Vec<u8>,
+}
+
+/// ∑ [Empty](Self::Empty) | [Standard](Self::Standard) | [Nonstandard](Self::Nonstandard)<br>
+/// What kind of MS-DOS program a [`Stub`] contains<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StubKind {
+    /// There is no stub - [`Header::pe_header_start`] immediately follows the header
+    Empty,
+    /// The standard linker stub, printing "This program cannot be run in DOS mode." (MSVC `link`, `lld-link`, GNU `ld`, ...)
+    Standard,
+    /// Any other stub program
+    Nonstandard,
+}
+
+/// ⨯ { [segment](Self::segment), [offset](Self::offset) }<br>
+/// A 16-bit real mode `segment:offset` address, such as the initial `CS:IP` or `SS:SP` of an MS-DOS program.<br>
+/// <br>
+///
+/// Segments in an [`mz::Header`] are relative to the start of the load module (which DOS relocates by adding the load segment.)
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FarPointer {
+    pub segment:            u16,
+    pub offset:             u16,
+}
+
+// A paragraph is 16 bytes in size. A page (or block) is 512 bytes long.
+
+/// ≈[`u16`] - 1 **[Pages]** = 32 [Paragraphs] = 512 [Bytes]
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+#[derive(Pod, Zeroable)]
+pub struct Pages(u16);
+
+/// ≈[`u16`] - 1 [Pages] = 32 **[Paragraphs]** = 512 [Bytes]
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+#[derive(Pod, Zeroable)]
+pub struct Paragraphs(u16);
+
+/// ≈[`u16`] - 1 [Pages] = 32 [Paragraphs] = 512 **[Bytes]**
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+#[derive(Pod, Zeroable)]
+pub struct Bytes(u16);
+
+
+
+impl Header {
+    /// Reads an [`mz::Header`] from `read`
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::InvalidData`] if [`signature`](#structfield.signature) ≠ `"MZ"`
+    /// * No error if <code>sum([`checksum`](#structfield.checksum), ...entire file...)</code> ≠ `0`
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read` didn't contain enough data
+    pub fn read_from(read: &mut impl Read) -> io::Result<Self> {
+        let s = Header::from_io(read)?;
+        if s.signature.buffer() != b"MZ" {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "mz::Header::signature != \"MZ\""))
+        } else {
+            Ok(s)
+        }
+    }
+}
+
+impl Header {
+    /// The size of the MS-DOS file image in bytes, as described by [`pages`](#structfield.pages) and [`last_page`](#structfield.last_page).
+    ///
+    /// For [`pe`] files this typically only covers the [`mz::Header`] and [`mz::Stub`], not the entire file.
+    pub fn file_size(&self) -> u32 {
+        let pages : u32 = self.pages.pages::<u16>().into();
+        match self.last_page.bytes::<u16>() {
+            0       => pages * 512,
+            last    => pages.saturating_sub(1) * 512 + u32::from(last),
+        }
+    }
+
+    /// Compute the value [`checksum`](#structfield.checksum) should have for the [`file_size`](Self::file_size) bytes of `read_at`.
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read_at` is shorter than [`file_size`](Self::file_size)
+    /// * [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn compute_checksum(&self, read_at: &impl ReadAt) -> io::Result<u16> {
+        let mut image = vec![0u8; usize::try_from(self.file_size()).unwrap_or(!0)];
+        read_at.read_exact_at(&mut image[..], 0)?;
+        Ok(checksum_of(&image))
+    }
+
+    /// Verify [`checksum`](#structfield.checksum) against [`compute_checksum`](Self::compute_checksum).
+    ///
+    /// MS-DOS never checked this, and most linkers leave it `0`, so mismatches are common and mostly harmless.
+    /// Both the two's complement ("sums to zero") and one's complement ("sums to `0xFFFF`") conventions are accepted.
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::InvalidData`] if the checksum doesn't match
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read_at` is shorter than [`file_size`](Self::file_size)
+    /// * [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn verify_checksum(&self, read_at: &impl ReadAt) -> io::Result<()> {
+        let expected = self.compute_checksum(read_at)?;
+        if self.checksum != expected && self.checksum != expected.wrapping_sub(1) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("mz::Header::checksum (0x{:04x}) != computed checksum (0x{:04x})", self.checksum, expected)));
+        }
+        Ok(())
+    }
+}
+
+/// The two's complement of the sum of all [`u16`]s in `image`, excluding [`Header::checksum`] itself
+fn checksum_of(image: &[u8]) -> u16 {
+    const CHECKSUM_OFFSET : usize = 0x12;
+    let mut sum = 0u16;
+    for (i, w) in image.chunks(2).enumerate() {
+        if i * 2 == CHECKSUM_OFFSET { continue }
+        sum = sum.wrapping_add(u16::from_le_bytes([w[0], *w.get(1).unwrap_or(&0)]));
+    }
+    sum.wrapping_neg()
+}
+
+impl Relocation {
+    /// Reads an [`mz::Relocation`] from `read`
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read` didn't contain enough data
+    pub fn read_from(read: &mut impl Read) -> io::Result<Self> {
+        Relocation::from_io(read)
+    }
+
+    /// The `segment:offset` of the [`u16`] segment value to patch, relative to the start of the load module
+    pub fn target(&self) -> FarPointer {
+        FarPointer { segment: self.segment, offset: self.offset }
+    }
+}
+
+impl FarPointer {
+    /// `segment * 16 + offset`: the byte offset this points to, relative to the start of the load module
+    pub fn linear(&self) -> u32 {
+        u32::from(self.segment) * 16 + u32::from(self.offset)
+    }
+}
+
+impl Stub {
+    /// The [standard linker stub](StubKind::Standard) program, followed by its `$`-terminated message.
+    pub const STANDARD : &'static [u8] = b"\x0E\x1F\xBA\x0E\x00\xB4\x09\xCD\x21\xB8\x01\x4C\xCD\x21This program cannot be run in DOS mode.\r\r\n$";
+
+    /// Reads the [`mz::Stub`] between `header` and [`Header::pe_header_start`] from `read_at`
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read_at` didn't contain enough data
+    /// * [`io::Error`] forwarded from [`ReadAtExt::read_exact_vec_at`]
+    pub fn read_from(read_at: &impl ReadAt, header: &Header) -> io::Result<Self> {
+        let offset = header.hdrsize.bytes::<u32>().max(std::mem::size_of::<<Header as FromMemory>::Raw>() as u32);
+        let data = read_at.read_exact_vec_at(header.pe_header_start.saturating_sub(offset) as usize, offset.into())?;
+        Ok(Self { offset, data })
+    }
+
+    pub fn kind(&self) -> StubKind {
+        if self.data.iter().all(|b| *b == 0) {
+            StubKind::Empty
+        } else if self.data.starts_with(Self::STANDARD) {
+            StubKind::Standard
+        } else {
+            StubKind::Nonstandard
+        }
+    }
+
+    /// The `$`-terminated message the stub prints (via `INT 21h` / `AH=09h`), if it can be found.
+    ///
+    /// This is a heuristic: the standard stub's message is always found, nonstandard stubs are searched for `"This program"`.
+    pub fn message(&self) -> Option<&[u8]> {
+        let start = if self.kind() == StubKind::Standard { 14 } else {
+            self.data.windows(12).position(|w| w == b"This program")?
+        };
+        let msg = &self.data[start..];
+        let end = msg.iter().position(|b| *b == b'$')?;
+        Some(&msg[..end])
+    }
+}
+
+impl Pages {
+    pub fn pages<N: From<u16>>(&self)         -> N { N::from(self.0) }
+    pub fn paragraphs<N: From<u32>>(&self)    -> N { N::from(self.0 as u32 * (512 / 16)) }
+    pub fn bytes<N: From<u32>>(&self)         -> N { N::from(self.0 as u32 * 512) }
+}
+
+impl Paragraphs {
+    pub fn paragraphs<N: From<u16>>(&self)    -> N { N::from(self.0) }
+    pub fn bytes<N: From<u32>>(&self)         -> N { N::from(self.0 as u32 * 16) }
+}
+
+impl Bytes {
+    pub fn bytes<N: From<u16>>(&self)         -> N { N::from(self.0) }
+}
+
+impl FromMemory for Pages {
+    type Raw    = u16le;
+    type Error  = io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Pages(raw.to_le())) }
+    fn to_raw(&self) -> Self::Raw { u16le::new(self.0) }
+}
+
+impl FromMemory for Paragraphs {
+    type Raw    = u16le;
+    type Error  = io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Paragraphs(raw.to_le())) }
+    fn to_raw(&self) -> Self::Raw { u16le::new(self.0) }
+}
+
+impl FromMemory for Bytes {
+    type Raw    = u16le;
+    type Error  = io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Bytes(raw.to_le())) }
+    fn to_raw(&self) -> Self::Raw { u16le::new(self.0) }
+}
+
+
+
+// TODO: From<Header> for raw::Header ?
+
+impl Debug for FarPointer   { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "{:04x}:{:04x}", self.segment, self.offset) } }
+impl Debug for Bytes        { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "{} bytes",       self.0) } }
+impl Debug for Paragraphs   { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "{} paragraphs",  self.0) } }
+impl Debug for Pages        { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "{} pages",       self.0) } }
+
+
+
+#[test] fn layout() {
+    use std::mem::*;
+
+    assert_eq!(size_of::<<Header as FromMemory>::Raw>(), 64);
+    assert_eq!(size_of::<<Relocation as FromMemory>::Raw>(), 4);
+    assert_eq!(align_of::<<Header as FromMemory>::Raw>(), align_of::<u32>());
+    assert_eq!(align_of::<<Relocation as FromMemory>::Raw>(), align_of::<u16>());
+}
+
+#[test] fn stub() {
+    let mut data = Stub::STANDARD.to_vec();
+    data.resize(64, 0);
+    let stub = Stub { offset: 64, data };
+    assert_eq!(stub.kind(), StubKind::Standard);
+    assert_eq!(stub.message(), Some(&b"This program cannot be run in DOS mode.\r\r\n"[..]));
+
+    let stub = Stub { offset: 64, data: b"\xBA\x10\x00\x0E\x1F\xB4\x09\xCD\x21\xB8\x01\x4C\xCD\x21\x90\x90This program must be run under Win32\r\n$".to_vec() };
+    assert_eq!(stub.kind(), StubKind::Nonstandard);
+    assert_eq!(stub.message(), Some(&b"This program must be run under Win32\r\n"[..]));
+
+    assert_eq!(Stub::default().kind(), StubKind::Empty);
+    assert_eq!(Stub::default().message(), None);
+
+    let mut image = vec![0u8; 64];
+    image.extend_from_slice(Stub::STANDARD);
+    let mut header = Header { hdrsize: Paragraphs(4), pe_header_start: image.len() as u32, .. Default::default() };
+    let stub = Stub::read_from(&crate::io::MemoryReadAt(image.clone()), &header).unwrap();
+    assert_eq!((stub.offset, stub.kind()), (64, StubKind::Standard));
+    header.pe_header_start = u32::MAX; // doesn't try to allocate 4 GiB
+    assert_eq!(Stub::read_from(&crate::io::MemoryReadAt(image), &header).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test] fn checksum() {
+    let mut image = vec![0u8; 0x20];
+    image[0x00] = 0x01;
+    image[0x12] = 0xFF; // ignored
+    image[0x1F] = 0x02;
+    assert_eq!(checksum_of(&image), 0u16.wrapping_sub(0x0201));
+    assert_eq!(checksum_of(&image[..0x1F]), 0u16.wrapping_sub(0x0001));
+}
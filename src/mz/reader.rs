@@ -0,0 +1,153 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, SeeklessFile};
+
+use std::convert::*;
+use std::fs::File;
+use std::ops::Range;
+use std::path::PathBuf;
+
+
+
+/// Wraps a [`ReadAt`] for ease of reading a pure MS-DOS executable by caching [`mz::Header`] and [`mz::Relocation`]s.<br>
+/// <br>
+///
+/// Unlike [`pe::Reader`], this never looks at [`mz::Header::pe_header_start`] - which, for genuine MS-DOS programs, overlaps
+/// whatever happened to follow the 28 byte DOS header (often the [`mz::Relocation`] table or code.)
+pub struct Reader<R> {
+    src:                        Src,
+    reader:                     R,
+    header:                     mz::Header,
+    relocations:                Vec<mz::Relocation>,
+}
+
+impl Reader<SeeklessFile> {
+    /// Open an MS-DOS executable and parse the header and relocations.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path);
+        let src = Src::PathBuf(path);
+        Reader::read_src(SeeklessFile::from(src.anno(file, "error opening mz::Reader")?), src)
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    /// Read an MS-DOS executable and parse the header and relocations.  Prefer [`Reader::open`] for better error messages.
+    pub fn read(reader: R) -> io::Result<Self> { Self::read_src(reader, Src::Unknown) }
+
+    /// Read the load module: the program image DOS copies into memory, following the header paragraphs ([`mz::Header::hdrsize`])
+    /// and ending at [`mz::Header::file_size`].  Anything past that is an overlay, not loaded by DOS.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the file is shorter than [`mz::Header::file_size`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_load_module(&self) -> io::Result<Vec<u8>> {
+        let range = self.load_module_range();
+        let mut data = vec![0u8; (range.end - range.start) as usize];
+        self.src.anno(self.reader.read_exact_at(&mut data[..], range.start.into()), "error reading mz load module")?;
+        Ok(data)
+    }
+
+    /// Verify [`mz::Header::checksum`].  See [`mz::Header::verify_checksum`] for caveats.
+    pub fn verify_checksum(&self) -> io::Result<()> {
+        self.src.anno(self.header.verify_checksum(&self.reader), "error verifying mz::Header::checksum")
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
+        // Genuine MS-DOS programs only need the first 28 bytes of mz::Header, and may be shorter than the full 64 bytes
+        let mut raw = [0u8; 64];
+        let n = src.anno(reader.read_up_to_at(&mut raw[..], 0), "error reading mz::Header")?;
+        if n < 0x1C { return src.anno(Err(io::ErrorKind::UnexpectedEof.into()), "error reading mz::Header") }
+
+        let header = src.anno(mz::Header::from_memory(&mut &raw[..]), "error reading mz::Header")?;
+        if !matches!(header.signature.buffer(), b"MZ" | b"ZM") {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "mz::Header::signature != \"MZ\"")), "error reading mz::Header");
+        }
+        if header.hdrsize.bytes::<u32>() > header.file_size() {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "mz::Header::hdrsize > mz::Header::file_size()")), "error reading mz::Header");
+        }
+
+        let mut relocations = vec![mz::Relocation::default(); header.nrelocs.into()];
+        let mut offset = u64::from(header.relocs);
+        for reloc in relocations.iter_mut() {
+            *reloc = src.anno(mz::Relocation::from_read_at_advance(&reader, &mut offset), "error reading mz::Relocation")?;
+        }
+
+        Ok(Self { src, reader, header, relocations })
+    }
+}
+
+impl<R> Reader<R> {
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn header(&self) -> &mz::Header { &self.header }
+    #[allow(missing_docs)] pub fn relocations(&self) -> &[mz::Relocation] { &self.relocations[..] }
+
+    /// Initial `CS:IP`, relative to the start of the load module
+    pub fn entry_point(&self) -> mz::FarPointer { mz::FarPointer { segment: self.header.cs, offset: self.header.ip } }
+
+    /// Initial `SS:SP`, relative to the start of the load module
+    pub fn initial_stack(&self) -> mz::FarPointer { mz::FarPointer { segment: self.header.ss, offset: self.header.sp } }
+
+    /// The file offsets of the load module (see [`read_load_module`](Reader::read_load_module))
+    pub fn load_module_range(&self) -> Range<u32> {
+        self.header.hdrsize.bytes() .. self.header.file_size()
+    }
+}
+
+
+
+#[cfg(test)] fn fixture() -> Vec<u8> {
+    let mut image = Vec::new();
+    for field in [
+        u16::from_le_bytes(*b"MZ"),
+        68,         // last_page: file_size = 68
+        1,          // pages
+        2,          // nrelocs
+        3,          // hdrsize (paragraphs)
+        0x10,       // minalloc
+        0xFFFF,     // maxalloc
+        0x0001,     // ss
+        0x0100,     // sp
+        0,          // checksum
+        0x0004,     // ip
+        0x0000,     // cs
+        0x1C,       // relocs
+        0,          // overlay
+    ].iter() { image.extend_from_slice(&field.to_le_bytes()) }
+    for (offset, segment) in [(0x0002u16, 0x0000u16), (0x0010, 0x0001)].iter() {
+        image.extend_from_slice(&offset.to_le_bytes());
+        image.extend_from_slice(&segment.to_le_bytes());
+    }
+    image.resize(48, 0);
+    image.extend((0 .. 20).map(|b| 0xA0 + b)); // load module
+    image.extend_from_slice(b"OVERLAY");
+    image
+}
+
+#[test] fn read() {
+    let mz = Reader::read(MemoryReadAt(fixture())).unwrap();
+    assert_eq!(mz.header().file_size(), 68);
+    assert_eq!(mz.relocations().iter().map(|r| (r.offset, r.segment)).collect::<Vec<_>>(), [(0x0002, 0x0000), (0x0010, 0x0001)]);
+    assert_eq!(mz.entry_point(), mz::FarPointer { segment: 0x0000, offset: 0x0004 });
+    assert_eq!(mz.entry_point().linear(), 0x0004);
+    assert_eq!(mz.initial_stack(), mz::FarPointer { segment: 0x0001, offset: 0x0100 });
+    assert_eq!(mz.initial_stack().linear(), 0x0110);
+    assert_eq!(mz.load_module_range(), 48 .. 68);
+    assert_eq!(mz.read_load_module().unwrap(), (0 .. 20).map(|b| 0xA0 + b).collect::<Vec<u8>>()); // excludes the overlay
+}
+
+#[test] fn read_malformed() {
+    let read = |image: &[u8]| Reader::read(MemoryReadAt(image.to_vec())).map(|_| ()).unwrap_err().kind();
+    let image = fixture();
+    assert_eq!(read(&image[.. 0x1B]), io::ErrorKind::UnexpectedEof);    // truncated header
+    assert_eq!(read(&image[.. 0x20]), io::ErrorKind::UnexpectedEof);    // truncated relocations
+    assert_eq!(read(&[b"XX", &image[2..]].concat()), io::ErrorKind::InvalidData);
+
+    let mut hdrsize = image.clone();
+    hdrsize[8] = 5; // 80 bytes > file_size
+    assert_eq!(read(&hdrsize), io::ErrorKind::InvalidData);
+
+    let truncated = Reader::read(MemoryReadAt(image[.. 60].to_vec())).unwrap();
+    assert_eq!(truncated.read_load_module().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,45 @@
+//! The 16-bit Windows 1.x - 3.x / OS/2 1.x **N**ew **E**xecutable format.
+//!
+//! | Type  | Desc  |
+//! | ------| ------|
+//! | [`Header`]            | `IMAGE_OS2_HEADER`, found at [`mz::Header::pe_header_start`].  Offsets of every other table.
+//! | [`Reader`]            | Reads the [`Header`] and [`SegmentEntry`]s, and the other tables on demand.
+//! | [`SegmentEntry`]      | Where a code or data segment lives in the file, and how to load it.
+//! | [`Relocation`]        | A per-segment fixup, targeting another segment, an import, or an OS fixup.
+//! | [`ResourceType`]      | A group of [`Resource`]s sharing a type (e.g. `RT_ICON`.)
+//! | [`Name`]              | An entry of the resident or non-resident name table: an exported name and its ordinal.
+//! | [`EntryPoint`]        | An entry of the entry table: the segment and offset of an ordinal.
+//!
+//! ## References
+//! *   <https://wiki.osdev.org/NE>
+//! *   <https://www.fileformat.info/format/exe/corion-ne.htm>
+//! *   `newexe.h` from the Windows 3.1 DDK
+//! *   `IMAGE_OS2_HEADER` in `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\winnt.h`
+
+mod entry_point;                    pub use entry_point::*;
+mod header;                         pub use header::*;
+mod module_flags;                   pub use module_flags::*;
+mod name;                           pub use name::*;
+mod reader;                         pub use reader::*;
+mod relocation;                     pub use relocation::*;
+mod resource;                       pub use resource::*;
+mod segment_entry;                  pub use segment_entry::*;
+mod target_os;                      pub use target_os::*;
+
+#[cfg(doc)] use crate::*;
+
+
+
+/// Decode an 8-bit string (typically ASCII, otherwise of unknown codepage) as Latin-1, which round trips losslessly.
+pub(crate) fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| char::from(*b)).collect()
+}
+
+/// Split a length-prefixed ("Pascal") string off the front of `data`.
+pub(crate) fn split_pascal(data: &[u8]) -> std::io::Result<(&[u8], &[u8])> {
+    let eof = || std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "ne: length-prefixed string extends past end of table");
+    let (len, rest) = data.split_first().ok_or_else(eof)?;
+    let len = usize::from(*len);
+    if rest.len() < len { return Err(eof()) }
+    Ok(rest.split_at(len))
+}
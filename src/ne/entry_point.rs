@@ -0,0 +1,101 @@
+#[cfg(doc)] use crate::*;
+
+use std::io;
+
+
+
+/// ⨯ { [ordinal](Self::ordinal), [kind](Self::kind), [segment](Self::segment), [offset](Self::offset), [flags](Self::flags) }<br>
+/// A used entry of the NE entry table<br>
+/// <br>
+///
+/// Unused entries are skipped, but still consume ordinals.
+///
+/// ## References
+/// *   <https://wiki.osdev.org/NE#Entry_Table>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    /// 1-based ordinal, as referenced by [`ne::Name::ordinal`] and importing modules
+    pub ordinal:    u16,
+    pub kind:       EntryKind,
+    /// 1-based segment number ([`ne::Reader::segment`]), or 0 for [`EntryKind::Constant`]
+    pub segment:    u8,
+    /// Offset within [`segment`](Self::segment), or the value of an [`EntryKind::Constant`]
+    pub offset:     u16,
+    pub flags:      EntryFlags,
+}
+
+/// ∑ [Fixed](Self::Fixed) | [Movable](Self::Movable) | [Constant](Self::Constant)<br>
+/// What kind of segment an [`EntryPoint`] refers to<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntryKind {
+    /// An entry point in a fixed segment
+    Fixed,
+    /// An entry point in a movable segment, reached through an `INT 3Fh` thunk
+    Movable,
+    /// A constant defined within the module (OS/2)
+    Constant,
+}
+
+from_memory_flags! {
+    /// [EXPORTED](Self::EXPORTED) |
+    /// [SHARED_DATA](Self::SHARED_DATA)<br>
+    /// [ne::EntryPoint::flags]<br>
+    /// <br>
+    #[repr(transparent)]
+    pub struct EntryFlags : u8 {
+        /// Zero / no flags set.
+        const NONE                      = 0;
+        /// The entry is exported
+        const EXPORTED                  = 0x01;
+        /// The entry uses a shared (global) data segment
+        const SHARED_DATA               = 0x02;
+    }
+}
+
+impl EntryPoint {
+    /// Parse an entry table, stopping at the zero count terminator (or the end of `data`.)
+    pub(crate) fn parse_table(mut data: &[u8]) -> io::Result<Vec<Self>> {
+        let eof = || io::Error::new(io::ErrorKind::UnexpectedEof, "ne::EntryPoint extends past end of entry table");
+        let mut entries = Vec::new();
+        let mut ordinal = 1u16;
+        while let [count, indicator, rest @ ..] = data {
+            if *count == 0 { break }
+            let (kind, size) = match *indicator {
+                0x00 => { ordinal = ordinal.wrapping_add(u16::from(*count)); data = rest; continue },
+                0xFF => (EntryKind::Movable, 6),
+                0xFE => (EntryKind::Constant, 3),
+                _    => (EntryKind::Fixed, 3),
+            };
+            let bundle = rest.get(..usize::from(*count) * size).ok_or_else(eof)?;
+            for e in bundle.chunks_exact(size) {
+                let flags = EntryFlags::from_bits_truncate(e[0]);
+                let (segment, offset) = match kind {
+                    EntryKind::Movable  => (e[3], u16::from_le_bytes([e[4], e[5]])), // e[1..3] is the `INT 3Fh` opcode
+                    EntryKind::Constant => (0, u16::from_le_bytes([e[1], e[2]])),
+                    EntryKind::Fixed    => (*indicator, u16::from_le_bytes([e[1], e[2]])),
+                };
+                entries.push(EntryPoint { ordinal, kind, segment, offset, flags });
+                ordinal = ordinal.wrapping_add(1);
+            }
+            data = &rest[bundle.len()..];
+        }
+        Ok(entries)
+    }
+}
+
+
+
+#[test] fn parse_table() {
+    let entries = EntryPoint::parse_table(&[
+        0x02, 0x00,                                             // 2 unused
+        0x01, 0x03, 0x01, 0x34, 0x12,                           // 1 fixed in segment 3
+        0x01, 0xFF, 0x03, 0xCD, 0x3F, 0x02, 0x78, 0x56,         // 1 movable in segment 2
+        0x00,
+    ]).unwrap();
+    assert_eq!(entries, vec![
+        EntryPoint { ordinal: 3, kind: EntryKind::Fixed,   segment: 3, offset: 0x1234, flags: EntryFlags::EXPORTED },
+        EntryPoint { ordinal: 4, kind: EntryKind::Movable, segment: 2, offset: 0x5678, flags: EntryFlags::EXPORTED | EntryFlags::SHARED_DATA },
+    ]);
+    assert!(EntryPoint::parse_table(&[0x02, 0x01, 0x01, 0x00, 0x00]).is_err());
+}
@@ -0,0 +1,117 @@
+use crate::*;
+use ne::*;
+
+use bytemuck::*;
+
+use std::io::{self, *};
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [signature](Self::signature),
+    ///     [linker_version](Self::linker_version),
+    ///     [entry_table](Self::entry_table),
+    ///     [flags](Self::flags),
+    ///     [nsegments](Self::nsegments),
+    ///     ...
+    /// }<br>
+    /// `IMAGE_OS2_HEADER`: the NE header, found at [`mz::Header::pe_header_start`]<br>
+    /// <br>
+    ///
+    /// Unless otherwise noted, table offsets are relative to the start of this header.
+    ///
+    /// ## References
+    /// *   <https://wiki.osdev.org/NE>
+    /// *   `IMAGE_OS2_HEADER` in `winnt.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Header {
+        /// `b"NE"` for valid NE modules.
+        pub signature:                  abistr::CStrBuf<[u8; 2]>,
+        pub linker_version:             u8,
+        pub linker_revision:            u8,
+        /// Offset of the entry table (see [`ne::EntryPoint`])
+        pub entry_table:                u16,
+        /// Size of the entry table in bytes
+        pub entry_table_size:           u16,
+        /// 32-bit CRC of the entire file (typically 0)
+        pub crc:                        u32,
+        pub flags:                      ModuleFlags,
+        /// 1-based segment number of the automatic data segment (`DGROUP`), or 0 if none
+        pub auto_data_segment:          u16,
+        /// Initial local heap size, in bytes
+        pub heap_size:                  u16,
+        /// Initial stack size, in bytes
+        pub stack_size:                 u16,
+        /// Initial value of the 16-bit Instruction Pointer register
+        pub ip:                         u16,
+        /// 1-based segment number of the initial Code Segment
+        pub cs:                         u16,
+        /// Initial value of the 16-bit Stack Pointer register
+        pub sp:                         u16,
+        /// 1-based segment number of the initial Stack Segment
+        pub ss:                         u16,
+        /// Number of [`ne::SegmentEntry`]s in the segment table
+        pub nsegments:                  u16,
+        /// Number of entries in the module reference table
+        pub nmodule_refs:               u16,
+        /// Size of the non-resident name table in bytes
+        pub nonresident_names_size:     u16,
+        /// Offset of the segment table (see [`ne::SegmentEntry`])
+        pub segment_table:              u16,
+        /// Offset of the resource table (see [`ne::ResourceType`])
+        pub resource_table:             u16,
+        /// Offset of the resident name table (see [`ne::Name`])
+        pub resident_names:             u16,
+        /// Offset of the module reference table: [`u16`] offsets into the imported names table
+        pub module_refs:                u16,
+        /// Offset of the imported names table: length-prefixed strings
+        pub imported_names:             u16,
+        /// File offset (*not* relative to this header!) of the non-resident name table (see [`ne::Name`])
+        pub nonresident_names:          u32,
+        /// Number of movable entries in the entry table
+        pub nmovable_entries:           u16,
+        /// Segment and resource data offsets are in units of `1 << alignment_shift` bytes (0 means 9, or 512 bytes)
+        pub alignment_shift:            u16,
+        /// Number of resource segments (OS/2)
+        pub nresource_segments:         u16,
+        pub target_os:                  TargetOs,
+        pub other_flags:                OtherFlags,
+        /// Offset of the gangload area, in [`alignment_shift`](Self::alignment_shift) units (Windows)
+        pub gangload_start:             u16,
+        /// Size of the gangload area, in [`alignment_shift`](Self::alignment_shift) units (Windows)
+        pub gangload_size:              u16,
+        /// Minimum code swap area size
+        pub min_code_swap:              u16,
+        /// Expected Windows version, e.g. `0x030A` for Windows 3.10
+        pub expected_windows_version:   u16,
+    }
+}
+
+impl Header {
+    /// Reads an [`ne::Header`] from `read`
+    ///
+    /// ## Errors
+    /// * [`io::ErrorKind::InvalidData`] if [`signature`](#structfield.signature) ≠ `"NE"`
+    /// * [`io::ErrorKind::UnexpectedEof`] if `read` didn't contain enough data
+    pub fn read_from(read: &mut impl Read) -> io::Result<Self> {
+        let s = Header::from_io(read)?;
+        if s.signature.buffer() != b"NE" {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "ne::Header::signature != \"NE\""))
+        } else {
+            Ok(s)
+        }
+    }
+
+    /// The effective [`alignment_shift`](Self::alignment_shift) (0 is treated as the default of 9, or 512 byte sectors)
+    pub fn effective_alignment_shift(&self) -> u16 {
+        if self.alignment_shift == 0 { 9 } else { self.alignment_shift }
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(0x40, std::mem::size_of::<<Header as FromMemory>::Raw>());
+}
@@ -0,0 +1,74 @@
+#[cfg(doc)] use crate::*;
+
+from_memory_flags! {
+    /// [SINGLEDATA](Self::SINGLEDATA) |
+    /// [MULTIPLEDATA](Self::MULTIPLEDATA) |
+    /// [PROTECTED_MODE](Self::PROTECTED_MODE) |
+    /// [LIBRARY](Self::LIBRARY) |
+    /// ...<br>
+    /// [ne::Header::flags]: module type and requirements<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   `NE*` flags in `newexe.h`
+    #[repr(transparent)]
+    pub struct ModuleFlags : u16 {
+        /// Zero / no flags set.  For libraries, indicates there's no automatic data segment.
+        const NONE                      = 0;
+
+        /// A single shared automatic data segment (typical of DLLs)
+        const SINGLEDATA                = 0x0001;
+        /// An automatic data segment per instance (typical of applications)
+        const MULTIPLEDATA              = 0x0002;
+        /// Per-process library initialization (OS/2)
+        const PER_PROCESS_INIT          = 0x0004;
+        /// Runs in protected mode only
+        const PROTECTED_MODE            = 0x0008;
+        /// Contains 8086 instructions
+        const I8086                     = 0x0010;
+        /// Contains 80286 instructions
+        const I80286                    = 0x0020;
+        /// Contains 80386 instructions
+        const I80386                    = 0x0040;
+        /// Contains floating-point instructions
+        const X87                       = 0x0080;
+
+        /// OS/2 application type: full screen, not compatible with the Presentation Manager window API
+        const NOT_WINDOW_COMPATIBLE     = 0x0100;
+        /// OS/2 application type: compatible with the Presentation Manager window API
+        const WINDOW_COMPATIBLE         = 0x0200;
+        /// OS/2 application type: uses the Presentation Manager window API
+        const USES_WINDOW_API           = 0x0300;
+
+        /// OS/2 family application (bound to run under both MS-DOS and OS/2)
+        const FAMILY_API                = 0x0800;
+        /// The linker reported errors; the image isn't executable
+        const LINK_ERRORS               = 0x2000;
+        /// Private library (Windows)
+        const PRIVATE_LIBRARY           = 0x4000;
+        /// The module is a library (DLL or driver) rather than an application
+        const LIBRARY                   = 0x8000;
+    }
+}
+
+from_memory_flags! {
+    /// [LONG_FILENAMES](Self::LONG_FILENAMES) |
+    /// [PROTECTED_MODE_2X](Self::PROTECTED_MODE_2X) |
+    /// [PROPORTIONAL_FONTS](Self::PROPORTIONAL_FONTS) |
+    /// [GANGLOAD_AREA](Self::GANGLOAD_AREA)<br>
+    /// [ne::Header::other_flags]: additional, mostly Windows specific flags<br>
+    /// <br>
+    #[repr(transparent)]
+    pub struct OtherFlags : u8 {
+        /// Zero / no flags set.
+        const NONE                      = 0;
+        /// Supports long filenames (OS/2)
+        const LONG_FILENAMES            = 0x01;
+        /// A Windows 2.x application that runs in protected mode
+        const PROTECTED_MODE_2X         = 0x02;
+        /// A Windows 2.x application that supports proportional fonts
+        const PROPORTIONAL_FONTS        = 0x04;
+        /// Contains a gangload (fast-load) area
+        const GANGLOAD_AREA             = 0x08;
+    }
+}
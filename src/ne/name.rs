@@ -0,0 +1,34 @@
+use super::*;
+
+use std::io;
+
+
+
+/// ⨯ { [name](Self::name), [ordinal](Self::ordinal) }<br>
+/// An entry of the resident or non-resident name table<br>
+/// <br>
+///
+/// The first entry of the resident name table is the module name, and the first entry of the non-resident name table is the
+/// module description - both with ordinal 0.  Remaining entries are exported names.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Name {
+    /// The name, decoded as Latin-1
+    pub name:       String,
+    /// The [`ne::EntryPoint::ordinal`] this name refers to
+    pub ordinal:    u16,
+}
+
+impl Name {
+    /// Parse a name table, stopping at the zero length terminator (or the end of `data`.)
+    pub(crate) fn parse_table(mut data: &[u8]) -> io::Result<Vec<Self>> {
+        let mut names = Vec::new();
+        while data.first().is_some_and(|len| *len != 0) {
+            let (name, rest) = split_pascal(data)?;
+            let ordinal = rest.get(..2).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "ne::Name::ordinal extends past end of table"))?;
+            names.push(Name { name: latin1(name), ordinal: u16::from_le_bytes([ordinal[0], ordinal[1]]) });
+            data = &rest[2..];
+        }
+        Ok(names)
+    }
+}
+
@@ -0,0 +1,175 @@
+use crate::*;
+use crate::io::{self, *};
+
+use maulingmonkey_io_adapters::{ReadAt, SeeklessFile};
+
+use std::convert::*;
+use std::fs::File;
+use std::path::PathBuf;
+
+
+
+/// Wraps a [`ReadAt`] for ease of reading a 16-bit NE executable by caching [`ne::Header`] and [`ne::SegmentEntry`]s.<br>
+/// <br>
+///
+/// The remaining tables are read on demand.
+pub struct Reader<R> {
+    src:                        Src,
+    reader:                     R,
+    mz_header:                  mz::Header,
+    header:                     ne::Header,
+    segments:                   Vec<ne::SegmentEntry>,
+}
+
+impl Reader<SeeklessFile> {
+    /// Open an NE executable and parse the header and segment table.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = File::open(&path);
+        let src = Src::PathBuf(path);
+        Reader::read_src(SeeklessFile::from(src.anno(file, "error opening ne::Reader")?), src)
+    }
+}
+
+impl<R: ReadAt> Reader<R> {
+    /// Read an NE executable and parse the header and segment table.  Prefer [`Reader::open`] for better error messages.
+    pub fn read(reader: R) -> io::Result<Self> { Self::read_src(reader, Src::Unknown) }
+
+    /// Read the resource table.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the resource table was truncated
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_resources(&self) -> io::Result<Vec<ne::ResourceType>> {
+        // The resource table has no explicit size, but is always followed by the resident name table
+        if self.header.resource_table == self.header.resident_names { return Ok(Vec::new()) }
+        let table = self.read_table(self.header.resource_table, self.header.resident_names, "error reading ne resource table")?;
+        self.src.anno(ne::ResourceType::parse_table(&table[..]), "error decoding ne resource table")
+    }
+
+    /// Read the data of a single resource.
+    pub fn read_resource_data(&self, resource: &ne::Resource) -> io::Result<Vec<u8>> {
+        self.src.anno(self.reader.read_exact_vec_at(resource.length as usize, resource.offset.into()), "error reading ne::Resource data")
+    }
+
+    /// Read the resident name table.  The first entry is the module name.
+    pub fn read_resident_names(&self) -> io::Result<Vec<ne::Name>> {
+        let table = self.read_table(self.header.resident_names, self.header.module_refs, "error reading ne resident name table")?;
+        self.src.anno(ne::Name::parse_table(&table[..]), "error decoding ne resident name table")
+    }
+
+    /// Read the non-resident name table.  The first entry is the module description.
+    pub fn read_nonresident_names(&self) -> io::Result<Vec<ne::Name>> {
+        let mut table = vec![0u8; self.header.nonresident_names_size.into()];
+        self.src.anno(self.reader.read_exact_at(&mut table[..], self.header.nonresident_names.into()), "error reading ne non-resident name table")?;
+        self.src.anno(ne::Name::parse_table(&table[..]), "error decoding ne non-resident name table")
+    }
+
+    /// Read the module reference table, resolving each entry to the name of the imported module (e.g. `"KERNEL"`.)
+    ///
+    /// [`ne::RelocationTarget::ImportOrdinal::module`] and [`ne::RelocationTarget::ImportName::module`] are 1-based indicies into this list.
+    pub fn read_module_references(&self) -> io::Result<Vec<String>> {
+        let mut refs = vec![0u8; usize::from(self.header.nmodule_refs) * 2];
+        self.src.anno(self.reader.read_exact_at(&mut refs[..], self.header_offset(self.header.module_refs)), "error reading ne module reference table")?;
+        refs.chunks_exact(2).map(|r| self.read_imported_name(u16::from_le_bytes([r[0], r[1]]))).collect()
+    }
+
+    /// Read a length-prefixed string from the imported names table, at `offset` relative to the start of said table.
+    pub fn read_imported_name(&self, offset: u16) -> io::Result<String> {
+        let mut name = [0u8; 256];
+        let start = self.header_offset(self.header.imported_names) + u64::from(offset);
+        let n = self.src.anno(self.reader.read_up_to_at(&mut name[..], start), "error reading ne imported name")?;
+        let (name, _) = self.src.anno(ne::split_pascal(&name[..n]), "error reading ne imported name")?;
+        Ok(ne::latin1(name))
+    }
+
+    /// Read the entry table.
+    pub fn read_entry_points(&self) -> io::Result<Vec<ne::EntryPoint>> {
+        let mut table = vec![0u8; self.header.entry_table_size.into()];
+        self.src.anno(self.reader.read_exact_at(&mut table[..], self.header_offset(self.header.entry_table)), "error reading ne entry table")?;
+        self.src.anno(ne::EntryPoint::parse_table(&table[..]), "error decoding ne entry table")
+    }
+
+    /// Read the data of the 1-based segment number `segment`.  Uninitialized segments read as empty.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `segment` isn't a valid segment number
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_segment_data(&self, segment: u16) -> io::Result<Vec<u8>> {
+        let range = match self.segment_or_err(segment)?.file_range(self.header.effective_alignment_shift()) {
+            None        => return Ok(Vec::new()),
+            Some(range) => range,
+        };
+        self.src.anno(self.reader.read_exact_vec_at((range.end - range.start) as usize, range.start), "error reading ne segment data")
+    }
+
+    /// Read the relocations of the 1-based segment number `segment`.  Segments without [`ne::SegmentFlags::RELOC_INFO`] have none.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `segment` isn't a valid segment number
+    /// *   [`io::ErrorKind::InvalidData`] if a relocation record had unknown flags
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_segment_relocations(&self, segment: u16) -> io::Result<Vec<ne::Relocation>> {
+        let entry = self.segment_or_err(segment)?;
+        if !entry.flags.contains(ne::SegmentFlags::RELOC_INFO) { return Ok(Vec::new()) }
+        let mut offset = match entry.file_range(self.header.effective_alignment_shift()) {
+            None        => return Ok(Vec::new()),
+            Some(range) => range.end,
+        };
+
+        let count = self.src.anno(u16::from_read_at_advance(&self.reader, &mut offset), "error reading ne relocation count")?;
+        let mut records = vec![0u8; usize::from(count) * 8];
+        self.src.anno(self.reader.read_exact_at(&mut records[..], offset), "error reading ne::Relocation")?;
+        records.chunks_exact(8).map(|r| {
+            let mut record = [0u8; 8];
+            record.copy_from_slice(r);
+            self.src.anno(ne::Relocation::from_record(record), "error decoding ne::Relocation")
+        }).collect()
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
+        let mz_header = src.anno(mz::Header::from_read_at(&reader, 0), "error reading mz::Header")?;
+
+        let mut offset = u64::from(mz_header.pe_header_start);
+        let header = src.anno(ne::Header::from_read_at_advance(&reader, &mut offset), "error reading ne::Header")?;
+        if header.signature.buffer() != b"NE" {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "ne::Header::signature != \"NE\"")), "error reading ne::Header");
+        }
+        if header.effective_alignment_shift() >= 32 {
+            return src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "ne::Header::alignment_shift >= 32")), "error reading ne::Header");
+        }
+
+        let mut offset = u64::from(mz_header.pe_header_start) + u64::from(header.segment_table);
+        let mut segments = vec![ne::SegmentEntry::default(); header.nsegments.into()];
+        for segment in segments.iter_mut() {
+            *segment = src.anno(ne::SegmentEntry::from_read_at_advance(&reader, &mut offset), "error reading ne::SegmentEntry")?;
+        }
+
+        Ok(Self { src, reader, mz_header, header, segments })
+    }
+
+    fn read_table(&self, start: u16, end: u16, note: &str) -> io::Result<Vec<u8>> {
+        if end < start { return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "ne::Header table offsets out of order")), note) }
+        let mut table = vec![0u8; usize::from(end - start)];
+        self.src.anno(self.reader.read_exact_at(&mut table[..], self.header_offset(start)), note)?;
+        Ok(table)
+    }
+
+    fn segment_or_err(&self, segment: u16) -> io::Result<&ne::SegmentEntry> {
+        self.segment(segment).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{}: ne segment number {} out of range 1 ..= {}", self.src, segment, self.segments.len())))
+    }
+}
+
+impl<R> Reader<R> {
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn mz_header(&self) -> &mz::Header { &self.mz_header }
+    #[allow(missing_docs)] pub fn header(&self) -> &ne::Header { &self.header }
+    #[allow(missing_docs)] pub fn segments(&self) -> &[ne::SegmentEntry] { &self.segments[..] }
+
+    /// Get a segment by 1-based segment number, as used by [`ne::Header::cs`], [`ne::EntryPoint::segment`], etc.
+    pub fn segment(&self, segment: impl TryInto<usize>) -> Option<&ne::SegmentEntry> {
+        segment.try_into().ok().and_then(|s| s.checked_sub(1)).and_then(|idx| self.segments.get(idx))
+    }
+
+    fn header_offset(&self, offset: u16) -> u64 { u64::from(self.mz_header.pe_header_start) + u64::from(offset) }
+}
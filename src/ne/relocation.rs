@@ -0,0 +1,111 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+
+
+
+/// ⨯ { [source](Self::source), [additive](Self::additive), [offset](Self::offset), [target](Self::target) }<br>
+/// A per-segment NE relocation record, found after the segment data of segments with [`ne::SegmentFlags::RELOC_INFO`]<br>
+/// <br>
+///
+/// ## References
+/// *   <https://wiki.osdev.org/NE#Relocations>
+/// *   `struct new_rlc` in `newexe.h`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Relocation {
+    /// What kind of location is being patched
+    pub source:     RelocationSource,
+    /// If `true`, the target is added to the existing value at [`offset`](Self::offset).
+    /// If `false`, [`offset`](Self::offset) is the head of a chain of locations to patch, each containing the offset of the next (ending in `0xFFFF`.)
+    pub additive:   bool,
+    /// Offset within the segment of the (first) location to patch
+    pub offset:     u16,
+    pub target:     RelocationTarget,
+}
+
+/// ∑ [Internal](Self::Internal) | [InternalMovable](Self::InternalMovable) | [ImportOrdinal](Self::ImportOrdinal) | [ImportName](Self::ImportName) | [OsFixup](Self::OsFixup)<br>
+/// What a [`Relocation`] refers to<br>
+/// <br>
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RelocationTarget {
+    /// An offset within a fixed segment of this module
+    Internal { /** 1-based segment number */ segment: u8, offset: u16 },
+    /// An entry point of a movable segment of this module
+    InternalMovable { /** [`ne::EntryPoint::ordinal`] */ ordinal: u16 },
+    /// An entry point of another module, by ordinal
+    ImportOrdinal { /** 1-based index into [`ne::Reader::read_module_references`] */ module: u16, ordinal: u16 },
+    /// An entry point of another module, by name
+    ImportName { /** 1-based index into [`ne::Reader::read_module_references`] */ module: u16, /** see [`ne::Reader::read_imported_name`] */ name: u16 },
+    /// An operating system fixup, typically floating point emulation
+    OsFixup { fixup: u16 },
+}
+
+/// ∑
+/// [LOBYTE](Self::LOBYTE) |
+/// [SEGMENT](Self::SEGMENT) |
+/// [FAR_ADDR](Self::FAR_ADDR) |
+/// [OFFSET](Self::OFFSET) |
+/// ...<br>
+/// [ne::Relocation::source]: the size and kind of location being patched<br>
+/// <br>
+///
+/// ## References
+/// *   `NRS*` in `newexe.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct RelocationSource(u8);
+
+impl RelocationSource {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    #[doc = "Low byte of an offset"                                             ] pub const LOBYTE      : RelocationSource = RelocationSource::new(0);
+    #[doc = "16-bit segment selector"                                           ] pub const SEGMENT     : RelocationSource = RelocationSource::new(2);
+    #[doc = "16:16 far pointer (offset, then segment)"                          ] pub const FAR_ADDR    : RelocationSource = RelocationSource::new(3);
+    #[doc = "16-bit offset"                                                     ] pub const OFFSET      : RelocationSource = RelocationSource::new(5);
+    #[doc = "16:32 far pointer (offset, then segment)"                          ] pub const FAR_ADDR48  : RelocationSource = RelocationSource::new(11);
+    #[doc = "32-bit offset"                                                     ] pub const OFFSET32    : RelocationSource = RelocationSource::new(13);
+}
+
+impl Debug for RelocationSource {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::LOBYTE        => write!(fmt, "RelocationSource::LOBYTE"),
+            Self::SEGMENT       => write!(fmt, "RelocationSource::SEGMENT"),
+            Self::FAR_ADDR      => write!(fmt, "RelocationSource::FAR_ADDR"),
+            Self::OFFSET        => write!(fmt, "RelocationSource::OFFSET"),
+            Self::FAR_ADDR48    => write!(fmt, "RelocationSource::FAR_ADDR48"),
+            Self::OFFSET32      => write!(fmt, "RelocationSource::OFFSET32"),
+            other               => write!(fmt, "RelocationSource(0x{:02x})", other.0),
+        }
+    }
+}
+
+impl FromMemory for RelocationSource {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
+
+impl Relocation {
+    /// Parse a single 8 byte relocation record
+    pub(crate) fn from_record(r: [u8; 8]) -> io::Result<Self> {
+        let w = |i: usize| u16::from_le_bytes([r[i], r[i+1]]);
+        let target = match r[1] & 0x03 {
+            0 if r[4] == 0xFF   => RelocationTarget::InternalMovable { ordinal: w(6) },
+            0                   => RelocationTarget::Internal { segment: r[4], offset: w(6) },
+            1                   => RelocationTarget::ImportOrdinal { module: w(4), ordinal: w(6) },
+            2                   => RelocationTarget::ImportName { module: w(4), name: w(6) },
+            _                   => RelocationTarget::OsFixup { fixup: w(4) },
+        };
+        if r[1] & !0x07 != 0 { return Err(io::Error::new(io::ErrorKind::InvalidData, format!("ne::Relocation: unknown flags 0x{:02x}", r[1]))) }
+        Ok(Relocation { source: RelocationSource::new(r[0] & 0x0F), additive: r[1] & 0x04 != 0, offset: w(2), target })
+    }
+}
+
@@ -0,0 +1,156 @@
+use super::*;
+
+use std::convert::*;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::ops::Range;
+
+
+
+/// ⨯ { [type_id](Self::type_id), [resources](Self::resources) }<br>
+/// A group of [`Resource`]s sharing a type, from the NE resource table<br>
+/// <br>
+///
+/// ## References
+/// *   <https://wiki.osdev.org/NE#Resource_Table>
+/// *   `TYPEINFO` in <https://www.fileformat.info/format/exe/corion-ne.htm>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceType {
+    /// e.g. [`ResourceId::Id`]\(3\) for `RT_ICON`
+    pub type_id:    ResourceId,
+    pub resources:  Vec<Resource>,
+}
+
+/// ⨯ { [id](Self::id), [offset](Self::offset), [length](Self::length), [flags](Self::flags) }<br>
+/// A single resource from the NE resource table<br>
+/// <br>
+///
+/// ## References
+/// *   `NAMEINFO` in <https://www.fileformat.info/format/exe/corion-ne.htm>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Resource {
+    pub id:         ResourceId,
+    /// File offset of the resource data, in bytes (already scaled by the resource table's alignment shift)
+    pub offset:     u32,
+    /// Length of the resource data, in bytes (already scaled by the resource table's alignment shift)
+    pub length:     u32,
+    pub flags:      ResourceFlags,
+}
+
+/// ∑ [Id](Self::Id) | [Name](Self::Name)<br>
+/// A resource type or name: either an integer or a string<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceId {
+    /// An integer ID (with the high bit, which flags integer IDs in the table, already masked off)
+    Id(u16),
+    /// A string ID, decoded as Latin-1
+    Name(String),
+}
+
+from_memory_flags! {
+    /// [MOVABLE](Self::MOVABLE) |
+    /// [SHARED](Self::SHARED) |
+    /// [PRELOAD](Self::PRELOAD) |
+    /// [DISCARDABLE](Self::DISCARDABLE)<br>
+    /// [ne::Resource::flags]<br>
+    /// <br>
+    #[repr(transparent)]
+    pub struct ResourceFlags : u16 {
+        /// Zero / no flags set.
+        const NONE                      = 0;
+        /// Movable (rather than fixed)
+        const MOVABLE                   = 0x0010;
+        /// Shareable / pure: may be shared between instances
+        const SHARED                    = 0x0020;
+        /// Loaded at startup (rather than on demand)
+        const PRELOAD                   = 0x0040;
+        /// Discardable under memory pressure
+        const DISCARDABLE               = 0x1000;
+    }
+}
+
+impl Resource {
+    /// The file offsets of the resource data
+    pub fn file_range(&self) -> Range<u64> { u64::from(self.offset) .. u64::from(self.offset) + u64::from(self.length) }
+}
+
+impl Display for ResourceId {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            ResourceId::Id(id)      => write!(fmt, "#{}", id),
+            ResourceId::Name(name)  => write!(fmt, "{}", name),
+        }
+    }
+}
+
+impl ResourceType {
+    /// Parse the entire resource table.  `table` must start at [`ne::Header::resource_table`].
+    pub(crate) fn parse_table(table: &[u8]) -> io::Result<Vec<Self>> {
+        let eof = || io::Error::new(io::ErrorKind::UnexpectedEof, "ne::ResourceType extends past end of resource table");
+        let u16_at = |o: usize| table.get(o..o+2).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or_else(eof);
+        let id_at = |id: u16| -> io::Result<ResourceId> {
+            if id & 0x8000 != 0 { return Ok(ResourceId::Id(id & 0x7FFF)) }
+            let name = table.get(usize::from(id)..).ok_or_else(eof)?;
+            Ok(ResourceId::Name(latin1(split_pascal(name)?.0)))
+        };
+
+        if table.is_empty() { return Ok(Vec::new()) }
+        let shift = u32::from(u16_at(0)?);
+        if shift >= 32 { return Err(io::Error::new(io::ErrorKind::InvalidData, "ne: resource table alignment shift >= 32")) }
+        let shifted = |v: u16| u32::try_from(u64::from(v) << shift).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("ne: resource offset/length 0x{:X} << {} exceeds u32::MAX", v, shift)));
+
+        let mut types = Vec::new();
+        let mut o = 2;
+        loop {
+            let type_id = u16_at(o)?;
+            if type_id == 0 { break }
+            let count = u16_at(o + 2)?;
+            o += 8;
+
+            let mut resources = Vec::with_capacity(count.into());
+            for _ in 0 .. count {
+                resources.push(Resource {
+                    offset: shifted(u16_at(o)?)?,
+                    length: shifted(u16_at(o + 2)?)?,
+                    flags:  ResourceFlags::from_bits_truncate(u16_at(o + 4)?),
+                    id:     id_at(u16_at(o + 6)?)?,
+                });
+                o += 12;
+            }
+            types.push(ResourceType { type_id: id_at(type_id)?, resources });
+        }
+        Ok(types)
+    }
+}
+
+
+
+#[test] fn parse_table() {
+    let table = [
+        0x04, 0x00,                                             // alignment shift
+        0x03, 0x80, 0x01, 0x00, 0, 0, 0, 0,                     // RT_ICON x1
+        0x10, 0x00, 0x02, 0x00, 0x50, 0x10, 0x01, 0x80, 0, 0, 0, 0,
+        0x2C, 0x00, 0x01, 0x00, 0, 0, 0, 0,                     // "FOO" x1
+        0x20, 0x00, 0x03, 0x00, 0x30, 0x00, 0x2C, 0x00, 0, 0, 0, 0,
+        0x00, 0x00,
+        0x03, b'F', b'O', b'O', 0x00,
+    ];
+    let types = ResourceType::parse_table(&table[..]).unwrap();
+    assert_eq!(types.len(), 2);
+    assert_eq!(types[0].type_id, ResourceId::Id(3));
+    assert_eq!(types[0].resources, vec![Resource { id: ResourceId::Id(1), offset: 0x100, length: 0x20, flags: ResourceFlags::MOVABLE | ResourceFlags::PRELOAD | ResourceFlags::DISCARDABLE }]);
+    assert_eq!(types[1].type_id, ResourceId::Name("FOO".into()));
+    assert_eq!(types[1].resources[0].id, ResourceId::Name("FOO".into()));
+}
+
+#[test] fn parse_table_shift() {
+    let table = |shift: u8, offset: u8| [
+        shift, 0x00,
+        0x03, 0x80, 0x01, 0x00, 0, 0, 0, 0,                     // RT_ICON x1
+        offset, 0x00, 0x01, 0x00, 0x50, 0x10, 0x01, 0x80, 0, 0, 0, 0,
+        0x00, 0x00,
+    ];
+    assert_eq!(ResourceType::parse_table(&table(31, 0x01)[..]).unwrap()[0].resources[0].offset, 0x8000_0000);
+    assert_eq!(ResourceType::parse_table(&table(31, 0x02)[..]).unwrap_err().kind(), io::ErrorKind::InvalidData); // would previously truncate to 0
+}
@@ -0,0 +1,90 @@
+#[cfg(doc)] use crate::*;
+
+use std::ops::Range;
+
+
+
+from_memory_struct! {
+    /// { [sector](Self::sector), [length](Self::length), [flags](Self::flags), [min_alloc](Self::min_alloc) }<br>
+    /// An entry of the NE segment table: where a code or data segment lives in the file, and how to load it<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://wiki.osdev.org/NE#Segment_Table>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct SegmentEntry {
+        /// Offset of the segment data, in [`ne::Header::alignment_shift`] units.  0 if the segment has no data in the file.
+        pub sector:                     u16,
+        /// Length of the segment data in the file, in bytes.  0 means 64 KiB (if [`sector`](Self::sector) ≠ 0).
+        pub length:                     u16,
+        pub flags:                      SegmentFlags,
+        /// Minimum allocation size, in bytes.  0 means 64 KiB.
+        pub min_alloc:                  u16,
+    }
+}
+
+impl SegmentEntry {
+    /// The file offsets of the segment data, or [`None`] if the segment is uninitialized (has no data in the file)
+    pub fn file_range(&self, alignment_shift: u16) -> Option<Range<u64>> {
+        if self.sector == 0 { return None }
+        let start = u64::from(self.sector) << alignment_shift;
+        let len = if self.length == 0 { 0x10000 } else { u64::from(self.length) };
+        Some(start .. start + len)
+    }
+
+    /// The minimum number of bytes to allocate for the segment
+    pub fn min_alloc_bytes(&self) -> u32 {
+        if self.min_alloc == 0 { 0x10000 } else { u32::from(self.min_alloc) }
+    }
+
+    /// `true` if this is a data segment, `false` for code
+    pub fn is_data(&self) -> bool { self.flags.contains(SegmentFlags::DATA) }
+}
+
+from_memory_flags! {
+    /// [DATA](Self::DATA) |
+    /// [MOVABLE](Self::MOVABLE) |
+    /// [PRELOAD](Self::PRELOAD) |
+    /// [RELOC_INFO](Self::RELOC_INFO) |
+    /// [DISCARDABLE](Self::DISCARDABLE) |
+    /// ...<br>
+    /// [ne::SegmentEntry::flags]: segment type and loading hints<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   `NS*` flags in `newexe.h`
+    #[repr(transparent)]
+    pub struct SegmentFlags : u16 {
+        /// Zero / no flags set: a code segment.
+        const NONE                      = 0;
+        /// Data segment (rather than code)
+        const DATA                      = 0x0001;
+        /// Segment was allocated by the loader
+        const ALLOCATED                 = 0x0002;
+        /// Segment was loaded by the loader
+        const LOADED                    = 0x0004;
+        /// Segment data is iterated (compressed as repeat records)
+        const ITERATED                  = 0x0008;
+        /// Movable (rather than fixed)
+        const MOVABLE                   = 0x0010;
+        /// Shareable / pure: may be shared between instances
+        const SHARED                    = 0x0020;
+        /// Loaded at startup (rather than on demand)
+        const PRELOAD                   = 0x0040;
+        /// Code: execute only.  Data: read only.
+        const READ_ONLY                 = 0x0080;
+        /// Relocation records follow the segment data (see [`ne::Relocation`])
+        const RELOC_INFO                = 0x0100;
+        /// Conforming code segment (OS/2)
+        const CONFORMING                = 0x0200;
+        /// Descriptor privilege level (OS/2)
+        const PRIVILEGE_LEVEL           = 0x0C00;
+        /// Discardable under memory pressure
+        const DISCARDABLE               = 0x1000;
+        /// 32-bit segment (OS/2)
+        const USE32                     = 0x2000;
+        /// Huge segment, spanning multiple 64 KiB selectors
+        const HUGE                      = 0x4000;
+    }
+}
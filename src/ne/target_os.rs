@@ -0,0 +1,57 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑
+/// [OS2](Self::OS2) |
+/// [WINDOWS](Self::WINDOWS) |
+/// [DOS4](Self::DOS4) |
+/// [WIN386](Self::WIN386) |
+/// ...<br>
+/// [ne::Header::target_os]: which operating system the module was built for<br>
+/// <br>
+///
+/// ## References
+/// *   `NE_OS_*` / `ne_exetyp` in `newexe.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct TargetOs(u8);
+
+impl TargetOs {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    #[doc = "Unknown (typically Windows 1.x / 2.x)"                            ] pub const UNKNOWN     : TargetOs = TargetOs::new(0);
+    #[doc = "OS/2"                                                              ] pub const OS2         : TargetOs = TargetOs::new(1);
+    #[doc = "Windows"                                                           ] pub const WINDOWS     : TargetOs = TargetOs::new(2);
+    #[doc = "European MS-DOS 4.x"                                               ] pub const DOS4        : TargetOs = TargetOs::new(3);
+    #[doc = "Windows 386"                                                       ] pub const WIN386      : TargetOs = TargetOs::new(4);
+    #[doc = "Borland Operating System Services"                                 ] pub const BOSS        : TargetOs = TargetOs::new(5);
+}
+
+impl Debug for TargetOs {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::UNKNOWN   => write!(fmt, "TargetOs::UNKNOWN"),
+            Self::OS2       => write!(fmt, "TargetOs::OS2"),
+            Self::WINDOWS   => write!(fmt, "TargetOs::WINDOWS"),
+            Self::DOS4      => write!(fmt, "TargetOs::DOS4"),
+            Self::WIN386    => write!(fmt, "TargetOs::WIN386"),
+            Self::BOSS      => write!(fmt, "TargetOs::BOSS"),
+            other           => write!(fmt, "TargetOs(0x{:02x})", other.0),
+        }
+    }
+}
+
+impl FromMemory for TargetOs {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
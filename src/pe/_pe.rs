@@ -3,24 +3,37 @@
 //! ## References
 //! *   <https://wiki.osdev.org/PE>
 
+mod checksum;                       pub(crate) use checksum::*;
 mod data_directories;               pub use data_directories::*;
 mod data_directory;                 pub use data_directory::*;
+mod debug_directory;                pub use debug_directory::*;
+mod debug_type;                     pub use debug_type::*;
 mod dll_characteristics;            pub use dll_characteristics::*;
+mod export_directory;               pub use export_directory::*;
+mod export;                         pub use export::*;
 mod file_characteristics;           pub use file_characteristics::*;
 mod file_header;                    pub use file_header::*;
 mod header;                         pub use header::*;
+mod image;                          pub use image::*;
 mod import_descriptor;              pub use import_descriptor::*;
 mod import_lookup_table;            pub use import_lookup_table::*;
+mod import_symbol;                  pub use import_symbol::*;
 mod machine;                        pub use machine::*;
 mod optional_header_32;             pub use optional_header_32::*;
 mod optional_header_64;             pub use optional_header_64::*;
 mod optional_header;                pub use optional_header::*;
 mod reader;                         pub use reader::*;
+mod resources;                      pub use resources::*;
+mod rich_header;                    pub use rich_header::*;
 mod rva;                            pub use rva::*;
 mod section_characteristics;        pub use section_characteristics::*;
 mod section_header;                 pub use section_header::*;
+mod strip_options;                  pub use strip_options::*;
 mod subsystem;                      pub use subsystem::*;
 mod time_date;                      pub use time_date::*;
+mod version_info;                   pub use version_info::*;
 mod version;                        pub use version::*;
+mod win_certificate;                pub use win_certificate::*;
+mod writer;                         pub use writer::*;
 
 type Signature = abistr::CStrBuf<[u8; 4]>;
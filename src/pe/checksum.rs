@@ -0,0 +1,93 @@
+#[cfg(doc)] use crate::*;
+
+
+
+/// Offset of `checksum` within both [`pe::OptionalHeader32`] and [`pe::OptionalHeader64`]
+pub(crate) const OPTIONAL_HEADER_CHECKSUM_OFFSET : u64 = 64;
+
+/// Incremental implementation of the [`CheckSumMappedFile`](https://learn.microsoft.com/en-us/windows/win32/api/imagehlp/nf-imagehlp-checksummappedfile) algorithm:
+/// a 16-bit, carry-folded sum of every little-endian [`u16`] in the file (treating the checksum field itself as zero), plus the file length.
+///
+/// Data may be fed in arbitrarily sized chunks, including odd lengths.
+pub(crate) struct Checksum {
+    sum:                u32,
+    offset:             u64,
+    checksum_offset:    u64,
+    pending:            Option<u8>,
+}
+
+impl Checksum {
+    /// `checksum_offset` is the file offset of the 4-byte checksum field to skip.
+    pub fn new(checksum_offset: u64) -> Self {
+        Self { sum: 0, offset: 0, checksum_offset, pending: None }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        if let Some(lo) = self.pending.take() {
+            match data.split_first() {
+                None => { self.pending = Some(lo); return },
+                Some((hi, rest)) => {
+                    self.add_word(u16::from_le_bytes([lo, *hi]));
+                    data = rest;
+                },
+            }
+        }
+
+        let mut words = data.chunks_exact(2);
+        for w in &mut words {
+            self.add_word(u16::from_le_bytes([w[0], w[1]]));
+        }
+        self.pending = words.remainder().first().copied();
+    }
+
+    pub fn finish(mut self) -> u32 {
+        if let Some(lo) = self.pending.take() {
+            self.add_word(u16::from(lo));
+            self.offset -= 1; // the padding byte isn't part of the file length
+        }
+        let sum = (self.sum & 0xFFFF) + (self.sum >> 16);
+        let sum = (sum & 0xFFFF) + (sum >> 16);
+        sum.wrapping_add(self.offset as u32)
+    }
+
+    fn add_word(&mut self, word: u16) {
+        let o = self.offset;
+        self.offset += 2;
+        // Zero the checksum field byte by byte: an odd e_lfanew leaves it straddling words
+        let field = self.checksum_offset .. self.checksum_offset.saturating_add(4);
+        let [mut lo, mut hi] = word.to_le_bytes();
+        if field.contains(&o)       { lo = 0 }
+        if field.contains(&(o + 1)) { hi = 0 }
+        self.sum += u32::from(u16::from_le_bytes([lo, hi]));
+        self.sum = (self.sum & 0xFFFF) + (self.sum >> 16);
+    }
+}
+
+
+
+#[test] fn checksum() {
+    let data = [0x01, 0x00, 0xFF, 0xFF, 0x02, 0x00, 0xAA, 0xBB, 0xCC, 0xDD, 0x03];
+    let mut c = Checksum::new(6);
+    c.update(&data);
+    assert_eq!(c.finish(), 0x0006 + 11);
+
+    let mut c = Checksum::new(6);
+    for b in data.iter() { c.update(std::slice::from_ref(b)); c.update(&[]); }
+    assert_eq!(c.finish(), 0x0006 + 11);
+
+    let mut c = Checksum::new(!0 - 8);
+    c.update(&data[..10]);
+    assert_eq!(c.finish(), 0x0001 + 0x0002 + 0xBBAA + 0xDDCC - 0xFFFF + 10);
+}
+
+#[test] fn checksum_odd_offset() {
+    let data = [0x01, 0x00, 0xFF, 0xFF, 0x02, 0xAA, 0xBB, 0xCC, 0xDD, 0x03, 0x04];
+    let mut zeroed = data;
+    zeroed[5 .. 9].iter_mut().for_each(|b| *b = 0);
+    let mut expected = Checksum::new(!0 - 8);
+    expected.update(&zeroed);
+
+    let mut c = Checksum::new(5);
+    c.update(&data);
+    assert_eq!(c.finish(), expected.finish());
+}
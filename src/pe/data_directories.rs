@@ -76,7 +76,7 @@ from_memory_struct! {
         pub delay_import:       DataDirectory,
 
         /// IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR
-        /// COM runtime descriptor / CLR header
+        /// COM runtime descriptor / CLR header: see [`crate::clr::Header`]
         pub com_descriptor:     DataDirectory,
 
         _reserved:              DataDirectory,
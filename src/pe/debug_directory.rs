@@ -0,0 +1,42 @@
+use crate::*;
+use pe::*;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [time_date_stamp](Self::time_date_stamp),
+    ///     [debug_type](Self::debug_type),
+    ///     [size_of_data](Self::size_of_data),
+    ///     [address_of_raw_data](Self::address_of_raw_data),
+    ///     [pointer_to_raw_data](Self::pointer_to_raw_data),
+    ///     ...
+    /// }<br>
+    /// An entry of the debug directory, referenced by [`pe::DataDirectories::debug`]<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#debug-directory-image-only>
+    /// *   `IMAGE_DEBUG_DIRECTORY` in `winnt.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct DebugDirectory {
+        /// Reserved, 0
+        pub characteristics:        u32,
+        pub time_date_stamp:        TimeDate,
+        pub version:                MajorMinorVersion<u16>,
+        pub debug_type:             DebugType,
+        /// Size of the debug data, excluding this directory entry
+        pub size_of_data:           u32,
+        /// Where the debug data is loaded, or 0 if it isn't mapped by a section
+        pub address_of_raw_data:    RVA,
+        /// File offset of the debug data
+        pub pointer_to_raw_data:    u32,
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(28, std::mem::size_of::<<DebugDirectory as FromMemory>::Raw>());
+}
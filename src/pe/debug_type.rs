@@ -0,0 +1,87 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+/// ∑
+/// [CODEVIEW](Self::CODEVIEW) |
+/// [POGO](Self::POGO) |
+/// [REPRO](Self::REPRO) |
+/// [VC_FEATURE](Self::VC_FEATURE) |
+/// ...<br>
+/// [pe::DebugDirectory::debug_type]: the format of the debug data<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#debug-type>
+/// *   `IMAGE_DEBUG_TYPE_*` in `winnt.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Pod, Zeroable)]
+pub struct DebugType(u32le);
+
+impl DebugType {
+    pub const fn new(value: u32) -> Self {
+        Self(u32le::new(value))
+    }
+
+    #[doc = "Unknown value, ignored by all tools"                               ] pub const UNKNOWN                 : DebugType = DebugType::new(0);
+    #[doc = "COFF debug information (line numbers, symbol table, string table)" ] pub const COFF                    : DebugType = DebugType::new(1);
+    #[doc = "Visual C++ debug information (typically an `RSDS` PDB reference)"  ] pub const CODEVIEW                : DebugType = DebugType::new(2);
+    #[doc = "Frame pointer omission information"                                ] pub const FPO                     : DebugType = DebugType::new(3);
+    #[doc = "Location of a DBG file"                                            ] pub const MISC                    : DebugType = DebugType::new(4);
+    #[doc = "A copy of the `.pdata` section"                                    ] pub const EXCEPTION               : DebugType = DebugType::new(5);
+    #[doc = "Reserved"                                                          ] pub const FIXUP                   : DebugType = DebugType::new(6);
+    #[doc = "Mapping from an RVA in the image to an RVA in the source image"    ] pub const OMAP_TO_SRC             : DebugType = DebugType::new(7);
+    #[doc = "Mapping from an RVA in the source image to an RVA in the image"    ] pub const OMAP_FROM_SRC           : DebugType = DebugType::new(8);
+    #[doc = "Reserved for Borland"                                              ] pub const BORLAND                 : DebugType = DebugType::new(9);
+    #[doc = "Reserved"                                                          ] pub const RESERVED10              : DebugType = DebugType::new(10);
+    #[doc = "Reserved"                                                          ] pub const CLSID                   : DebugType = DebugType::new(11);
+    #[doc = "Visual C++ feature usage counts"                                   ] pub const VC_FEATURE              : DebugType = DebugType::new(12);
+    #[doc = "Profile guided optimization data"                                  ] pub const POGO                    : DebugType = DebugType::new(13);
+    #[doc = "Incremental link time code generation"                             ] pub const ILTCG                   : DebugType = DebugType::new(14);
+    #[doc = "Intel memory protection extensions"                                ] pub const MPX                     : DebugType = DebugType::new(15);
+    #[doc = "Deterministic build: timestamps are hashes (`/Brepro`)"            ] pub const REPRO                   : DebugType = DebugType::new(16);
+    #[doc = "An embedded portable PDB"                                          ] pub const EMBEDDED_PORTABLE_PDB   : DebugType = DebugType::new(17);
+    #[doc = "A hash of the PDB"                                                 ] pub const PDB_CHECKSUM            : DebugType = DebugType::new(19);
+    #[doc = "Extended DLL characteristics, such as CET compatibility"           ] pub const EX_DLLCHARACTERISTICS   : DebugType = DebugType::new(20);
+}
+
+impl Debug for DebugType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::UNKNOWN               => "UNKNOWN",
+            Self::COFF                  => "COFF",
+            Self::CODEVIEW              => "CODEVIEW",
+            Self::FPO                   => "FPO",
+            Self::MISC                  => "MISC",
+            Self::EXCEPTION             => "EXCEPTION",
+            Self::FIXUP                 => "FIXUP",
+            Self::OMAP_TO_SRC           => "OMAP_TO_SRC",
+            Self::OMAP_FROM_SRC         => "OMAP_FROM_SRC",
+            Self::BORLAND               => "BORLAND",
+            Self::RESERVED10            => "RESERVED10",
+            Self::CLSID                 => "CLSID",
+            Self::VC_FEATURE            => "VC_FEATURE",
+            Self::POGO                  => "POGO",
+            Self::ILTCG                 => "ILTCG",
+            Self::MPX                   => "MPX",
+            Self::REPRO                 => "REPRO",
+            Self::EMBEDDED_PORTABLE_PDB => "EMBEDDED_PORTABLE_PDB",
+            Self::PDB_CHECKSUM          => "PDB_CHECKSUM",
+            Self::EX_DLLCHARACTERISTICS => "EX_DLLCHARACTERISTICS",
+            other                       => return write!(fmt, "DebugType({})", other.0.to_le()),
+        };
+        write!(fmt, "DebugType::{}", name)
+    }
+}
+
+impl FromMemory for DebugType {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
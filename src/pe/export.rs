@@ -0,0 +1,52 @@
+use crate::*;
+
+
+
+/// ⨯ { [ordinal](Self::ordinal), [name](Self::name), [hint](Self::hint), [target](Self::target) }<br>
+/// A single function or variable exported by a DLL<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    /// The (biased) ordinal, as used to import by ordinal
+    pub ordinal:    u16,
+    /// The exported name, or [`None`] for `NONAME` exports only exported by ordinal
+    pub name:       Option<String>,
+    /// Index into the name pointer table (or 0 if unnamed), to speed up lookups by importers
+    pub hint:       u16,
+    pub target:     ExportTarget,
+}
+
+/// ∑ [Rva](Self::Rva) | [Forwarder](Self::Forwarder)<br>
+/// Where an [`Export`] resolves to<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// The export is defined by this image, at this [`pe::RVA`]
+    Rva(pe::RVA),
+    /// The export is forwarded to another DLL, e.g. `"NTDLL.RtlAllocateHeap"` or `"NTDLL.#123"`
+    Forwarder(String),
+}
+
+/// ⨯ { [directory](Self::directory), [dll_name](Self::dll_name), [exports](Self::exports) }<br>
+/// The decoded export tables of a DLL, as read by [`pe::Reader::read_exports`]<br>
+/// <br>
+#[derive(Clone, Debug, Default)]
+pub struct Exports {
+    pub directory:  pe::ExportDirectory,
+    /// The name the DLL was linked as (e.g. `"KERNEL32.dll"`)
+    pub dll_name:   String,
+    /// Every export, sorted by [`ordinal`](Export::ordinal).  Functions exported under multiple names appear once per name.
+    pub exports:    Vec<Export>,
+}
+
+impl Exports {
+    /// Find an export by name
+    pub fn find(&self, name: &str) -> Option<&Export> {
+        self.exports.iter().find(|e| e.name.as_deref() == Some(name))
+    }
+
+    /// Find an export by (biased) ordinal
+    pub fn find_ordinal(&self, ordinal: u16) -> Option<&Export> {
+        self.exports.iter().find(|e| e.ordinal == ordinal)
+    }
+}
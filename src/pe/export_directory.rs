@@ -0,0 +1,55 @@
+use crate::*;
+use pe::*;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [time_date_stamp](Self::time_date_stamp),
+    ///     [name_rva](Self::name_rva),
+    ///     [ordinal_base](Self::ordinal_base),
+    ///     [nfunctions](Self::nfunctions),
+    ///     [nnames](Self::nnames),
+    ///     [functions_rva](Self::functions_rva),
+    ///     [names_rva](Self::names_rva),
+    ///     [name_ordinals_rva](Self::name_ordinals_rva),
+    ///     ...
+    /// }<br>
+    /// The export directory table, referenced by [`pe::DataDirectories::export`]<br>
+    /// <br>
+    ///
+    /// See [`pe::Reader::read_exports`] to decode the tables this references.
+    ///
+    /// ## References
+    /// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#export-directory-table>
+    /// *   `IMAGE_EXPORT_DIRECTORY` in `winnt.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ExportDirectory {
+        /// Reserved, 0
+        pub characteristics:    u32,
+        pub time_date_stamp:    TimeDate,
+        pub major_version:      u16,
+        pub minor_version:      u16,
+        /// [`RVA`] of the name of the DLL (e.g. `"KERNEL32.dll"`)
+        pub name_rva:           RVA,
+        /// The ordinal of the first entry of the export address table (typically 1)
+        pub ordinal_base:       u32,
+        /// Number of entries in the export address table
+        pub nfunctions:         u32,
+        /// Number of entries in the name pointer and name ordinal tables
+        pub nnames:             u32,
+        /// [`RVA`] of the export address table: an [`RVA`] (or forwarder string [`RVA`]) per exported function
+        pub functions_rva:      RVA,
+        /// [`RVA`] of the name pointer table: sorted [`RVA`]s of export names
+        pub names_rva:          RVA,
+        /// [`RVA`] of the name ordinal table: an unbiased [`u16`] index into the export address table per name
+        pub name_ordinals_rva:  RVA,
+    }
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(40, std::mem::size_of::<<ExportDirectory as FromMemory>::Raw>());
+}
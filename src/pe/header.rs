@@ -93,6 +93,23 @@ impl Header {
             },
         }
     }
+
+    /// Serialize this header as it would appear in a file.
+    ///
+    /// The optional header is truncated or zero-padded to [`FileHeader::optional_header_size`], matching [`read_from`](Self::read_from).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(bytes_of(&self.signature));
+        data.extend_from_slice(&self.file_header.to_bytes());
+        let mut optional_header = match self.optional_header.as_ref() {
+            None                                            => Vec::new(),
+            Some(OptionalHeader::OptionalHeader32(oh))      => oh.to_bytes(),
+            Some(OptionalHeader::OptionalHeader64(oh))      => oh.to_bytes(),
+        };
+        optional_header.resize(usize::from(self.file_header.optional_header_size), 0);
+        data.extend_from_slice(&optional_header[..]);
+        data
+    }
 }
 
 
@@ -0,0 +1,1004 @@
+use crate::*;
+use crate::io::Src;
+use super::*;
+
+use bytemuck::*;
+
+use std::convert::*;
+use std::fs;
+use std::io::{self, Write};
+use std::num::NonZeroU32;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+
+
+/// An existing PE image loaded into memory for editing<br>
+/// <br>
+///
+/// Edit the public [`file_header`](Self::file_header) and [`optional_header`](Self::optional_header) fields, then [`write`](Self::write) or [`save`](Self::save)
+/// the image: the (fixed size) headers are patched in place, and the checksum is recomputed.
+/// Sections can also be [added](Self::add_section), [removed](Self::remove_section), or [resized](Self::resize_last_section),
+/// which moves later raw data and any overlay, updating file offsets that reference them.
+/// Note that any Authenticode signature will no longer match the modified image.
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_format_exe::*;
+/// let mut image = pe::Image::open("example.exe").unwrap();
+/// image.optional_header.set_subsystem(pe::Subsystem::WINDOWS_GUI);
+/// image.optional_header.set_dll_characteristics(image.optional_header.dll_characteristics() | pe::DllCharacteristics::NX_COMPAT);
+/// image.file_header.characteristics |= pe::FileCharacteristics::LARGE_ADDRESS_AWARE;
+/// image.save("example.exe").unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Image {
+    src:                Src,
+    data:               Vec<u8>,
+    pe_header_start:    u32,
+    /// Written back as-is, except for [`nsections`](FileHeader::nsections)
+    pub file_header:    FileHeader,
+    /// Written back as-is, except for [`checksum`](OptionalHeader32::checksum)
+    pub optional_header: OptionalHeader,
+    section_headers:    Vec<SectionHeader>,
+}
+
+impl Image {
+    /// Read and parse a PE image from disk.
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`fs::read`]
+    /// *   See [`from_bytes`](Self::from_bytes)
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let data = fs::read(&path);
+        let src = Src::PathBuf(path);
+        let data = src.anno(data, "error reading pe::Image")?;
+        Self::from_bytes_src(data, src)
+    }
+
+    /// Parse a PE image from memory.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the `MZ` or `PE` signatures are invalid, or there's no [`pe::OptionalHeader`]
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the headers are truncated
+    pub fn from_bytes(data: Vec<u8>) -> io::Result<Self> { Self::from_bytes_src(data, Src::Unknown) }
+
+    fn from_bytes_src(data: Vec<u8>, src: Src) -> io::Result<Self> {
+        let mz_header = src.anno(mz::Header::read_from(&mut &data[..]), "error reading mz::Header")?;
+        let pe_header_start = mz_header.pe_header_start;
+        let mut r = data.get(pe_header_start as usize ..).unwrap_or(&[]);
+        let header = src.anno(pe::Header::read_from(&mut r), "error reading pe::Header")?;
+        let optional_header = src.anno(header.optional_header.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "pe::Header::optional_header is None")), "error reading pe::Image")?;
+        let mut r = data.get(Self::section_headers_start(pe_header_start, &header.file_header) ..).unwrap_or(&[]);
+        let section_headers = (0 .. header.file_header.nsections).map(|_| SectionHeader::read_from(&mut r)).collect::<io::Result<Vec<_>>>();
+        let section_headers = src.anno(section_headers, "error reading pe::SectionHeader")?;
+        Ok(Self { src, data, pe_header_start, file_header: header.file_header, optional_header, section_headers })
+    }
+
+    fn section_range(&self, section: &SectionHeader) -> Range<usize> {
+        let start = section.pointer_to_raw_data.map_or(0, |ptr| ptr.get() as usize).min(self.data.len());
+        let end = (start + section.size_of_raw_data as usize).min(self.data.len());
+        start .. end
+    }
+
+    fn end_of_sections(&self) -> usize {
+        let headers = self.optional_header.size_of_headers() as usize;
+        self.section_headers.iter().map(|s| self.section_range(s).end).fold(headers, usize::max)
+    }
+
+    /// Ensure no [`DataDirectory`] (besides the certificate table, which isn't loaded) references `rva` within section `idx`
+    fn check_unreferenced(&self, rva: Range<RVA>, idx: usize) -> io::Result<()> {
+        let section = &self.section_headers[idx];
+        let section = u64::from(section.virtual_address) .. u64::from(section.virtual_address) + u64::from(section.virtual_size);
+        let rva = u64::from(rva.start) .. u64::from(rva.end);
+        for (name, dd) in self.data_directory().iter_name_dd() {
+            if name == "security" || dd.size == 0 { continue }
+            let dd_start = u64::from(dd.virtual_address);
+            let dd_end = dd_start + u64::from(dd.size); // u64: dd is typically from untrusted data
+            if dd_start < section.end.min(rva.end) && rva.start.max(section.start) < dd_end {
+                return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::DataDirectories::{} references section {}", name, idx))), "error editing pe::Image");
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove `range` of the file if it trails the sections, or zero it in place otherwise (to avoid moving mapped data)
+    fn erase(&mut self, range: Range<usize>) {
+        let range = range.start.min(self.data.len()) .. range.end.min(self.data.len());
+        if range.start >= self.end_of_sections() {
+            self.splice(range, Vec::new());
+        } else {
+            self.data[range].iter_mut().for_each(|b| *b = 0);
+        }
+    }
+
+    /// Replace `range` of the file with `replacement`, updating file offsets (section data, COFF symbols, certificates, debug data) that followed it
+    fn splice(&mut self, range: Range<usize>, replacement: Vec<u8>) {
+        let delta = replacement.len() as i64 - (range.end - range.start) as i64;
+        let at = range.end;
+        self.data.splice(range, replacement);
+        if delta == 0 { return }
+        let shift = |offset: u32| if offset as usize >= at { (i64::from(offset) + delta) as u32 } else { offset };
+        let shift_nz = |offset: Option<NonZeroU32>| offset.and_then(|o| NonZeroU32::new(shift(o.get())));
+
+        for section in self.section_headers.iter_mut() {
+            section.pointer_to_raw_data     = shift_nz(section.pointer_to_raw_data);
+            section.pointer_to_relocations  = shift_nz(section.pointer_to_relocations);
+            section.pointer_to_linenumbers  = shift_nz(section.pointer_to_linenumbers);
+        }
+        if self.file_header.symbols != 0 { self.file_header.symbols = shift(self.file_header.symbols) }
+        let security = &mut self.optional_header.data_directory_mut().security;
+        if security.size != 0 { security.virtual_address = RVA::new(shift(security.virtual_address.to_u32())) } // a file offset, not an RVA
+
+        let debug = self.data_directory().debug;
+        let entry_size = std::mem::size_of::<<DebugDirectory as FromMemory>::Raw>();
+        if let Some(start) = self.rva_to_offset(debug.virtual_address).filter(|_| debug.size != 0) {
+            for entry in (start .. start + debug.size as usize).step_by(entry_size) {
+                let raw = match self.data.get_mut(entry .. entry + entry_size) { Some(raw) => raw, None => break };
+                let mut dir = match DebugDirectory::from_io(&mut &raw[..]) { Ok(dir) => dir, Err(_) => break };
+                if dir.pointer_to_raw_data == 0 { continue }
+                dir.pointer_to_raw_data = shift(dir.pointer_to_raw_data);
+                raw.copy_from_slice(&dir.to_bytes()[..]);
+            }
+        }
+    }
+
+    fn section_headers_start(pe_header_start: u32, file_header: &FileHeader) -> usize {
+        pe_header_start as usize + 4 + std::mem::size_of::<<FileHeader as FromMemory>::Raw>() + usize::from(file_header.optional_header_size)
+    }
+
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn pe_header_start(&self) -> u32 { self.pe_header_start }
+    #[allow(missing_docs)] pub fn section_headers(&self) -> &[SectionHeader] { &self.section_headers[..] }
+    #[allow(missing_docs)] pub fn data_directory(&self) -> &DataDirectories { self.optional_header.data_directory() }
+    #[allow(missing_docs)] pub fn data_directory_mut(&mut self) -> &mut DataDirectories { self.optional_header.data_directory_mut() }
+
+    /// The image as originally read, including section layout changes, but without any pending header modifications
+    pub fn original_bytes(&self) -> &[u8] { &self.data[..] }
+
+    /// The raw data of section `idx` (up to [`size_of_raw_data`](SectionHeader::size_of_raw_data) bytes), or [`None`] if `idx` is out of bounds
+    pub fn section_data(&self, idx: usize) -> Option<&[u8]> {
+        let range = self.section_range(self.section_headers.get(idx)?);
+        self.data.get(range)
+    }
+
+    /// The raw data of section `idx` (up to [`size_of_raw_data`](SectionHeader::size_of_raw_data) bytes), or [`None`] if `idx` is out of bounds
+    pub fn section_data_mut(&mut self, idx: usize) -> Option<&mut [u8]> {
+        let range = self.section_range(self.section_headers.get(idx)?);
+        self.data.get_mut(range)
+    }
+
+    /// Any data trailing the last section's raw data, including the certificate table if the image is signed
+    pub fn overlay(&self) -> &[u8] { &self.data[self.end_of_sections().min(self.data.len()) ..] }
+
+    /// Convert an [`RVA`] to an offset into [`original_bytes`](Self::original_bytes), if it's backed by file data
+    pub fn rva_to_offset(&self, rva: RVA) -> Option<usize> {
+        if rva.to_u32() < self.optional_header.size_of_headers() { return Some(rva.to_usize()) }
+        let section = self.section_headers.iter().find(|s| s.virtual_address_range().contains(&rva))?;
+        let offset = rva - section.virtual_address;
+        if offset >= section.size_of_raw_data { return None }
+        Some(section.pointer_to_raw_data?.get() as usize + offset as usize)
+    }
+
+    /// Read the [`DebugDirectory`] entries referenced by [`DataDirectories::debug`].
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the debug directory isn't backed by file data
+    pub fn debug_directory(&self) -> io::Result<Vec<DebugDirectory>> {
+        let dd = self.data_directory().debug;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(Vec::new()) }
+        let n = dd.size as usize / std::mem::size_of::<<DebugDirectory as FromMemory>::Raw>();
+        let r = self.rva_to_offset(dd.virtual_address).and_then(|start| self.data.get(start .. start + dd.size as usize));
+        let mut r = self.src.anno(r.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "pe::DataDirectories::debug isn't backed by file data")), "error reading pe::DebugDirectory")?;
+        let entries = (0 .. n).map(|_| DebugDirectory::from_io(&mut r)).collect();
+        self.src.anno(entries, "error reading pe::DebugDirectory")
+    }
+
+    /// Parse the resource tree referenced by [`DataDirectories::resource`], or an empty one if the image has no resources.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the resource directory or data isn't backed by file data
+    /// *   [`io::ErrorKind::InvalidData`] / [`io::ErrorKind::UnexpectedEof`] if the resource tree is malformed
+    pub fn resources(&self) -> io::Result<Resources> {
+        let dd = self.data_directory().resource;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(Resources::default()) }
+        let read = |rva: RVA, size: u32| self.rva_to_offset(rva).and_then(|start| self.data.get(start .. start.checked_add(size as usize)?))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("resource data at {:?} isn't backed by file data", rva)));
+        let dir = self.src.anno(read(dd.virtual_address, dd.size), "error reading pe::DataDirectories::resource")?;
+        self.src.anno(Resources::parse(dir, |rva, size| read(rva, size).map(|data| data.to_vec())), "error reading pe::Resources")
+    }
+
+    /// Serialize `resources` and update [`DataDirectories::resource`] to reference them.
+    ///
+    /// If the existing resources are at the start of a section, that section is reused:
+    /// *   The last section is [resized](Self::resize_last_section) to fit (typical for images without relocations).
+    /// *   Other sections are overwritten in place if the resources fit within their existing raw data and address range,
+    ///     and no other [`DataDirectory`] references the section.
+    ///
+    /// Otherwise, a new `.rsrc` section is [added](Self::add_section).
+    /// The section previously holding the resources is then [removed](Self::remove_section) if nothing else references it,
+    /// so repeated calls don't accumulate sections when e.g. `.reloc` follows `.rsrc`.
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`Resources::to_bytes`], [`add_section`](Self::add_section), or [`resize_last_section`](Self::resize_last_section)
+    pub fn set_resources(&mut self, resources: &Resources) -> io::Result<()> {
+        let size = resources.to_bytes(RVA::NULL)?.len() as u32;
+        let dd = self.data_directory().resource;
+        let last = self.section_headers.len().checked_sub(1);
+        let existing = self.section_headers.iter().position(|s| dd.size != 0 && s.virtual_address == dd.virtual_address);
+
+        let old = std::mem::replace(&mut self.data_directory_mut().resource, DataDirectory::EMPTY);
+        let fits_in_place = existing.filter(|idx| Some(*idx) != last).filter(|idx| {
+            let section = &self.section_headers[*idx];
+            let next = self.section_headers.iter().map(|s| s.virtual_address.to_u32()).filter(|va| *va > section.virtual_address.to_u32()).min().unwrap_or(u32::MAX);
+            let span = next - section.virtual_address.to_u32();
+            size <= section.size_of_raw_data.min(span) && self.section_range(section).len() == section.size_of_raw_data as usize
+                && self.check_unreferenced(section.virtual_address .. RVA::new(u32::MAX), *idx).is_ok() // the whole section is overwritten / zeroed
+        });
+        let (idx, rva) = match (existing, fits_in_place) {
+            (_, Some(idx)) => {
+                let section = &mut self.section_headers[idx];
+                section.virtual_size = section.virtual_size.max(size);
+                (idx, section.virtual_address)
+            },
+            (Some(idx), None) if Some(idx) == last => {
+                if let Err(err) = self.resize_last_section(size) {
+                    self.data_directory_mut().resource = old;
+                    return Err(err);
+                }
+                (idx, self.section_headers[idx].virtual_address)
+            },
+            _ => {
+                let characteristics = SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ;
+                match self.add_section(".rsrc", characteristics, vec![0u8; size as usize]) {
+                    Ok(rva) => (self.section_headers.len() - 1, rva),
+                    Err(err) => {
+                        self.data_directory_mut().resource = old;
+                        return Err(err);
+                    },
+                }
+            },
+        };
+
+        let bytes = resources.to_bytes(rva)?;
+        let data = self.section_data_mut(idx).expect("section just added, resized, or checked");
+        data[.. bytes.len()].copy_from_slice(&bytes[..]);
+        data[bytes.len() ..].iter_mut().for_each(|b| *b = 0);
+        self.data_directory_mut().resource = DataDirectory { virtual_address: rva, size };
+
+        if let (Some(existing), None) = (existing, fits_in_place) {
+            if existing != idx { let _ = self.remove_section(existing); } // left unreferenced in place if something else still uses it
+        }
+        Ok(())
+    }
+
+    /// Read the [`ImportDescriptor`]s referenced by [`DataDirectories::import`], up to (excluding) the null terminator.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the import directory isn't backed by file data
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the import directory isn't null terminated
+    pub fn import_descriptors(&self) -> io::Result<Vec<ImportDescriptor>> {
+        let dd = self.data_directory().import;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(Vec::new()) }
+        let start = self.rva_to_offset(dd.virtual_address).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "pe::DataDirectories::import isn't backed by file data"));
+        let mut r = &self.data[self.src.anno(start, "error reading pe::ImportDescriptor")? ..];
+        let mut descriptors = Vec::new();
+        loop {
+            let descriptor = self.src.anno(ImportDescriptor::from_io(&mut r), "error reading pe::ImportDescriptor")?;
+            if descriptor.import_lookup_table_rva == RVA::NULL && descriptor.dll_ascii_name_rva == RVA::NULL && descriptor.iat_rva == RVA::NULL { break }
+            descriptors.push(descriptor);
+        }
+        Ok(descriptors)
+    }
+
+    /// The `b'\0'`-terminated string at `rva` (excluding the terminator), if it's backed by file data
+    pub fn read_strz_rva(&self, rva: RVA) -> Option<&[u8]> {
+        let data = &self.data[self.rva_to_offset(rva)? ..];
+        Some(&data[.. data.iter().position(|b| *b == 0)?])
+    }
+
+    /// Import `symbols` from `dll`, returning the [`RVA`] of each symbol's Import Address Table slot.
+    ///
+    /// The import directory is copied to a new `.idata` section, along with a new [`ImportDescriptor`], ILT, IAT, and hint/name table.
+    /// Existing ILTs and IATs are left in place, so code referencing them remains valid.
+    /// [`DataDirectories::iat`] is only set if it was empty: it's left unchanged otherwise, as it needn't cover every IAT.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `dll` or a symbol name contains `'\0'`
+    /// *   [`io::Error`] forwarded from [`import_descriptors`](Self::import_descriptors) or [`add_section`](Self::add_section)
+    pub fn add_import(&mut self, dll: &str, symbols: &[ImportSymbol]) -> io::Result<Vec<RVA>> {
+        let nul = |name: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Image::add_import: {:?} contains '\\0'", name));
+        if dll.contains('\0') { return Err(nul(dll)) }
+        for symbol in symbols.iter() { if let ImportSymbol::Name { name, .. } = symbol { if name.contains('\0') { return Err(nul(name)) } } }
+
+        let descriptors = self.import_descriptors()?;
+        let descriptor_size = std::mem::size_of::<<ImportDescriptor as FromMemory>::Raw>();
+        let entry_size = match self.optional_header {
+            OptionalHeader::OptionalHeader32(_) => std::mem::size_of::<<ImportLookupTableEntry32 as FromMemory>::Raw>(),
+            OptionalHeader::OptionalHeader64(_) => std::mem::size_of::<<ImportLookupTableEntry64 as FromMemory>::Raw>(),
+        };
+        let directory_size  = descriptor_size * (descriptors.len() + 2);
+        let table_size      = entry_size * (symbols.len() + 1);
+        let ilt             = align(directory_size as u64, entry_size as u32) as usize;
+        let iat             = ilt + table_size;
+        let mut offset      = iat + table_size;
+        let mut hint_names  = Vec::with_capacity(symbols.len());
+        for symbol in symbols.iter() {
+            hint_names.push(offset);
+            if let ImportSymbol::Name { name, .. } = symbol { offset = (offset + 2 + name.len() + 1 + 1) & !1 }
+        }
+        let dll_name = offset;
+        let size = dll_name + dll.len() + 1;
+
+        let characteristics = SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ | SectionCharacteristics::MEM_WRITE;
+        let rva = self.add_section(".idata", characteristics, vec![0u8; size])?;
+        let at = |offset: usize| rva + offset as u32;
+
+        let mut data = Vec::with_capacity(size);
+        for descriptor in descriptors.iter() { data.extend_from_slice(&descriptor.to_bytes()[..]) }
+        data.extend_from_slice(&ImportDescriptor {
+            import_lookup_table_rva:    at(ilt),
+            time_date_stamp:            TimeDate::UNIX_EPOCH,
+            forwarder_chain:            0,
+            dll_ascii_name_rva:         at(dll_name),
+            iat_rva:                    at(iat),
+        }.to_bytes()[..]);
+        data.resize(ilt, 0);
+        let mut table = Vec::with_capacity(table_size);
+        for (symbol, hint_name) in symbols.iter().zip(hint_names.iter().copied()) {
+            match (symbol, entry_size) {
+                (ImportSymbol::Ordinal(ordinal), 4) => table.extend_from_slice(&ImportLookupTableEntry32::from_ordinal(*ordinal).to_bytes()[..]),
+                (ImportSymbol::Ordinal(ordinal), _) => table.extend_from_slice(&ImportLookupTableEntry64::from_ordinal(*ordinal).to_bytes()[..]),
+                (ImportSymbol::Name { .. }, 4)      => table.extend_from_slice(&ImportLookupTableEntry32::from_name_table_rva(at(hint_name)).to_bytes()[..]),
+                (ImportSymbol::Name { .. }, _)      => table.extend_from_slice(&ImportLookupTableEntry64::from_name_table_rva(at(hint_name)).to_bytes()[..]),
+            }
+        }
+        table.resize(table_size, 0);
+        data.extend_from_slice(&table[..]); // ILT
+        data.extend_from_slice(&table[..]); // IAT (overwritten by the loader)
+        for symbol in symbols.iter() {
+            if let ImportSymbol::Name { hint, name } = symbol {
+                data.extend_from_slice(&hint.to_le_bytes());
+                data.extend_from_slice(name.as_bytes());
+                data.push(0);
+                data.resize((data.len() + 1) & !1, 0);
+            }
+        }
+        data.extend_from_slice(dll.as_bytes());
+        data.push(0);
+        debug_assert_eq!(data.len(), size);
+
+        let idx = self.section_headers.len() - 1;
+        self.section_data_mut(idx).expect("section just added")[.. size].copy_from_slice(&data[..]);
+        let new_iat = at(iat) .. at(iat) + table_size as u32;
+        let dd = self.data_directory_mut();
+        dd.import = DataDirectory { virtual_address: rva, size: directory_size as u32 };
+        if dd.iat.size == 0 { dd.iat = DataDirectory { virtual_address: new_iat.start, size: table_size as u32 } }
+        Ok(symbols.iter().enumerate().map(|(i, _)| new_iat.start + (i * entry_size) as u32).collect())
+    }
+
+    /// Rename imported DLL `from` (case insensitive, e.g. `"KERNEL32.dll"`) to `to`, returning the number of [`ImportDescriptor`]s updated.
+    ///
+    /// Names are overwritten in place if `to` fits, otherwise `to` is written to a new `.idata` section.
+    /// [`DataDirectories::bound_imports`] is cleared, as it would refer to the old DLL name.
+    /// Delay-load imports are not renamed.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `to` contains `'\0'`
+    /// *   [`io::ErrorKind::NotFound`] if no [`ImportDescriptor`] imports `from`
+    /// *   [`io::Error`] forwarded from [`import_descriptors`](Self::import_descriptors) or [`add_section`](Self::add_section)
+    pub fn rename_import(&mut self, from: &str, to: &str) -> io::Result<usize> {
+        if to.contains('\0') { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Image::rename_import: {:?} contains '\\0'", to))) }
+        let descriptors = self.import_descriptors()?;
+        let matches = descriptors.iter().enumerate().filter_map(|(i, d)| {
+            let name = self.read_strz_rva(d.dll_ascii_name_rva)?;
+            if name.eq_ignore_ascii_case(from.as_bytes()) { Some((i, d.dll_ascii_name_rva, name.len())) } else { None }
+        }).collect::<Vec<_>>();
+        if matches.is_empty() { return self.src.anno(Err(io::Error::new(io::ErrorKind::NotFound, format!("no pe::ImportDescriptor imports {:?}", from))), "error renaming import") }
+
+        let mut relocated = None;
+        if matches.iter().any(|(_, _, len)| to.len() > *len) {
+            let mut name = to.as_bytes().to_vec();
+            name.push(0);
+            relocated = Some(self.add_section(".idata", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, name)?);
+        }
+
+        let directory = self.rva_to_offset(self.data_directory().import.virtual_address).expect("pe::DataDirectories::import was just read");
+        let descriptor_size = std::mem::size_of::<<ImportDescriptor as FromMemory>::Raw>();
+        for (i, name_rva, len) in matches.iter().copied() {
+            match relocated {
+                Some(rva) if to.len() > len => {
+                    let mut descriptor = descriptors[i];
+                    descriptor.dll_ascii_name_rva = rva;
+                    let at = directory + descriptor_size * i;
+                    self.data[at .. at + descriptor_size].copy_from_slice(&descriptor.to_bytes()[..]);
+                },
+                _ => {
+                    let at = self.rva_to_offset(name_rva).expect("name was just read");
+                    let name = &mut self.data[at .. at + len];
+                    name.iter_mut().for_each(|b| *b = 0);
+                    name[.. to.len()].copy_from_slice(to.as_bytes());
+                },
+            }
+        }
+        self.data_directory_mut().bound_imports = DataDirectory::EMPTY;
+        Ok(matches.len())
+    }
+
+    /// Remove debug information, signatures, and other data not needed to load the image, as selected by `options`.
+    ///
+    /// Data mapped by sections (such as the debug directory in `.rdata`) is zeroed in place, while data trailing the sections is removed.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use maulingmonkey_format_exe::*;
+    /// let mut image = pe::Image::open("example.exe").unwrap();
+    /// image.strip(&pe::StripOptions { timestamps: true, .. Default::default() }).unwrap();
+    /// image.save("example.exe").unwrap();
+    /// ```
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the debug directory isn't backed by file data
+    /// *   [`io::ErrorKind::InvalidData`] if a `"Rich"` marker was found without a matching `"DanS"` marker
+    /// *   [`io::ErrorKind::InvalidData`] / [`io::ErrorKind::UnexpectedEof`] if stripping timestamps and the resource tree is malformed
+    pub fn strip(&mut self, options: &StripOptions) -> io::Result<()> {
+        if options.certificates {
+            let security = self.data_directory().security;
+            self.data_directory_mut().security = DataDirectory::EMPTY;
+            let start = security.virtual_address.to_usize(); // a file offset, not an RVA
+            if security.size != 0 { self.erase(start .. start.saturating_add(security.size as usize)) }
+        }
+
+        if options.debug {
+            let debug = self.data_directory().debug;
+            let mut entries = self.debug_directory()?;
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.pointer_to_raw_data));
+            for entry in entries.iter() {
+                let start = entry.pointer_to_raw_data as usize;
+                if start != 0 { self.erase(start .. start.saturating_add(entry.size_of_data as usize)) }
+            }
+            if let Some(start) = self.rva_to_offset(debug.virtual_address).filter(|_| debug.size != 0) {
+                self.erase(start .. start + debug.size as usize);
+            }
+            self.data_directory_mut().debug = DataDirectory::EMPTY;
+
+            if self.file_header.symbols != 0 {
+                let start = self.file_header.symbols as usize;
+                let strings = (start + self.file_header.nsymbols as usize * coff::Symbol::SIZE).min(self.data.len());
+                let strings_size = self.data.get(strings .. strings + 4).map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+                self.file_header.symbols    = 0;
+                self.file_header.nsymbols   = 0;
+                self.erase(start .. strings + strings_size);
+            }
+        }
+
+        if options.rich_header {
+            let prefix = &self.data[.. (self.pe_header_start as usize).min(self.data.len())];
+            if let Some(rich) = self.src.anno(RichHeader::find_in(prefix), "error decoding pe::RichHeader")? {
+                self.data[rich.offset as usize .. rich.end() as usize].iter_mut().for_each(|b| *b = 0);
+            }
+        }
+
+        if options.overlay {
+            let end = self.end_of_sections();
+            self.erase(end .. self.data.len());
+            let security = self.data_directory().security;
+            if security.virtual_address.to_usize() >= end { self.data_directory_mut().security = DataDirectory::EMPTY }
+            if self.file_header.symbols as usize >= end { self.file_header.symbols = 0; self.file_header.nsymbols = 0 }
+        }
+
+        if options.timestamps {
+            self.file_header.link_time_date = TimeDate::UNIX_EPOCH;
+            let time_date_stamp = 4; // after `characteristics`, in pe::ExportDirectory, pe::DebugDirectory, and IMAGE_RESOURCE_DIRECTORY
+            let export = self.data_directory().export;
+            let mut stamps = Vec::new();
+            if let Some(at) = self.rva_to_offset(export.virtual_address).filter(|_| export.size != 0) { stamps.push(at + time_date_stamp) }
+            let debug = self.data_directory().debug;
+            if let Some(start) = self.rva_to_offset(debug.virtual_address).filter(|_| debug.size != 0) {
+                let entry_size = std::mem::size_of::<<DebugDirectory as FromMemory>::Raw>();
+                stamps.extend((start .. start + debug.size as usize).step_by(entry_size).map(|at| at + time_date_stamp));
+            }
+            let resource = self.data_directory().resource;
+            if let Some(start) = self.rva_to_offset(resource.virtual_address).filter(|_| resource.size != 0) {
+                let dir = self.data.get(start .. start.saturating_add(resource.size as usize)).unwrap_or(&[]);
+                let offsets = self.src.anno(Resources::directory_offsets(dir), "error reading pe::Resources")?;
+                stamps.extend(offsets.into_iter().map(|offset| start + offset + time_date_stamp));
+            }
+            for at in stamps {
+                if let Some(stamp) = self.data.get_mut(at .. at + 4) { stamp.iter_mut().for_each(|b| *b = 0) }
+            }
+        }
+        Ok(())
+    }
+
+    /// Append a new section named `name` (up to 8 bytes) containing `data`, returning the [`RVA`] it will be loaded at.
+    ///
+    /// The [`virtual_size`](SectionHeader::virtual_size) is `data.len()`, or 1 if `data` is empty (so a later section doesn't share its [`RVA`].)
+    /// Use [`add_section_with_virtual_size`](Self::add_section_with_virtual_size) for uninitialized (`.bss`) data.
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`add_section_with_virtual_size`](Self::add_section_with_virtual_size)
+    pub fn add_section(&mut self, name: &str, characteristics: SectionCharacteristics, data: Vec<u8>) -> io::Result<RVA> {
+        let virtual_size = u32::try_from(data.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pe::Image: image exceeds 4 GiB"))?;
+        self.add_section_with_virtual_size(name, characteristics, data, virtual_size.max(1))
+    }
+
+    /// Append a new section named `name` (up to 8 bytes) containing `data`, zero filled to `virtual_size` bytes when loaded, returning the [`RVA`] it will be loaded at.
+    ///
+    /// The raw data is placed after the existing sections, moving any overlay (such as the certificate table) after it.
+    /// If the section table has no room for another entry, [`size_of_headers`](OptionalHeader32::size_of_headers) is grown,
+    /// moving all section data, provided the first section isn't loaded immediately after the headers.
+    /// [`DataDirectories::bound_imports`] is cleared (and its bytes zeroed) if the new entry overwrites it.
+    /// On error, the image is left unmodified.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `name` is longer than 8 bytes, or the image would exceed 4 GiB
+    /// *   [`io::ErrorKind::InvalidInput`] if `virtual_size` is 0, or less than `data.len()`
+    /// *   [`io::ErrorKind::InvalidData`] if there's no room for another [`SectionHeader`]
+    pub fn add_section_with_virtual_size(&mut self, name: &str, characteristics: SectionCharacteristics, data: Vec<u8>, virtual_size: u32) -> io::Result<RVA> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "pe::Image: image exceeds 4 GiB");
+        if virtual_size == 0 || (virtual_size as usize) < data.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Image: section virtual size 0x{:X} is 0 or smaller than its 0x{:X} bytes of data", virtual_size, data.len())));
+        }
+        if name.len() > 8 { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Image: section name {:?} exceeds 8 bytes", name))) }
+        let mut short = [0u8; 8];
+        short[..name.len()].copy_from_slice(name.as_bytes());
+
+        let file_alignment      = self.optional_header.file_alignment();
+        let section_alignment   = self.optional_header.section_alignment();
+        let section_header_size = std::mem::size_of::<<SectionHeader as FromMemory>::Raw>();
+        let table_end           = Self::section_headers_start(self.pe_header_start, &self.file_header) + section_header_size * self.section_headers.len();
+        let new_table_end       = table_end + section_header_size;
+
+        // Validate everything up front, so a failure leaves the image untouched
+        let bound = self.data_directory().bound_imports;
+        let bound = bound.virtual_address.to_usize() .. bound.virtual_address.to_usize().saturating_add(bound.size as usize);
+        let drop_bound = !bound.is_empty() && bound.start < new_table_end && table_end < bound.end;
+        let size_of_headers = self.optional_header.size_of_headers();
+        let in_use = self.data.get(table_end .. new_table_end.min(size_of_headers as usize)).unwrap_or(&[]).iter().enumerate()
+            .any(|(i, b)| *b != 0 && !(drop_bound && bound.contains(&(table_end + i))));
+        if in_use { return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "no room for another pe::SectionHeader")), "error adding pe::SectionHeader") }
+
+        let headers_end = (size_of_headers as usize).min(self.data.len());
+        let new_size_of_headers = if new_table_end <= size_of_headers as usize { u64::from(size_of_headers) } else {
+            let new_size_of_headers = align(new_table_end as u64, file_alignment);
+            let first_va = self.section_headers.iter().map(|s| u64::from(s.virtual_address)).min().unwrap_or(u64::MAX);
+            if align(new_size_of_headers, section_alignment) > first_va {
+                return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidData, "no room for another pe::SectionHeader before the first section")), "error adding pe::SectionHeader");
+            }
+            u32::try_from(new_size_of_headers).map_err(|_| too_big())?;
+            new_size_of_headers
+        };
+        let headers_growth = new_size_of_headers.saturating_sub(headers_end as u64);
+
+        let next = self.section_headers.iter().map(|s| u64::from(s.virtual_address) + u64::from(s.virtual_size)).max().unwrap_or(new_size_of_headers);
+        let virtual_address = u32::try_from(align(next, section_alignment)).map_err(|_| too_big())?;
+        virtual_address.checked_add(virtual_size).ok_or_else(too_big)?;
+        let mut header = SectionHeader {
+            name: cast(short),
+            virtual_size,
+            virtual_address: RVA::new(virtual_address),
+            characteristics,
+            .. Default::default()
+        };
+        let at = self.end_of_sections().min(self.data.len()) + headers_growth as usize; // after growing the headers
+        let start = align(at as u64, file_alignment);
+        let size_of_raw_data = align(data.len() as u64, file_alignment);
+        if !data.is_empty() {
+            u32::try_from(start + size_of_raw_data).map_err(|_| too_big())?;
+            header.pointer_to_raw_data  = NonZeroU32::new(start as u32);
+            header.size_of_raw_data     = size_of_raw_data as u32;
+        }
+        let sections = self.section_headers.iter().copied().chain(Some(header)).collect::<Vec<_>>();
+        let mut dry_run = self.optional_header;
+        dry_run.update_sizes(&sections[..], new_size_of_headers as u32)?; // only raw data pointers change below, not sizes
+
+        if drop_bound {
+            let zero = bound.start.min(headers_end) .. bound.end.min(headers_end);
+            self.data[zero].iter_mut().for_each(|b| *b = 0);
+            self.data_directory_mut().bound_imports = DataDirectory::EMPTY;
+        }
+        if new_size_of_headers > u64::from(size_of_headers) {
+            self.splice(headers_end .. headers_end, vec![0u8; headers_growth as usize]);
+        }
+        if !data.is_empty() {
+            let mut raw = vec![0u8; (start - at as u64) as usize];
+            raw.extend_from_slice(&data[..]);
+            raw.resize((start + size_of_raw_data) as usize - at, 0);
+            self.splice(at .. at, raw);
+        }
+        self.section_headers.push(header);
+        self.optional_header.update_sizes(&self.section_headers[..], new_size_of_headers as u32)?;
+        Ok(RVA::new(virtual_address))
+    }
+
+    /// Remove section `idx` and its raw data, returning its header.
+    ///
+    /// Later raw data (and any overlay) is moved down to fill the gap.
+    /// If another section follows in memory, the preceding section's [`virtual_size`](SectionHeader::virtual_size) is extended to keep the image contiguous.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `idx` is out of bounds, or is the first of multiple sections
+    /// *   [`io::ErrorKind::InvalidInput`] if a [`DataDirectory`] references the section
+    pub fn remove_section(&mut self, idx: usize) -> io::Result<SectionHeader> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        let section = *self.section_headers.get(idx).ok_or_else(|| invalid(format!("pe::Image: section index {} out of bounds", idx)))?;
+        self.check_unreferenced(section.virtual_address .. RVA::new(u32::MAX), idx)?;
+        let is_last = idx + 1 == self.section_headers.len();
+        if idx == 0 && !is_last { return Err(invalid("pe::Image: cannot remove the first of multiple sections".into())) }
+
+        if !is_last {
+            let prev = &mut self.section_headers[idx-1];
+            let end = align(u64::from(section.virtual_address) + u64::from(section.virtual_size), self.optional_header.section_alignment());
+            prev.virtual_size = (end - u64::from(prev.virtual_address)) as u32;
+        }
+        let range = self.section_range(&section);
+        self.section_headers.remove(idx);
+        if !range.is_empty() { self.splice(range, Vec::new()) }
+        self.optional_header.update_sizes(&self.section_headers[..], self.optional_header.size_of_headers())?;
+        Ok(section)
+    }
+
+    /// Grow or shrink the last section to `virtual_size` bytes, zero filling or truncating its raw data to match.
+    ///
+    /// Any overlay (such as the certificate table) is moved to follow the new raw data.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the image has no sections, or the image would exceed 4 GiB
+    /// *   [`io::ErrorKind::InvalidInput`] if shrinking would truncate data referenced by a [`DataDirectory`]
+    pub fn resize_last_section(&mut self, virtual_size: u32) -> io::Result<()> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "pe::Image: image exceeds 4 GiB");
+        let idx = self.section_headers.len().checked_sub(1).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "pe::Image: no sections to resize"))?;
+        let section = self.section_headers[idx];
+        section.virtual_address.to_u32().checked_add(virtual_size).ok_or_else(too_big)?;
+        if virtual_size < section.virtual_size {
+            self.check_unreferenced(section.virtual_address + virtual_size .. RVA::new(u32::MAX), idx)?;
+        }
+
+        let file_alignment = self.optional_header.file_alignment();
+        let old = self.section_range(&section);
+        let start = match section.pointer_to_raw_data {
+            Some(ptr)   => ptr.get() as usize,
+            None        => align(self.end_of_sections().min(self.data.len()) as u64, file_alignment) as usize,
+        };
+        let size_of_raw_data = u32::try_from(align(virtual_size.into(), file_alignment)).map_err(|_| too_big())?;
+        let raw_size = size_of_raw_data as usize;
+        let old_size = old.end - old.start;
+        if section.pointer_to_raw_data.is_none() && raw_size != 0 {
+            let at = self.end_of_sections().min(self.data.len());
+            self.splice(at .. at, vec![0u8; start + raw_size - at]);
+        } else if raw_size > old_size {
+            self.splice(old.end .. old.end, vec![0u8; raw_size - old_size]);
+        } else if raw_size < old_size {
+            self.splice(start + raw_size .. old.end, Vec::new());
+        }
+
+        let section = &mut self.section_headers[idx];
+        section.virtual_size        = virtual_size;
+        section.size_of_raw_data    = size_of_raw_data;
+        section.pointer_to_raw_data = if raw_size == 0 { None } else { NonZeroU32::new(u32::try_from(start).map_err(|_| too_big())?) };
+        self.optional_header.update_sizes(&self.section_headers[..], self.optional_header.size_of_headers())
+    }
+
+    /// Serialize the image, patching the headers in place and recomputing the checksum.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the section table would overflow [`size_of_headers`](OptionalHeader32::size_of_headers)
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut data = self.data.clone();
+        let mut file_header = self.file_header;
+        file_header.nsections = u16::try_from(self.section_headers.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pe::Image: too many sections"))?;
+
+        let mut headers = Header { signature: cast(*b"PE\0\0"), file_header, optional_header: Some(self.optional_header) }.to_bytes();
+        for section in self.section_headers.iter() { headers.extend_from_slice(&section.to_bytes()[..]) }
+        let start = self.pe_header_start as usize;
+        let end = start + headers.len();
+        if end > (self.optional_header.size_of_headers() as usize).min(data.len()) {
+            return self.src.anno(Err(io::Error::new(io::ErrorKind::InvalidInput, "pe::SectionHeader table overflows pe::OptionalHeader::size_of_headers")), "error writing pe::Image");
+        }
+        data[start .. end].copy_from_slice(&headers[..]);
+
+        update_checksum(&mut data[..], self.pe_header_start);
+        Ok(data)
+    }
+
+    /// Serialize the image to `w`.  See [`to_bytes`](Self::to_bytes) for details.
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.to_bytes()?[..])
+    }
+
+    /// Serialize the image to `path`.  See [`to_bytes`](Self::to_bytes) for details.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let data = self.to_bytes()?;
+        fs::write(path.as_ref(), &data[..]).map_err(|err| io::Error::new(err.kind(), format!("{}: error writing pe::Image: {}", path.as_ref().display(), err)))
+    }
+}
+
+
+
+#[test] fn patch_headers() {
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let original = w.to_bytes().unwrap();
+
+    let mut image = Image::from_bytes(original.clone()).unwrap();
+    image.optional_header.set_subsystem(Subsystem::WINDOWS_GUI);
+    image.optional_header.set_dll_characteristics(image.optional_header.dll_characteristics() - DllCharacteristics::DYNAMIC_BASE);
+    image.optional_header.set_size_of_stack_reserve(0x40_0000).unwrap();
+    assert!(image.optional_header.set_size_of_heap_reserve(1 << 32).is_err());
+    image.file_header.characteristics |= FileCharacteristics::LARGE_ADDRESS_AWARE;
+    image.file_header.link_time_date = TimeDate::from_secs(0x12345678);
+    let patched = image.to_bytes().unwrap();
+    assert_eq!(patched.len(), original.len());
+
+    let image = Image::from_bytes(patched).unwrap();
+    assert_eq!(image.optional_header.subsystem(), Subsystem::WINDOWS_GUI);
+    assert!(!image.optional_header.dll_characteristics().contains(DllCharacteristics::DYNAMIC_BASE));
+    assert!(image.file_header.characteristics.contains(FileCharacteristics::LARGE_ADDRESS_AWARE));
+    assert_eq!(image.file_header.link_time_date, TimeDate::from_secs(0x12345678));
+    match image.optional_header {
+        OptionalHeader::OptionalHeader32(oh) => assert_eq!(oh.size_of_stack_reserve, 0x40_0000),
+        other => panic!("expected PE32, got {:?}", other),
+    }
+    let mut checksum = Checksum::new(u64::from(image.pe_header_start()) + 24 + OPTIONAL_HEADER_CHECKSUM_OFFSET);
+    checksum.update(image.original_bytes());
+    assert_eq!(checksum.finish(), image.optional_header.checksum());
+    assert_eq!(image.section_headers()[0].name.to_bytes(), b".text");
+}
+
+#[test] fn edit_sections() {
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    w.add_section(".data", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ | SectionCharacteristics::MEM_WRITE, vec![1; 0x300]).unwrap();
+    let rdata = w.add_section(".rdata", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![0; 0x40]).unwrap();
+    w.data_directory_mut().debug = DataDirectory { virtual_address: rdata, size: 28 };
+    let mut original = w.to_bytes().unwrap();
+    let signed_at = original.len() as u32;
+    original.extend_from_slice(b"overlay!");
+
+    let mut image = Image::from_bytes(original).unwrap();
+    image.data_directory_mut().security = DataDirectory { virtual_address: RVA::new(signed_at), size: 8 };
+    let rdata_offset = image.section_headers()[2].pointer_to_raw_data.unwrap().get();
+    let debug = DebugDirectory { debug_type: DebugType::REPRO, size_of_data: 4, address_of_raw_data: rdata + 28, pointer_to_raw_data: rdata_offset + 28, .. Default::default() };
+    image.section_data_mut(2).unwrap()[..28].copy_from_slice(&debug.to_bytes()[..]);
+    assert_eq!(image.overlay(), b"overlay!");
+
+    let sig = image.add_section(".sig", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, b"payload".to_vec()).unwrap();
+    assert_eq!(sig, RVA::new(0x4000));
+    assert_eq!(image.section_data(3).unwrap()[..7], b"payload"[..]);
+    assert_eq!(image.overlay(), b"overlay!");
+    assert_eq!(image.optional_header.size_of_headers(), 0x400); // grown to fit a fourth pe::SectionHeader
+    assert_eq!(image.data_directory().security.virtual_address, RVA::new(signed_at + 0x200 + 0x200));
+    assert_eq!(image.debug_directory().unwrap()[0].pointer_to_raw_data, rdata_offset + 0x200 + 28);
+    assert_eq!(image.optional_header.size_of_image(), 0x5000);
+
+    image.resize_last_section(0x1234).unwrap();
+    assert_eq!(image.section_data(3).unwrap().len(), 0x1400);
+    assert_eq!(image.overlay(), b"overlay!");
+    assert_eq!(image.optional_header.size_of_image(), 0x6000);
+
+    assert!(image.remove_section(0).is_err());
+    assert!(image.remove_section(2).is_err()); // referenced by pe::DataDirectories::debug
+    image.remove_section(1).unwrap();
+    assert_eq!(image.section_headers()[0].virtual_size, 0x2000);
+    assert_eq!(image.section_headers()[1].pointer_to_raw_data.unwrap().get(), rdata_offset + 0x200 - 0x400);
+    assert_eq!(image.debug_directory().unwrap()[0].pointer_to_raw_data, rdata_offset + 0x200 - 0x400 + 28);
+    assert_eq!(image.overlay(), b"overlay!");
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.section_headers().len(), 3);
+    assert_eq!(image.section_headers()[2].name.to_bytes(), b".sig");
+    assert_eq!(image.section_data(2).unwrap()[..7], b"payload"[..]);
+    assert_eq!(image.overlay(), b"overlay!");
+}
+
+#[test] fn add_section_virtual_size() {
+    let mut w = Writer::new_pe32_plus(Machine::AMD64);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let mut image = Image::from_bytes(w.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.add_section(".empty", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, Vec::new()).unwrap(), RVA::new(0x2000));
+    assert_eq!(image.add_section_with_virtual_size(".bss", SectionCharacteristics::CNT_UNINITIALIZED_DATA | SectionCharacteristics::MEM_READ | SectionCharacteristics::MEM_WRITE, Vec::new(), 0x1800).unwrap(), RVA::new(0x3000));
+    assert_eq!(image.add_section(".data", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 4]).unwrap(), RVA::new(0x5000));
+    assert_eq!(image.add_section_with_virtual_size(".zero", SectionCharacteristics::NONE, Vec::new(), 0).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.section_headers().iter().map(|s| s.virtual_size).collect::<Vec<_>>(), [1, 1, 0x1800, 4]);
+    assert_eq!(image.section_headers().iter().map(|s| s.size_of_raw_data).collect::<Vec<_>>(), [0x200, 0, 0, 0x200]);
+}
+
+#[test] fn add_section_bound_imports() {
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let mut original = w.to_bytes().unwrap();
+    let image = Image::from_bytes(original.clone()).unwrap();
+    let table_end = Image::section_headers_start(image.pe_header_start, &image.file_header) + 40;
+    original[table_end .. table_end + 16].copy_from_slice(&[0x78, 0x56, 0x34, 0x12, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // one descriptor + terminator
+    let mut image = Image::from_bytes(original).unwrap();
+    image.data_directory_mut().bound_imports = DataDirectory { virtual_address: RVA::new(table_end as u32), size: 16 };
+
+    // Something besides the bound import table is in the way: nothing should change
+    image.data[table_end + 16] = 0xFF;
+    let before = image.data.clone();
+    assert!(image.add_section(".new", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 4]).is_err());
+    assert_eq!(image.data_directory().bound_imports.size, 16);
+    assert_eq!(image.section_headers().len(), 1);
+    assert!(image.data == before);
+
+    image.data[table_end + 16] = 0;
+    image.add_section(".new", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 4]).unwrap();
+    assert_eq!(image.data_directory().bound_imports.size, 0);
+    assert!(image.data[table_end .. table_end + 16].iter().all(|b| *b == 0));
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.section_headers().len(), 2);
+    assert_eq!(image.section_data(1).unwrap()[..4], [1; 4]);
+}
+
+#[test] fn remove_section_overflowing_dd() {
+    let mut w = Writer::new_pe32_plus(Machine::AMD64);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    w.add_section(".data", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 4]).unwrap();
+    w.data_directory_mut().debug = DataDirectory { virtual_address: RVA::new(0xFFFF_F000), size: 0x2000 };
+    let mut image = Image::from_bytes(w.to_bytes().unwrap()).unwrap();
+    image.remove_section(1).unwrap();
+    assert_eq!(image.section_headers().len(), 1);
+}
+
+#[test] fn edit_resources() {
+    let mut resources = Resources::new();
+    resources.insert(Resource { type_id: ResourceId::MANIFEST, name: 1.into(), language: 0x0409, code_page: 0, data: b"<assembly/>".to_vec() });
+
+    let rsrc = resources.to_bytes(RVA::new(0x2000)).unwrap();
+    let mut w = Writer::new_pe32_plus(Machine::AMD64);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    w.data_directory_mut().resource = DataDirectory { virtual_address: RVA::new(0x2000), size: rsrc.len() as u32 };
+    assert_eq!(w.add_section(".rsrc", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, rsrc).unwrap(), RVA::new(0x2000));
+    let mut image = Image::from_bytes(w.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.resources().unwrap(), resources);
+
+    resources.set_string(1, 0x0409, "replaced in place");
+    image.set_resources(&resources).unwrap();
+    assert_eq!(image.section_headers().len(), 2);
+    assert_eq!(image.resources().unwrap(), resources);
+
+    // .reloc following .rsrc: fits in place
+    image.add_section(".reloc", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![0; 12]).unwrap();
+    resources.remove(&ResourceId::MANIFEST, &1.into(), 0x0409);
+    image.set_resources(&resources).unwrap();
+    assert_eq!(image.section_headers().len(), 3);
+    assert_eq!(image.data_directory().resource.virtual_address, RVA::new(0x2000));
+
+    // Outgrows the original .rsrc: moved to a new last section, and the original removed
+    resources.insert(Resource { type_id: ResourceId::RCDATA, name: 1.into(), language: 0, code_page: 0, data: vec![0xAA; 0x300] });
+    image.set_resources(&resources).unwrap();
+    assert_eq!(image.section_headers().iter().map(|s| s.name.to_bytes()).collect::<Vec<_>>(), [&b".text"[..], b".reloc", b".rsrc"]);
+    assert_eq!(image.data_directory().resource.virtual_address, RVA::new(0x4000));
+    assert_eq!(image.section_headers()[0].virtual_size, 0x2000); // still contiguous with .reloc
+
+    // Now the last section: resized
+    resources.insert(Resource { type_id: ResourceId::RCDATA, name: 2.into(), language: 0, code_page: 0, data: vec![0xBB; 0x300] });
+    image.set_resources(&resources).unwrap();
+    assert_eq!(image.section_headers().len(), 3);
+    assert_eq!(image.data_directory().resource.virtual_address, RVA::new(0x4000));
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    assert_eq!(image.resources().unwrap(), resources);
+    assert_eq!(image.resources().unwrap().string(1, 0x0409).as_deref(), Some("replaced in place"));
+}
+
+#[test] fn edit_resources_shared_section() {
+    let mut resources = Resources::new();
+    resources.insert(Resource { type_id: ResourceId::MANIFEST, name: 1.into(), language: 0x0409, code_page: 0, data: b"<assembly/>".to_vec() });
+
+    let mut rsrc = resources.to_bytes(RVA::new(0x2000)).unwrap();
+    let size = rsrc.len() as u32;
+    let shared = (size + 15) & !15;
+    rsrc.resize(shared as usize, 0);
+    rsrc.extend_from_slice(&[0xCC; 16]);
+    let mut w = Writer::new_pe32_plus(Machine::AMD64);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    w.data_directory_mut().resource  = DataDirectory { virtual_address: RVA::new(0x2000), size };
+    w.data_directory_mut().exception = DataDirectory { virtual_address: RVA::new(0x2000 + shared), size: 16 };
+    w.add_section(".rsrc", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, rsrc).unwrap();
+    w.add_section(".reloc", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![0; 12]).unwrap();
+    let mut image = Image::from_bytes(w.to_bytes().unwrap()).unwrap();
+
+    // Would fit in place, but zeroing the rest of .rsrc would clobber the exception directory: moved to a new section instead
+    resources.set_string(1, 0x0409, "x");
+    image.set_resources(&resources).unwrap();
+    assert_eq!(image.section_headers().len(), 4);
+    assert_eq!(image.data_directory().resource.virtual_address, RVA::new(0x4000));
+    assert_eq!(image.section_data(1).unwrap()[shared as usize ..][..16], [0xCC; 16]);
+    assert_eq!(image.resources().unwrap(), resources);
+}
+
+#[test] fn edit_imports() {
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let mut image = Image::from_bytes(w.to_bytes().unwrap()).unwrap();
+
+    let iat = image.add_import("KERNEL32.dll", &["ExitProcess".into(), "GetLastError".into()]).unwrap();
+    assert_eq!(iat.len(), 2);
+    assert_eq!(iat[1] - iat[0], 4);
+    let shim = image.add_import("shim.dll", &[ImportSymbol::Ordinal(5)]).unwrap();
+    assert_eq!(image.data_directory().iat, DataDirectory { virtual_address: iat[0], size: 12 }); // unchanged by the second import
+    assert_eq!(shim.len(), 1);
+
+    assert_eq!(image.rename_import("kernel32.DLL", "kernelbase.dll").unwrap(), 1);
+    assert_eq!(image.rename_import("shim.dll", "s.dll").unwrap(), 1);
+    assert_eq!(image.rename_import("missing.dll", "s.dll").unwrap_err().kind(), io::ErrorKind::NotFound);
+    assert_eq!(image.section_headers().len(), 4);
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    let descriptors = image.import_descriptors().unwrap();
+    assert_eq!(descriptors.len(), 2);
+    assert_eq!(image.read_strz_rva(descriptors[0].dll_ascii_name_rva), Some(&b"kernelbase.dll"[..]));
+    assert_eq!(image.read_strz_rva(descriptors[1].dll_ascii_name_rva), Some(&b"s.dll"[..]));
+    assert_eq!(descriptors[0].iat_rva, iat[0]);
+
+    let entry = |rva: RVA| ImportLookupTableEntry32::from_io(&mut &image.original_bytes()[image.rva_to_offset(rva).unwrap() ..]).unwrap();
+    let name = entry(descriptors[0].import_lookup_table_rva + 4).name_table_rva().unwrap();
+    assert_eq!(image.read_strz_rva(name + 2), Some(&b"GetLastError"[..]));
+    assert!(entry(descriptors[0].import_lookup_table_rva + 8).is_eot());
+    assert_eq!(entry(descriptors[1].iat_rva).ordinal(), Some(5));
+}
+
+#[test] fn strip() {
+    let key = 0x1234_5678u32;
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.dos_stub.resize(64, 0);
+    for dword in [u32::from_le_bytes(*b"DanS") ^ key, key, key, key, 0x0105_75CF ^ key, 3 ^ key, u32::from_le_bytes(*b"Rich"), key].iter() {
+        w.dos_stub.extend_from_slice(&dword.to_le_bytes());
+    }
+    w.file_header.link_time_date = TimeDate::from_secs(0x5000_0000);
+    w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3]).unwrap();
+    let rdata = w.add_section(".rdata", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![0; 0x40]).unwrap();
+    w.data_directory_mut().debug = DataDirectory { virtual_address: rdata, size: 2 * 28 };
+    let mut original = w.to_bytes().unwrap();
+    let end_of_sections = original.len() as u32;
+    original.extend_from_slice(b"COFFDBG!");       // unmapped debug data
+    original.extend_from_slice(&[0xFF; 16]);        // certificate table
+    original.extend_from_slice(b"overlay!");
+
+    let mut image = Image::from_bytes(original).unwrap();
+    assert!(RichHeader::find_in(&image.original_bytes()[.. image.pe_header_start() as usize]).unwrap().is_some());
+    image.data_directory_mut().security = DataDirectory { virtual_address: RVA::new(end_of_sections + 8), size: 16 };
+    let rdata_offset = image.section_headers()[1].pointer_to_raw_data.unwrap().get();
+    let codeview = DebugDirectory { time_date_stamp: TimeDate::from_secs(1), debug_type: DebugType::CODEVIEW, size_of_data: 8, address_of_raw_data: rdata + 56, pointer_to_raw_data: rdata_offset + 56, .. Default::default() };
+    let coff     = DebugDirectory { time_date_stamp: TimeDate::from_secs(1), debug_type: DebugType::COFF,     size_of_data: 8, pointer_to_raw_data: end_of_sections, .. Default::default() };
+    let data = image.section_data_mut(1).unwrap();
+    data[..28].copy_from_slice(&codeview.to_bytes()[..]);
+    data[28..56].copy_from_slice(&coff.to_bytes()[..]);
+    data[56..64].copy_from_slice(b"RSDS.pdb");
+
+    let mut timestamps_only = image.clone();
+    let mut resources = Resources::new();
+    resources.insert(Resource { type_id: ResourceId::MANIFEST, name: 1.into(), language: 0x0409, code_page: 0, data: b"<assembly/>".to_vec() });
+    resources.insert(Resource { type_id: ResourceId::RCDATA, name: "BLOB".into(), language: 0, code_page: 0, data: vec![4; 9] });
+    timestamps_only.set_resources(&resources).unwrap();
+    let rsrc = timestamps_only.section_headers().len() - 1;
+    let directories = Resources::directory_offsets(timestamps_only.section_data(rsrc).unwrap()).unwrap();
+    assert_eq!(directories.len(), 5); // root, 2 types, 2 names
+    for offset in directories.iter().copied() { timestamps_only.section_data_mut(rsrc).unwrap()[offset + 4 .. offset + 8].copy_from_slice(&0x5000_0000u32.to_le_bytes()) }
+    timestamps_only.strip(&StripOptions { timestamps: true, .. StripOptions::NONE }).unwrap();
+    assert_eq!(timestamps_only.file_header.link_time_date, TimeDate::UNIX_EPOCH);
+    assert!(timestamps_only.debug_directory().unwrap().iter().all(|d| d.time_date_stamp == TimeDate::UNIX_EPOCH));
+    assert!(directories.iter().all(|offset| timestamps_only.section_data(rsrc).unwrap()[offset + 4 .. offset + 8] == [0; 4]));
+    assert_eq!(timestamps_only.resources().unwrap(), resources);
+    assert_eq!(timestamps_only.overlay().len(), 32);
+
+    image.strip(&StripOptions::default()).unwrap();
+    assert_eq!(image.data_directory().debug, DataDirectory::EMPTY);
+    assert_eq!(image.data_directory().security, DataDirectory::EMPTY);
+    assert!(image.section_data(1).unwrap().iter().all(|b| *b == 0));
+    assert_eq!(image.overlay(), b"");
+    assert_eq!(image.original_bytes().len(), end_of_sections as usize);
+    assert_eq!(image.file_header.link_time_date, TimeDate::from_secs(0x5000_0000));
+
+    let image = Image::from_bytes(image.to_bytes().unwrap()).unwrap();
+    assert!(RichHeader::find_in(&image.original_bytes()[.. image.pe_header_start() as usize]).unwrap().is_none());
+    let mut checksum = Checksum::new(u64::from(image.pe_header_start()) + 24 + OPTIONAL_HEADER_CHECKSUM_OFFSET);
+    checksum.update(image.original_bytes());
+    assert_eq!(checksum.finish(), image.optional_header.checksum());
+}
@@ -48,15 +48,26 @@ impl FromMemory for ImportLookupTableEntry32 {
     type Raw    = u32le;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Self(raw)) }
+    fn to_raw(&self) -> Self::Raw { self.0 }
 }
 
 impl FromMemory for ImportLookupTableEntry64 {
     type Raw    = u64le;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Self(raw)) }
+    fn to_raw(&self) -> Self::Raw { self.0 }
 }
 
 impl ImportLookupTableEntry32 {
+    /// The null entry terminating the table
+    pub const EOT : ImportLookupTableEntry32 = ImportLookupTableEntry32(u32le::new(0));
+
+    /// Import by ordinal
+    pub fn from_ordinal(ordinal: u16) -> Self { Self(u32le::new(1 << 31 | u32::from(ordinal))) }
+
+    /// Import by name, given the [`RVA`] of a hint/name table entry (`u16` hint, then a `b'\0'`-terminated name)
+    pub fn from_name_table_rva(rva: RVA) -> Self { Self(u32le::new(rva.to_u32() & 0x7FFF_FFFF)) }
+
     fn import_by_ordinal(&self) -> bool { (self.0.to_le() & (1 << 31)) != 0 }
     fn import_by_name   (&self) -> bool { !self.is_eot() && (self.0.to_le() & (1 << 31)) == 0 }
 }
@@ -82,6 +93,15 @@ impl ImportLookupTableEntry for ImportLookupTableEntry32 {
 }
 
 impl ImportLookupTableEntry64 {
+    /// The null entry terminating the table
+    pub const EOT : ImportLookupTableEntry64 = ImportLookupTableEntry64(u64le::new(0));
+
+    /// Import by ordinal
+    pub fn from_ordinal(ordinal: u16) -> Self { Self(u64le::new(1 << 63 | u64::from(ordinal))) }
+
+    /// Import by name, given the [`RVA`] of a hint/name table entry (`u16` hint, then a `b'\0'`-terminated name)
+    pub fn from_name_table_rva(rva: RVA) -> Self { Self(u64le::new(u64::from(rva.to_u32() & 0x7FFF_FFFF))) }
+
     fn import_by_ordinal(&self) -> bool { (self.0.to_le() & (1 << 63)) != 0 }
     fn import_by_name   (&self) -> bool { !self.is_eot() && (self.0.to_le() & (1 << 31)) == 0 }
 }
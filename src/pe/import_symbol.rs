@@ -0,0 +1,31 @@
+use std::fmt::{self, Display, Formatter};
+
+
+
+/// ∑ [Name](Self::Name) | [Ordinal](Self::Ordinal)<br>
+/// A symbol to import from a DLL, for [`pe::Image::add_import`](crate::pe::Image::add_import)<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImportSymbol {
+    /// Import by name (typical), e.g. `{ hint: 0, name: "XInputGetState" }`
+    Name {
+        /// Index into the DLL's export name table to try first, or `0` if unknown
+        hint:   u16,
+        name:   String,
+    },
+    /// Import by ordinal
+    Ordinal(u16),
+}
+
+impl From<&str> for ImportSymbol {
+    fn from(name: &str) -> Self { ImportSymbol::Name { hint: 0, name: name.into() } }
+}
+
+impl Display for ImportSymbol {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            ImportSymbol::Name { name, .. } => write!(fmt, "{}", name),
+            ImportSymbol::Ordinal(ordinal)  => write!(fmt, "#{}", ordinal),
+        }
+    }
+}
@@ -114,4 +114,5 @@ impl FromMemory for Machine {
     type Raw    = Self;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
 }
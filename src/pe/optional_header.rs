@@ -1,5 +1,8 @@
 use super::*;
 
+use std::convert::*;
+use std::io;
+
 
 
 /// ∑ [OptionalHeader32] | [OptionalHeader64]
@@ -26,12 +29,150 @@ impl OptionalHeader {
         }
     }
 
+    pub fn checksum(&self) -> u32 {
+        match self {
+            Self::OptionalHeader32(oh) => oh.checksum,
+            Self::OptionalHeader64(oh) => oh.checksum,
+        }
+    }
+
     pub fn data_directory(&self) -> &DataDirectories {
         match self {
             Self::OptionalHeader32(oh) => &oh.data_directory,
             Self::OptionalHeader64(oh) => &oh.data_directory,
         }
     }
+
+    pub fn data_directory_mut(&mut self) -> &mut DataDirectories {
+        match self {
+            Self::OptionalHeader32(oh) => &mut oh.data_directory,
+            Self::OptionalHeader64(oh) => &mut oh.data_directory,
+        }
+    }
+
+    pub fn section_alignment(&self) -> u32 {
+        match self {
+            Self::OptionalHeader32(oh) => oh.section_alignment,
+            Self::OptionalHeader64(oh) => oh.section_alignment,
+        }
+    }
+
+    pub fn file_alignment(&self) -> u32 {
+        match self {
+            Self::OptionalHeader32(oh) => oh.file_alignment,
+            Self::OptionalHeader64(oh) => oh.file_alignment,
+        }
+    }
+
+    pub fn size_of_image(&self) -> u32 {
+        match self {
+            Self::OptionalHeader32(oh) => oh.size_of_image,
+            Self::OptionalHeader64(oh) => oh.size_of_image,
+        }
+    }
+
+    pub fn size_of_headers(&self) -> u32 {
+        match self {
+            Self::OptionalHeader32(oh) => oh.size_of_headers,
+            Self::OptionalHeader64(oh) => oh.size_of_headers,
+        }
+    }
+}
+
+impl OptionalHeader {
+    pub fn subsystem(&self) -> Subsystem {
+        match self {
+            Self::OptionalHeader32(oh) => oh.subsystem,
+            Self::OptionalHeader64(oh) => oh.subsystem,
+        }
+    }
+
+    pub fn set_subsystem(&mut self, subsystem: Subsystem) {
+        match self {
+            Self::OptionalHeader32(oh) => oh.subsystem = subsystem,
+            Self::OptionalHeader64(oh) => oh.subsystem = subsystem,
+        }
+    }
+
+    pub fn set_dll_characteristics(&mut self, dll_characteristics: DllCharacteristics) {
+        match self {
+            Self::OptionalHeader32(oh) => oh.dll_characteristics = dll_characteristics,
+            Self::OptionalHeader64(oh) => oh.dll_characteristics = dll_characteristics,
+        }
+    }
+
+    pub fn set_subsystem_version(&mut self, version: MajorMinorVersion<u16>) {
+        match self {
+            Self::OptionalHeader32(oh) => oh.subsystem_version = version,
+            Self::OptionalHeader64(oh) => oh.subsystem_version = version,
+        }
+    }
+
+    pub fn set_operating_system_version(&mut self, version: MajorMinorVersion<u16>) {
+        match self {
+            Self::OptionalHeader32(oh) => oh.operating_system_version = version,
+            Self::OptionalHeader64(oh) => oh.operating_system_version = version,
+        }
+    }
+
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `reserve` exceeds [`u32::MAX`] for an [`OptionalHeader32`]
+    pub fn set_size_of_stack_reserve(&mut self, reserve: u64) -> io::Result<()> {
+        match self {
+            Self::OptionalHeader32(oh) => oh.size_of_stack_reserve = narrow(reserve, "size_of_stack_reserve")?,
+            Self::OptionalHeader64(oh) => oh.size_of_stack_reserve = reserve,
+        }
+        Ok(())
+    }
+
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `reserve` exceeds [`u32::MAX`] for an [`OptionalHeader32`]
+    pub fn set_size_of_heap_reserve(&mut self, reserve: u64) -> io::Result<()> {
+        match self {
+            Self::OptionalHeader32(oh) => oh.size_of_heap_reserve = narrow(reserve, "size_of_heap_reserve")?,
+            Self::OptionalHeader64(oh) => oh.size_of_heap_reserve = reserve,
+        }
+        Ok(())
+    }
+}
+
+impl OptionalHeader {
+    /// Recompute `size_of_code`, `size_of_initialized_data`, `size_of_uninitialized_data`, and `size_of_image` from `sections`, and set `size_of_headers`.
+    pub(crate) fn update_sizes(&mut self, sections: &[SectionHeader], size_of_headers: u32) -> io::Result<()> {
+        let file_alignment      = self.file_alignment();
+        let section_alignment   = self.section_alignment();
+        let (mut code, mut initialized, mut uninitialized) = (0u64, 0u64, 0u64);
+        let mut size_of_image = align(size_of_headers.into(), section_alignment);
+        for section in sections {
+            let ch = section.characteristics;
+            if ch.contains(SectionCharacteristics::CNT_CODE)                { code          += u64::from(section.size_of_raw_data) }
+            if ch.contains(SectionCharacteristics::CNT_INITIALIZED_DATA)    { initialized   += u64::from(section.size_of_raw_data) }
+            if ch.contains(SectionCharacteristics::CNT_UNINITIALIZED_DATA)  { uninitialized += align(section.virtual_size.into(), file_alignment) }
+            size_of_image = size_of_image.max(align(u64::from(section.virtual_address) + u64::from(section.virtual_size), section_alignment));
+        }
+        let (code, initialized, uninitialized, size_of_image) = (narrow(code, "size_of_code")?, narrow(initialized, "size_of_initialized_data")?, narrow(uninitialized, "size_of_uninitialized_data")?, narrow(size_of_image, "size_of_image")?);
+        match self {
+            Self::OptionalHeader32(oh) => {
+                oh.size_of_code                 = code;
+                oh.size_of_initialized_data     = initialized;
+                oh.size_of_uninitialized_data   = uninitialized;
+                oh.size_of_image                = size_of_image;
+                oh.size_of_headers              = size_of_headers;
+            },
+            Self::OptionalHeader64(oh) => {
+                oh.size_of_code                 = code;
+                oh.size_of_initialized_data     = initialized;
+                oh.size_of_uninitialized_data   = uninitialized;
+                oh.size_of_image                = size_of_image;
+                oh.size_of_headers              = size_of_headers;
+            },
+        }
+        Ok(())
+    }
+}
+
+fn narrow(value: u64, field: &str) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("pe::OptionalHeader32::{} cannot exceed u32::MAX", field)))
 }
 
 impl From<OptionalHeader32> for OptionalHeader {
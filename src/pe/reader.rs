@@ -5,7 +5,6 @@ use super::*;
 use maulingmonkey_io_adapters::{ReadAt, ReadAtCursor, ReadAtRef, SeeklessFile};
 
 use std::convert::*;
-use std::fmt::{self, *};
 use std::fs::File;
 use std::ops::*;
 use std::path::PathBuf;
@@ -98,9 +97,17 @@ impl<R: ReadAt> Reader<R> {
     /// Read data from an [`RVA`] range from one or more [`pe::SectionHeader`].
     ///
     /// ### Errors
-    /// *   [`io::ErrorKind::InvalidInput`] if `rva` isn't entirely mapped by sections
+    /// *   [`io::ErrorKind::InvalidInput`] if `rva` isn't entirely mapped by sections, or ends before it starts
     /// *   ...?
     pub fn read_exact_rva<'a>(&'_ self, rva: Range<RVA>, scratch: &'a mut Vec<u8>) -> io::Result<&'a [u8]> {
+        if rva.end < rva.start { return Err(io::Error::new(io::ErrorKind::InvalidInput, "RVA range ends before it starts")) }
+
+        // `rva` is often derived from untrusted sizes: check it's all mapped before allocating
+        let mut at = rva.start.to_u64();
+        while at < rva.end.to_u64() {
+            let mapped = self.pe_section_headers.iter().map(|s| s.virtual_address.to_u64() .. s.virtual_address.to_u64() + u64::from(s.virtual_size)).find(|s| s.contains(&at));
+            at = mapped.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not all RVA mapped"))?.end;
+        }
         scratch.resize(rva.end.to_usize() - rva.start.to_usize(), 0u8);
 
         let mut rva = rva.start;
@@ -146,7 +153,334 @@ impl<R: ReadAt> Reader<R> {
         Ok(s)
     }
 
-    fn read_src(reader: R, src: Src) -> io::Result<Self> {
+    /// Read the attribute certificate table referenced by [`pe::DataDirectories::security`].
+    ///
+    /// Returns an empty [`Vec`] if the image is unsigned.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if a [`pe::WinCertificate::length`] is too small or overruns the table
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_certificate_table(&self) -> io::Result<Vec<(pe::WinCertificate, Vec<u8>)>> {
+        let dd = self.data_directory().security;
+        let mut entries = Vec::new();
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(entries) }
+
+        let header_size = std::mem::size_of::<<pe::WinCertificate as FromMemory>::Raw>() as u32;
+        let start = u64::from(dd.virtual_address.to_u32()); // a file offset, not an RVA
+        let end = start + u64::from(dd.size);
+        let mut offset = start;
+        while offset + u64::from(header_size) <= end {
+            let header = self.src.anno(pe::WinCertificate::from_read_at(&self.reader, offset), "error reading pe::WinCertificate")?;
+            if header.length < header_size || offset + u64::from(header.length) > end {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: pe::WinCertificate::length ({}) out of bounds", self.src, header.length)));
+            }
+            let mut data = vec![0u8; (header.length - header_size) as usize];
+            self.src.anno(self.reader.read_exact_at(&mut data, offset + u64::from(header_size)), "error reading pe::WinCertificate data")?;
+            entries.push((header, data));
+            offset += (u64::from(header.length) + 7) & !7; // entries are 8-byte aligned
+        }
+        Ok(entries)
+    }
+
+    /// Read and decode all [`authenticode::Signature`]s from the attribute certificate table.
+    ///
+    /// Only [`pe::CertificateType::PKCS_SIGNED_DATA`] entries are decoded, others are skipped.
+    /// Nested signatures are found in [`authenticode::Signature::nested`], not as separate entries.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the certificate table or a PKCS#7 blob is malformed
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn authenticode_signatures(&self) -> io::Result<Vec<authenticode::Signature>> {
+        let mut signatures = Vec::new();
+        for (header, data) in self.read_certificate_table()? {
+            if header.certificate_type != pe::CertificateType::PKCS_SIGNED_DATA { continue }
+            signatures.push(self.src.anno(authenticode::Signature::from_der(&data), "error decoding authenticode::Signature")?);
+        }
+        Ok(signatures)
+    }
+
+    /// Compute the image checksum as [`CheckSumMappedFile`](https://learn.microsoft.com/en-us/windows/win32/api/imagehlp/nf-imagehlp-checksummappedfile) would,
+    /// for comparison against [`pe::OptionalHeader::checksum`].
+    ///
+    /// This reads the entire file, including any overlay and the certificate table.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the image has no [`pe::OptionalHeader`] (and thus no checksum field)
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_at`]
+    pub fn compute_checksum(&self) -> io::Result<u32> {
+        if self.pe_header.optional_header.is_none() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: pe::Header::optional_header is None, no checksum to compute", self.src)));
+        }
+
+        let optional_header_start = u64::from(self.mz_header.pe_header_start) + 4 + std::mem::size_of::<<pe::FileHeader as FromMemory>::Raw>() as u64;
+        let mut checksum = pe::Checksum::new(optional_header_start + pe::OPTIONAL_HEADER_CHECKSUM_OFFSET);
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut offset = 0;
+        loop {
+            match self.reader.read_at(&mut buf[..], offset) {
+                Ok(0) => break,
+                Ok(n) => {
+                    checksum.update(&buf[..n]);
+                    offset += n as u64;
+                },
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return self.src.anno(Err(err), "error reading image for checksum"),
+            }
+        }
+        Ok(checksum.finish())
+    }
+
+    /// Verify [`pe::OptionalHeader::checksum`] against [`compute_checksum`](Self::compute_checksum).
+    ///
+    /// The loader only enforces checksums for drivers, boot-time DLLs, and DLLs loaded into critical system processes - but for those, a mismatch is fatal.
+    /// A stored checksum of `0` ("not set") is treated as a mismatch.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the checksums don't match
+    /// *   [`io::ErrorKind::InvalidData`] if the image has no [`pe::OptionalHeader`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_at`]
+    pub fn verify_checksum(&self) -> io::Result<()> {
+        let computed = self.compute_checksum()?;
+        let stored = self.pe_header.optional_header.as_ref().map_or(0, |oh| oh.checksum());
+        if stored != computed {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}: pe::OptionalHeader::checksum (0x{:08x}) != computed checksum (0x{:08x})", self.src, stored, computed)));
+        }
+        Ok(())
+    }
+
+    /// Read the [`mz::Stub`] MS-DOS program preceeding the [`pe::Header`].
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_dos_stub(&self) -> io::Result<mz::Stub> {
+        self.src.anno(mz::Stub::read_from(&self.reader, &self.mz_header), "error reading mz::Stub")
+    }
+
+    /// Verify [`mz::Header::checksum`].  See [`mz::Header::verify_checksum`] for caveats - most linkers don't bother setting it.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the checksum doesn't match
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn verify_mz_checksum(&self) -> io::Result<()> {
+        self.src.anno(self.mz_header.verify_checksum(&self.reader), "error verifying mz::Header::checksum")
+    }
+
+    /// Read and decode the export tables referenced by [`pe::DataDirectories::export`].
+    ///
+    /// Returns [`None`] if the image exports nothing (typical of executables.)
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the export tables aren't mapped by sections
+    /// *   [`io::ErrorKind::InvalidData`] if a name ordinal is out of bounds, an ordinal exceeds [`u16::MAX`], or a table extends past [`u32::MAX`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_exports(&self) -> io::Result<Option<pe::Exports>> {
+        let dd = self.data_directory().export;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(None) }
+        let forwarders = self.src.anno(rva_range(dd.virtual_address, dd.size), "error reading pe::DataDirectories::export")?;
+
+        let mut scratch = Vec::new();
+        let directory = self.src.anno(pe::ExportDirectory::from_io(&mut RvaReader::new(self, dd.virtual_address)), "error reading pe::ExportDirectory")?;
+        let dll_name = String::from_utf8_lossy(self.src.anno(self.read_strz_rva(directory.name_rva, &mut scratch), "error reading pe::ExportDirectory::name_rva")?).into_owned();
+
+        let table = |rva: RVA, n: u32, size: u32, scratch: &mut Vec<u8>| -> io::Result<Vec<u32>> {
+            let range = n.checked_mul(size).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "export table size exceeds u32::MAX")).and_then(|bytes| rva_range(rva, bytes));
+            let data = self.src.anno(range.and_then(|range| self.read_exact_rva(range, scratch)), "error reading pe::ExportDirectory tables")?;
+            Ok(data.chunks_exact(size as usize).map(|c| if size == 2 { u32::from(u16::from_le_bytes([c[0], c[1]])) } else { u32::from_le_bytes([c[0], c[1], c[2], c[3]]) }).collect())
+        };
+        let functions       = table(directory.functions_rva,        directory.nfunctions,   4, &mut scratch)?;
+        let names           = table(directory.names_rva,            directory.nnames,       4, &mut scratch)?;
+        let name_ordinals   = table(directory.name_ordinals_rva,    directory.nnames,       2, &mut scratch)?;
+
+        let mut named = vec![false; functions.len()];
+        let mut exports = Vec::new();
+        let mut push = |index: u32, name: Option<String>, hint: u16, scratch: &mut Vec<u8>| -> io::Result<()> {
+            let function = RVA::new(*functions.get(index as usize).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("export name ordinal {} out of bounds", index)))?);
+            let ordinal = directory.ordinal_base.checked_add(index).and_then(|o| u16::try_from(o).ok()).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "export ordinal exceeds u16::MAX"))?;
+            let target = if forwarders.contains(&function) {
+                ExportTarget::Forwarder(String::from_utf8_lossy(self.read_strz_rva(function, scratch)?).into_owned())
+            } else {
+                ExportTarget::Rva(function)
+            };
+            exports.push(pe::Export { ordinal, name, hint, target });
+            Ok(())
+        };
+        for (hint, (name, index)) in names.iter().zip(name_ordinals.iter()).enumerate() {
+            let name = String::from_utf8_lossy(self.src.anno(self.read_strz_rva(RVA::new(*name), &mut scratch), "error reading export name")?).into_owned();
+            if let Some(named) = named.get_mut(*index as usize) { *named = true }
+            self.src.anno(push(*index, Some(name), hint as u16, &mut scratch), "error reading pe::Export")?;
+        }
+        for index in 0 .. functions.len() {
+            if named[index] || functions[index] == 0 { continue } // unused ordinal
+            self.src.anno(push(index as u32, None, 0, &mut scratch), "error reading pe::Export")?;
+        }
+        exports.sort_by_key(|e| e.ordinal);
+
+        Ok(Some(pe::Exports { directory, dll_name, exports }))
+    }
+
+    /// Read the [`clr::Header`] referenced by [`pe::DataDirectories::com_descriptor`].
+    ///
+    /// Returns [`None`] for native images.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the header isn't mapped by sections
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_clr_header(&self) -> io::Result<Option<clr::Header>> {
+        let dd = self.data_directory().com_descriptor;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(None) }
+        let header = self.src.anno(clr::Header::from_io(&mut RvaReader::new(self, dd.virtual_address)), "error reading clr::Header")?;
+        Ok(Some(header))
+    }
+
+    /// Determine if this image is native, managed, or mixed-mode, based on the [`clr::Header`].
+    pub fn clr_image_kind(&self) -> io::Result<clr::ImageKind> {
+        Ok(self.read_clr_header()?.map_or(clr::ImageKind::Native, |h| h.image_kind()))
+    }
+
+    /// Read the [`clr::Metadata`] referenced by [`clr::Header::metadata`], and parse its [`clr::MetadataRoot`].
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the metadata isn't mapped by sections
+    /// *   [`io::ErrorKind::InvalidData`] if the metadata root signature isn't `BSJB`, or the metadata extends past [`u32::MAX`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_clr_metadata(&self, header: &clr::Header) -> io::Result<clr::Metadata> {
+        let dd = header.metadata;
+        let mut data = Vec::new();
+        self.src.anno(rva_range(dd.virtual_address, dd.size).and_then(|range| self.read_exact_rva(range, &mut data)), "error reading clr::Metadata")?;
+        self.src.anno(clr::Metadata::parse(data), "error parsing clr::MetadataRoot")
+    }
+
+    /// Read the [`clr::Header::resources`] directory, and iterate the [`clr::EmbeddedResource`]s described by `tables`' [`clr::ManifestResource`]s.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the resources directory isn't mapped by sections
+    /// *   [`io::ErrorKind::InvalidData`] if the resources directory extends past [`u32::MAX`]
+    /// *   [`io::ErrorKind::InvalidData`] (per item) if a resource extends beyond the resources directory
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_clr_resources<'t>(&self, header: &clr::Header, tables: &'t clr::Tables) -> io::Result<clr::EmbeddedResources<'t>> {
+        let dd = header.resources;
+        let mut data = Vec::new();
+        if dd.virtual_address != RVA::NULL && dd.size != 0 {
+            self.src.anno(rva_range(dd.virtual_address, dd.size).and_then(|range| self.read_exact_rva(range, &mut data)), "error reading clr::Header::resources")?;
+        }
+        Ok(clr::EmbeddedResources::new(data, tables))
+    }
+
+    /// Read the [`clr::ReadyToRunHeader`] referenced by [`clr::Header::managed_native_header`], and its section table.
+    ///
+    /// Returns [`None`] if the image isn't precompiled, or was precompiled by the obsolete NGEN (no `RTR` signature.)
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_ready_to_run(&self, header: &clr::Header) -> io::Result<Option<clr::ReadyToRun>> {
+        let dd = header.managed_native_header;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(None) }
+        let mut r = RvaReader::new(self, dd.virtual_address);
+        let header = self.src.anno(clr::ReadyToRunHeader::from_io(&mut r), "error reading clr::ReadyToRunHeader")?;
+        if header.signature != clr::ReadyToRunHeader::SIGNATURE { return Ok(None) }
+        let sections = (0 .. header.nsections).map(|_| clr::ReadyToRunSection::from_io(&mut r)).collect::<io::Result<Vec<_>>>();
+        let sections = self.src.anno(sections, "error reading clr::ReadyToRunSection")?;
+        Ok(Some(clr::ReadyToRun { header, sections }))
+    }
+
+    /// Read the [`COMPILER_IDENTIFIER`](clr::ReadyToRunSectionType::COMPILER_IDENTIFIER) section, e.g. `"Crossgen2 8.0.0"`
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the section extends past [`u32::MAX`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_ready_to_run_compiler_identifier(&self, r2r: &clr::ReadyToRun) -> io::Result<Option<String>> {
+        let dd = match r2r.section(clr::ReadyToRunSectionType::COMPILER_IDENTIFIER) { Some(dd) => dd, None => return Ok(None) };
+        let range = self.src.anno(rva_range(dd.virtual_address, dd.size), "error reading clr::ReadyToRunSectionType::COMPILER_IDENTIFIER")?;
+        let mut scratch = Vec::new();
+        let id = self.src.anno(self.read_exact_rva(range, &mut scratch), "error reading clr::ReadyToRunSectionType::COMPILER_IDENTIFIER")?;
+        Ok(Some(String::from_utf8_lossy(&id[..id.iter().position(|b| *b == 0).unwrap_or(id.len())]).into_owned()))
+    }
+
+    /// Read the [`IMPORT_SECTIONS`](clr::ReadyToRunSectionType::IMPORT_SECTIONS) section
+    ///
+    /// ### Errors
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_ready_to_run_import_sections(&self, r2r: &clr::ReadyToRun) -> io::Result<Vec<clr::ReadyToRunImportSection>> {
+        let dd = match r2r.section(clr::ReadyToRunSectionType::IMPORT_SECTIONS) { Some(dd) => dd, None => return Ok(Vec::new()) };
+        let mut r = RvaReader::new(self, dd.virtual_address);
+        let n = dd.size as usize / std::mem::size_of::<<clr::ReadyToRunImportSection as FromMemory>::Raw>();
+        let sections = (0 .. n).map(|_| clr::ReadyToRunImportSection::from_io(&mut r)).collect();
+        self.src.anno(sections, "error reading clr::ReadyToRunImportSection")
+    }
+
+    /// Read the [`pe::DebugDirectory`] entries referenced by [`pe::DataDirectories::debug`].
+    ///
+    /// Returns an empty [`Vec`] if the image has no debug directory.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the debug directory isn't entirely mapped by sections
+    /// *   [`io::ErrorKind::InvalidData`] if the debug directory extends past [`u32::MAX`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_debug_directory(&self) -> io::Result<Vec<pe::DebugDirectory>> {
+        let dd = self.data_directory().debug;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(Vec::new()) }
+        let n = dd.size as usize / std::mem::size_of::<<pe::DebugDirectory as FromMemory>::Raw>();
+        let range = self.src.anno(rva_range(dd.virtual_address, dd.size), "error reading pe::DebugDirectory")?;
+        let mut scratch = Vec::new();
+        let mut r = self.src.anno(self.read_exact_rva(range, &mut scratch), "error reading pe::DebugDirectory")?;
+        let entries = (0 .. n).map(|_| pe::DebugDirectory::from_io(&mut r)).collect();
+        self.src.anno(entries, "error reading pe::DebugDirectory")
+    }
+
+    /// Read and parse the resource tree referenced by [`pe::DataDirectories::resource`].
+    ///
+    /// Returns an empty [`pe::Resources`] if the image has no resources.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the resource directory or data isn't entirely mapped by sections
+    /// *   [`io::ErrorKind::InvalidData`] / [`io::ErrorKind::UnexpectedEof`] if the resource tree is malformed, or the directory or data extends past [`u32::MAX`]
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_resources(&self) -> io::Result<pe::Resources> {
+        let dd = self.data_directory().resource;
+        if dd.virtual_address == RVA::NULL || dd.size == 0 { return Ok(pe::Resources::default()) }
+        let mut dir = Vec::new();
+        self.src.anno(rva_range(dd.virtual_address, dd.size).and_then(|range| self.read_exact_rva(range, &mut dir)), "error reading pe::DataDirectories::resource")?;
+        let resources = pe::Resources::parse(&dir[..], |rva, size| {
+            let mut data = Vec::new();
+            self.read_exact_rva(rva_range(rva, size)?, &mut data)?;
+            Ok(data)
+        });
+        self.src.anno(resources, "error reading pe::Resources")
+    }
+
+    /// Find and decode MSVC's undocumented [`pe::RichHeader`], which trails the [`mz::Stub`].
+    ///
+    /// Returns [`None`] if the image has no Rich header (non-Microsoft linker, or stripped.)
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if a `"Rich"` marker was found without a matching `"DanS"` marker
+    /// *   [`io::Error`] forwarded from [`ReadAtExt::read_exact_vec_at`]
+    pub fn rich_header(&self) -> io::Result<Option<pe::RichHeader>> {
+        let image_prefix = self.src.anno(self.reader.read_exact_vec_at(self.mz_header.pe_header_start as usize, 0), "error reading pe::RichHeader")?;
+        self.src.anno(pe::RichHeader::find_in(&image_prefix[..]), "error decoding pe::RichHeader")
+    }
+
+    /// Read the COFF [`coff::SymbolTable`], if any.  Images linked by MSVC have none, but MinGW-built images often keep theirs.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::UnexpectedEof`] if the symbol table or auxiliary records were truncated
+    /// *   [`io::ErrorKind::InvalidData`] if a symbol name referenced outside the string table
+    /// *   [`io::Error`] forwarded from [`ReadAt::read_exact_at`]
+    pub fn read_symbol_table(&self) -> io::Result<coff::SymbolTable> {
+        let fh = &self.pe_header.file_header;
+        if fh.symbols == 0 { return Ok(coff::SymbolTable::default()) }
+        self.src.anno(coff::SymbolTable::read_from(&self.reader, fh.symbols.into(), fh.nsymbols, coff::Symbol::SIZE), "error reading coff::SymbolTable")
+    }
+
+    /// Read the COFF [`coff::StringTable`], if any, to resolve `/123`-style long section names with [`coff::StringTable::section_name`].
+    pub fn read_string_table(&self) -> io::Result<coff::StringTable> {
+        let fh = &self.pe_header.file_header;
+        if fh.symbols == 0 { return Ok(coff::StringTable::default()) }
+        let offset = u64::from(fh.symbols) + u64::from(fh.nsymbols) * coff::Symbol::SIZE as u64;
+        self.src.anno(coff::StringTable::read_from(&self.reader, offset), "error reading coff::StringTable")
+    }
+
+    pub(crate) fn read_src(reader: R, src: Src) -> io::Result<Self> {
         let mz_header = src.anno(mz::Header::from_read_at(&reader, 0), "error reading mz::Header")?;
 
         let mut pe_reader = ReadAtCursor::new(ReadAtRef(&reader), u64::from(mz_header.pe_header_start), !0);
@@ -186,38 +520,12 @@ impl<R> Reader<R> {
 }
 
 
-
-enum Src {
-    Unknown,
-    PathBuf(PathBuf),
+/// `start .. start + size`, unless that would exceed [`u32::MAX`] (`start` and `size` are typically from untrusted data)
+fn rva_range(start: RVA, size: u32) -> io::Result<Range<RVA>> {
+    let end = start.to_u32().checked_add(size).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("RVA range {:?} + 0x{:X} exceeds u32::MAX", start, size)))?;
+    Ok(start .. RVA::new(end))
 }
 
-impl Src {
-    pub fn anno<T>(&self, r: io::Result<T>, note: &str) -> io::Result<T> {
-        if let Err(e) = r {
-            Err(io::Error::new(e.kind(), format!("{}: {}: {}", self, note, e)))
-        } else {
-            r
-        }
-    }
-}
-
-impl Default for Src {
-    fn default() -> Self {
-        Src::Unknown
-    }
-}
-
-impl Display for Src {
-    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
-        match self {
-            Src::Unknown    => write!(fmt, "unknown"),
-            Src::PathBuf(p) => write!(fmt, "`{}`", p.display()),
-        }
-    }
-}
-
-
 
 /// impl {[`Read`], [`Seek`], [`ReadAt`]} in terms of [`pe::Reader`]'s [`pe::SectionHeader`]s<br>
 /// <br>
@@ -334,3 +642,27 @@ impl<'r, R: ReadAt> maulingmonkey_io_adapters::ReadAt for RvaReader<'r, R> {
         r.read(buf)
     }
 }
+
+
+
+/// A PE32 image with an `.edata` section at 0x1000 holding a [`pe::ExportDirectory`] exporting `nfunctions` functions (1 actually present)
+#[cfg(test)] fn exports_fixture(dd_size: u32, nfunctions: u32) -> Vec<u8> {
+    let directory = pe::ExportDirectory { name_rva: RVA::new(0x1028), ordinal_base: 1, nfunctions, functions_rva: RVA::new(0x1030), .. Default::default() };
+    let mut edata = directory.to_bytes().to_vec();
+    edata.extend_from_slice(b"a.dll\0\0\0");
+    edata.extend_from_slice(&0x2000u32.to_le_bytes());
+    let mut w = pe::Writer::new_pe32(pe::Machine::I386);
+    w.data_directory_mut().export = pe::DataDirectory { virtual_address: RVA::new(0x1000), size: dd_size };
+    assert_eq!(w.add_section(".edata", pe::SectionCharacteristics::CNT_INITIALIZED_DATA | pe::SectionCharacteristics::MEM_READ, edata).unwrap(), RVA::new(0x1000));
+    w.to_bytes().unwrap()
+}
+
+#[test] fn read_exports_malformed() {
+    let exports = |dd_size, nfunctions| Reader::read(MemoryReadAt(exports_fixture(dd_size, nfunctions))).unwrap().read_exports();
+    let ok = exports(0x34, 1).unwrap().unwrap();
+    assert_eq!((&ok.dll_name[..], ok.exports.len(), &ok.exports[0].target), ("a.dll", 1, &pe::ExportTarget::Rva(RVA::new(0x2000))));
+
+    assert_eq!(exports(u32::MAX, 1).unwrap_err().kind(),            io::ErrorKind::InvalidData);    // forwarder range overflows
+    assert_eq!(exports(0x34, 0x4000_0001).unwrap_err().kind(),      io::ErrorKind::InvalidData);    // table size overflows
+    assert_eq!(exports(0x34, 0x3FFF_0000).unwrap_err().kind(),      io::ErrorKind::InvalidInput);   // unmapped: rejected before allocating ~4 GiB
+}
@@ -0,0 +1,429 @@
+use super::*;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::*;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+
+
+/// ∑ [Id](Self::Id) | [Name](Self::Name)<br>
+/// A resource type, name, or language: either an integer or a string<br>
+/// <br>
+///
+/// Standard resource types are provided as constants, e.g. [`ResourceId::MANIFEST`] for `RT_MANIFEST`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceId {
+    /// An integer ID, e.g. `MAKEINTRESOURCE(1)`
+    Id(u16),
+    /// A string ID (stored as UTF-16, decoded lossily)
+    Name(String),
+}
+
+impl ResourceId {
+    #[doc = "`RT_CURSOR`: a single cursor image, see [`GROUP_CURSOR`](Self::GROUP_CURSOR)"  ] pub const CURSOR          : ResourceId = ResourceId::Id(1);
+    #[doc = "`RT_BITMAP`"                                                                   ] pub const BITMAP          : ResourceId = ResourceId::Id(2);
+    #[doc = "`RT_ICON`: a single icon image, see [`GROUP_ICON`](Self::GROUP_ICON)"          ] pub const ICON            : ResourceId = ResourceId::Id(3);
+    #[doc = "`RT_MENU`"                                                                     ] pub const MENU            : ResourceId = ResourceId::Id(4);
+    #[doc = "`RT_DIALOG`"                                                                   ] pub const DIALOG          : ResourceId = ResourceId::Id(5);
+    #[doc = "`RT_STRING`: blocks of 16 strings, see [`Resources::set_string`]"              ] pub const STRING          : ResourceId = ResourceId::Id(6);
+    #[doc = "`RT_FONTDIR`"                                                                  ] pub const FONTDIR         : ResourceId = ResourceId::Id(7);
+    #[doc = "`RT_FONT`"                                                                     ] pub const FONT            : ResourceId = ResourceId::Id(8);
+    #[doc = "`RT_ACCELERATOR`"                                                              ] pub const ACCELERATOR     : ResourceId = ResourceId::Id(9);
+    #[doc = "`RT_RCDATA`: application defined raw data"                                     ] pub const RCDATA          : ResourceId = ResourceId::Id(10);
+    #[doc = "`RT_MESSAGETABLE`"                                                             ] pub const MESSAGETABLE    : ResourceId = ResourceId::Id(11);
+    #[doc = "`RT_GROUP_CURSOR`"                                                             ] pub const GROUP_CURSOR    : ResourceId = ResourceId::Id(12);
+    #[doc = "`RT_GROUP_ICON`: an icon directory, see [`Resources::set_icon`]"               ] pub const GROUP_ICON      : ResourceId = ResourceId::Id(14);
+    #[doc = "`RT_VERSION`: a `VS_VERSIONINFO` block, see [`VersionInfo`]"                   ] pub const VERSION         : ResourceId = ResourceId::Id(16);
+    #[doc = "`RT_DLGINCLUDE`"                                                               ] pub const DLGINCLUDE      : ResourceId = ResourceId::Id(17);
+    #[doc = "`RT_PLUGPLAY`"                                                                 ] pub const PLUGPLAY        : ResourceId = ResourceId::Id(19);
+    #[doc = "`RT_VXD`"                                                                      ] pub const VXD             : ResourceId = ResourceId::Id(20);
+    #[doc = "`RT_ANICURSOR`"                                                                ] pub const ANICURSOR       : ResourceId = ResourceId::Id(21);
+    #[doc = "`RT_ANIICON`"                                                                  ] pub const ANIICON         : ResourceId = ResourceId::Id(22);
+    #[doc = "`RT_HTML`"                                                                     ] pub const HTML            : ResourceId = ResourceId::Id(23);
+    #[doc = "`RT_MANIFEST`: a side-by-side assembly manifest (name 1 for EXEs, 2 for DLLs)" ] pub const MANIFEST        : ResourceId = ResourceId::Id(24);
+
+    /// Named entries precede integer IDs, and are sorted by (case sensitive) UTF-16 code units
+    fn cmp_directory_order(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (ResourceId::Name(a),   ResourceId::Name(b))    => a.encode_utf16().cmp(b.encode_utf16()),
+            (ResourceId::Name(_),   ResourceId::Id(_))      => Ordering::Less,
+            (ResourceId::Id(_),     ResourceId::Name(_))    => Ordering::Greater,
+            (ResourceId::Id(a),     ResourceId::Id(b))      => a.cmp(b),
+        }
+    }
+}
+
+impl Display for ResourceId {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            ResourceId::Id(id)      => write!(fmt, "#{}", id),
+            ResourceId::Name(name)  => write!(fmt, "{}", name),
+        }
+    }
+}
+
+impl From<u16> for ResourceId { fn from(id: u16) -> Self { ResourceId::Id(id) } }
+impl From<&str> for ResourceId { fn from(name: &str) -> Self { ResourceId::Name(name.into()) } }
+
+/// ⨯ { [type_id](Self::type_id), [name](Self::name), [language](Self::language), [code_page](Self::code_page), [data](Self::data) }<br>
+/// A single leaf of the PE resource tree<br>
+/// <br>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Resource {
+    /// e.g. [`ResourceId::VERSION`]
+    pub type_id:    ResourceId,
+    pub name:       ResourceId,
+    /// A `LANGID`, e.g. `0x0409` for en-US, or `0` for language neutral
+    pub language:   u16,
+    /// Usually 0
+    pub code_page:  u32,
+    pub data:       Vec<u8>,
+}
+
+/// The PE resource tree (`.rsrc`), flattened into a list of [`Resource`]s in directory order<br>
+/// <br>
+///
+/// Read with [`Image::resources`] or [`Reader::read_resources`], and write back with [`Image::set_resources`].
+/// Directory characteristics and timestamps are not preserved.
+///
+/// ### Example
+/// ```no_run
+/// # use maulingmonkey_format_exe::*;
+/// let mut image = pe::Image::open("example.exe").unwrap();
+/// let mut resources = image.resources().unwrap();
+/// resources.insert(pe::Resource { type_id: pe::ResourceId::MANIFEST, name: 1.into(), language: 0x0409, code_page: 0, data: b"<assembly .../>".to_vec() });
+/// resources.set_string(101, 0x0409, "Hello, world!");
+/// image.set_resources(&resources).unwrap();
+/// image.save("example.exe").unwrap();
+/// ```
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#the-rsrc-section>
+/// *   `IMAGE_RESOURCE_DIRECTORY`, `IMAGE_RESOURCE_DIRECTORY_ENTRY`, `IMAGE_RESOURCE_DATA_ENTRY` in `winnt.h`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Resources {
+    resources: Vec<Resource>,
+}
+
+impl Resources {
+    /// An empty resource tree
+    pub fn new() -> Self { Self::default() }
+
+    // #[allow(missing_docs)]: Honestly these are all pretty self-explanatory
+    #[allow(missing_docs)] pub fn resources(&self) -> &[Resource] { &self.resources[..] }
+    #[allow(missing_docs)] pub fn iter(&self) -> impl Iterator<Item = &Resource> { self.resources.iter() }
+    #[allow(missing_docs)] pub fn is_empty(&self) -> bool { self.resources.is_empty() }
+
+    /// Find the resource matching `type_id`, `name`, and `language` exactly
+    pub fn get(&self, type_id: &ResourceId, name: &ResourceId, language: u16) -> Option<&Resource> {
+        let idx = self.search(type_id, name, language).ok()?;
+        Some(&self.resources[idx])
+    }
+
+    /// Add `resource`, returning the resource it replaced (with the same type, name, and language), if any
+    pub fn insert(&mut self, resource: Resource) -> Option<Resource> {
+        match self.search(&resource.type_id, &resource.name, resource.language) {
+            Ok(idx)     => Some(std::mem::replace(&mut self.resources[idx], resource)),
+            Err(idx)    => { self.resources.insert(idx, resource); None },
+        }
+    }
+
+    /// Remove the resource matching `type_id`, `name`, and `language`, if any
+    pub fn remove(&mut self, type_id: &ResourceId, name: &ResourceId, language: u16) -> Option<Resource> {
+        let idx = self.search(type_id, name, language).ok()?;
+        Some(self.resources.remove(idx))
+    }
+
+    /// Keep only the resources for which `f` returns `true`, e.g. to remove all languages of a resource
+    pub fn retain(&mut self, f: impl FnMut(&Resource) -> bool) { self.resources.retain(f) }
+
+    /// Read string `id` from the [`ResourceId::STRING`] table
+    pub fn string(&self, id: u16, language: u16) -> Option<String> {
+        let block = self.get(&ResourceId::STRING, &ResourceId::Id(id / 16 + 1), language)?;
+        let s = string_block(&block.data[..]).nth(usize::from(id % 16))?;
+        if s.is_empty() { None } else { Some(String::from_utf16_lossy(&s[..])) }
+    }
+
+    /// Add, replace, or (if `value` is empty) remove string `id` in the [`ResourceId::STRING`] table
+    pub fn set_string(&mut self, id: u16, language: u16, value: &str) {
+        let name = ResourceId::Id(id / 16 + 1);
+        let mut strings = vec![Vec::new(); 16];
+        if let Some(block) = self.get(&ResourceId::STRING, &name, language) {
+            for (s, existing) in strings.iter_mut().zip(string_block(&block.data[..])) { *s = existing }
+        }
+        strings[usize::from(id % 16)] = value.encode_utf16().take(usize::from(u16::MAX)).collect();
+
+        if strings.iter().all(|s| s.is_empty()) {
+            self.remove(&ResourceId::STRING, &name, language);
+            return;
+        }
+        let mut data = Vec::new();
+        for s in strings.iter() {
+            data.extend_from_slice(&(s.len() as u16).to_le_bytes());
+            for unit in s.iter() { data.extend_from_slice(&unit.to_le_bytes()) }
+        }
+        self.insert(Resource { type_id: ResourceId::STRING, name, language, code_page: 0, data });
+    }
+
+    /// Add or replace [`ResourceId::GROUP_ICON`] `name` with the images of an `.ico` file.
+    ///
+    /// Each image is stored as a separate [`ResourceId::ICON`], numbered after the highest existing icon ID.
+    /// Icons belonging to a replaced group are removed.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if `ico` isn't a valid `.ico` file
+    pub fn set_icon(&mut self, name: ResourceId, language: u16, ico: &[u8]) -> io::Result<()> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, format!("pe::Resources::set_icon: {}", msg));
+        let u16_at = |o: usize| ico.get(o .. o + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+        let u32_at = |o: usize| ico.get(o .. o + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+        if u16_at(0) != Some(0) || u16_at(2) != Some(1) { return Err(invalid("not an .ico file")) }
+        let count = u16_at(4).ok_or_else(|| invalid("truncated ICONDIR"))?;
+
+        let mut images = Vec::with_capacity(count.into());
+        for i in 0 .. usize::from(count) {
+            let entry = 6 + 16 * i;
+            let header = ico.get(entry .. entry + 12).ok_or_else(|| invalid("truncated ICONDIRENTRY"))?;
+            let size    = u32_at(entry +  8).ok_or_else(|| invalid("truncated ICONDIRENTRY"))? as usize;
+            let offset  = u32_at(entry + 12).ok_or_else(|| invalid("truncated ICONDIRENTRY"))? as usize;
+            let image = ico.get(offset .. offset.saturating_add(size)).ok_or_else(|| invalid("image out of bounds"))?;
+            images.push((header, image));
+        }
+
+        if let Some(old) = self.remove(&ResourceId::GROUP_ICON, &name, language) {
+            for i in 0 .. usize::from(old.data.get(4..6).map_or(0, |b| u16::from_le_bytes([b[0], b[1]]))) {
+                if let Some(id) = old.data.get(6 + 14 * i + 12 .. 6 + 14 * i + 14) {
+                    self.remove(&ResourceId::ICON, &ResourceId::Id(u16::from_le_bytes([id[0], id[1]])), language);
+                }
+            }
+        }
+
+        let first_id = self.resources.iter().filter(|r| r.type_id == ResourceId::ICON).filter_map(|r| match r.name { ResourceId::Id(id) => Some(id), _ => None }).max().unwrap_or(0);
+        if u32::from(first_id) + u32::from(count) > u32::from(u16::MAX) { return Err(invalid("out of icon IDs")) }
+
+        let mut group = ico[..6].to_vec();
+        for (i, (header, image)) in images.into_iter().enumerate() {
+            let id = first_id + 1 + i as u16;
+            group.extend_from_slice(header);
+            group.extend_from_slice(&id.to_le_bytes());
+            self.insert(Resource { type_id: ResourceId::ICON, name: ResourceId::Id(id), language, code_page: 0, data: image.to_vec() });
+        }
+        self.insert(Resource { type_id: ResourceId::GROUP_ICON, name, language, code_page: 0, data: group });
+        Ok(())
+    }
+
+    /// Binary search [`resources`](Self::resources), which are kept in directory order
+    fn search(&self, type_id: &ResourceId, name: &ResourceId, language: u16) -> Result<usize, usize> {
+        self.resources.binary_search_by(|r| cmp_directory_order(r, type_id, name, language))
+    }
+
+    /// Parse the resource tree, given the contents of [`DataDirectories::resource`].
+    /// `read_data` reads the leaf data for an [`RVA`] range.
+    pub(crate) fn parse(dir: &[u8], mut read_data: impl FnMut(RVA, u32) -> io::Result<Vec<u8>>) -> io::Result<Self> {
+        let mut resources = Vec::new();
+        if dir.is_empty() { return Ok(Self { resources }) }
+        for (type_id, types) in parse_directory(dir, 0, true)? {
+            for (name, names) in parse_directory(dir, types, true)? {
+                for (language, data_entry) in parse_directory(dir, names, false)? {
+                    let language = match language {
+                        ResourceId::Id(language)    => language,
+                        ResourceId::Name(_)         => return Err(io::Error::new(io::ErrorKind::InvalidData, "pe::Resources: named language")),
+                    };
+                    let rva         = u32_at(dir, data_entry)?;
+                    let size        = u32_at(dir, data_entry + 4)?;
+                    let code_page   = u32_at(dir, data_entry + 8)?;
+                    let data        = read_data(RVA::new(rva), size)?;
+                    resources.push(Resource { type_id: type_id.clone(), name: name.clone(), language, code_page, data });
+                }
+            }
+        }
+        resources.sort_by(|a, b| cmp_directory_order(a, &b.type_id, &b.name, b.language));
+        Ok(Self { resources })
+    }
+
+    /// The offsets of every `IMAGE_RESOURCE_DIRECTORY` within the resource tree `dir` (root first)
+    pub(crate) fn directory_offsets(dir: &[u8]) -> io::Result<Vec<usize>> {
+        if dir.is_empty() { return Ok(Vec::new()) }
+        let mut offsets = vec![0];
+        for (_, types) in parse_directory(dir, 0, true)? {
+            offsets.push(types);
+            offsets.extend(parse_directory(dir, types, true)?.into_iter().map(|(_, names)| names));
+        }
+        Ok(offsets)
+    }
+
+    /// Serialize the resource tree for loading at `rva` (data entries reference absolute [`RVA`]s.)
+    ///
+    /// The layout matches `cvtres`: all directories (breadth first), then names, then data entries, then 8-byte aligned data.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if a name exceeds 65535 UTF-16 code units, or the tree would exceed 4 GiB
+    pub fn to_bytes(&self, rva: RVA) -> io::Result<Vec<u8>> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "pe::Resources: resource tree exceeds 4 GiB");
+        let sorted = self.resources.iter().collect::<Vec<_>>();
+
+        let mut tree = ResourceTree::new();
+        for r in sorted.iter().copied() {
+            match tree.last_mut() {
+                Some((type_id, names)) if **type_id == r.type_id => match names.last_mut() {
+                    Some((name, languages)) if **name == r.name => languages.push(r),
+                    _ => names.push((&r.name, vec![r])),
+                },
+                _ => tree.push((&r.type_id, vec![(&r.name, vec![r])])),
+            }
+        }
+
+        let directory_size = |n: usize| 16 + 8 * n;
+        let mut offset = directory_size(tree.len());
+        let mut name_directories = Vec::new();
+        for (_, names) in tree.iter() { name_directories.push(offset); offset += directory_size(names.len()) }
+        let mut language_directories = Vec::new();
+        for (_, names) in tree.iter() { for (_, languages) in names.iter() { language_directories.push(offset); offset += directory_size(languages.len()) } }
+
+        let mut strings = BTreeMap::<&str, usize>::new();
+        for (type_id, names) in tree.iter() {
+            for id in std::iter::once(*type_id).chain(names.iter().map(|(name, _)| *name)) {
+                if let ResourceId::Name(name) = id {
+                    if strings.contains_key(name.as_str()) { continue }
+                    let units = name.encode_utf16().count();
+                    if units > usize::from(u16::MAX) { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Resources: name {:?} exceeds 65535 UTF-16 code units", name))) }
+                    strings.insert(name.as_str(), offset);
+                    offset += 2 + 2 * units;
+                }
+            }
+        }
+
+        let data_entries = (offset + 3) & !3;
+        offset = data_entries + 16 * sorted.len();
+        let mut data_offsets = Vec::with_capacity(sorted.len());
+        for r in sorted.iter() {
+            offset = (offset + 7) & !7;
+            data_offsets.push(offset);
+            offset += r.data.len();
+        }
+        u32::try_from(offset).ok().and_then(|size| rva.to_u32().checked_add(size)).ok_or_else(too_big)?;
+
+        let mut out = vec![0u8; offset];
+        let put = |out: &mut Vec<u8>, at: usize, value: u32| out[at .. at + 4].copy_from_slice(&value.to_le_bytes());
+        let id_field = |id: &ResourceId| match id {
+            ResourceId::Id(id)      => u32::from(*id),
+            ResourceId::Name(name)  => 0x8000_0000 | strings[name.as_str()] as u32,
+        };
+        let directory = |out: &mut Vec<u8>, at: usize, ids: &mut dyn Iterator<Item = &ResourceId>| {
+            let (named, ids) = ids.fold((0u16, 0u16), |(n, i), id| match id { ResourceId::Name(_) => (n + 1, i), ResourceId::Id(_) => (n, i + 1) });
+            out[at + 12 .. at + 14].copy_from_slice(&named.to_le_bytes());
+            out[at + 14 .. at + 16].copy_from_slice(&ids.to_le_bytes());
+        };
+
+        directory(&mut out, 0, &mut tree.iter().map(|(type_id, _)| *type_id));
+        let mut leaf = 0;
+        let mut language_directory = language_directories.iter().copied();
+        for (t, (type_id, names)) in tree.iter().enumerate() {
+            put(&mut out, 16 + 8 * t, id_field(type_id));
+            put(&mut out, 16 + 8 * t + 4, 0x8000_0000 | name_directories[t] as u32);
+            directory(&mut out, name_directories[t], &mut names.iter().map(|(name, _)| *name));
+            for (n, (name, languages)) in names.iter().enumerate() {
+                let languages_at = language_directory.next().unwrap();
+                put(&mut out, name_directories[t] + 16 + 8 * n, id_field(name));
+                put(&mut out, name_directories[t] + 16 + 8 * n + 4, 0x8000_0000 | languages_at as u32);
+                out[languages_at + 14 .. languages_at + 16].copy_from_slice(&(languages.len() as u16).to_le_bytes());
+                for (l, r) in languages.iter().enumerate() {
+                    let entry = data_entries + 16 * leaf;
+                    put(&mut out, languages_at + 16 + 8 * l, u32::from(r.language));
+                    put(&mut out, languages_at + 16 + 8 * l + 4, entry as u32);
+                    put(&mut out, entry,     rva.to_u32() + data_offsets[leaf] as u32);
+                    put(&mut out, entry + 4, r.data.len() as u32);
+                    put(&mut out, entry + 8, r.code_page);
+                    out[data_offsets[leaf] .. data_offsets[leaf] + r.data.len()].copy_from_slice(&r.data[..]);
+                    leaf += 1;
+                }
+            }
+        }
+        for (name, at) in strings.iter() {
+            let units = name.encode_utf16().collect::<Vec<_>>();
+            out[*at .. *at + 2].copy_from_slice(&(units.len() as u16).to_le_bytes());
+            for (i, unit) in units.iter().enumerate() { out[at + 2 + 2 * i .. at + 4 + 2 * i].copy_from_slice(&unit.to_le_bytes()) }
+        }
+        Ok(out)
+    }
+}
+
+fn cmp_directory_order(r: &Resource, type_id: &ResourceId, name: &ResourceId, language: u16) -> Ordering {
+    r.type_id.cmp_directory_order(type_id).then_with(|| r.name.cmp_directory_order(name)).then(r.language.cmp(&language))
+}
+
+/// Resources grouped by type, then name, in directory order
+type ResourceTree<'r> = Vec<(&'r ResourceId, Vec<(&'r ResourceId, Vec<&'r Resource>)>)>;
+
+fn u32_at(dir: &[u8], offset: usize) -> io::Result<u32> {
+    let b = dir.get(offset .. offset + 4).ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "pe::Resources: offset out of bounds"))?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Parse an `IMAGE_RESOURCE_DIRECTORY` at `offset`, returning the ids and offsets of its subdirectories (or data entries, if `!subdirectories`)
+fn parse_directory(dir: &[u8], offset: usize, subdirectories: bool) -> io::Result<Vec<(ResourceId, usize)>> {
+    let counts = u32_at(dir, offset + 12)?;
+    let n = (counts & 0xFFFF) as usize + (counts >> 16) as usize;
+    let mut entries = Vec::with_capacity(n.min(dir.len() / 8));
+    for i in 0 .. n {
+        let entry = offset + 16 + 8 * i;
+        let id = u32_at(dir, entry)?;
+        let target = u32_at(dir, entry + 4)?;
+        let id = if id & 0x8000_0000 == 0 { ResourceId::Id(id as u16) } else {
+            let name = (id & 0x7FFF_FFFF) as usize;
+            let eof = || io::Error::new(io::ErrorKind::UnexpectedEof, "pe::Resources: name out of bounds");
+            let len = dir.get(name .. name + 2).map(|b| usize::from(u16::from_le_bytes([b[0], b[1]]))).ok_or_else(eof)?;
+            let units = dir.get(name + 2 .. name + 2 + 2 * len).ok_or_else(eof)?;
+            ResourceId::Name(String::from_utf16_lossy(&units.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect::<Vec<_>>()[..]))
+        };
+        if (target & 0x8000_0000 != 0) != subdirectories {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "pe::Resources: resource tree isn't three levels deep"));
+        }
+        entries.push((id, (target & 0x7FFF_FFFF) as usize));
+    }
+    Ok(entries)
+}
+
+/// Iterate the (up to 16) length-prefixed UTF-16 strings of an [`ResourceId::STRING`] block
+fn string_block(mut data: &[u8]) -> impl Iterator<Item = Vec<u16>> + '_ {
+    std::iter::from_fn(move || {
+        let len = usize::from(u16::from_le_bytes([*data.first()?, *data.get(1)?]));
+        let s = data.get(2 .. 2 + 2 * len)?;
+        data = &data[2 + 2 * len ..];
+        Some(s.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect())
+    }).take(16)
+}
+
+
+
+#[test] fn roundtrip() {
+    let mut resources = Resources::new();
+    resources.insert(Resource { type_id: ResourceId::MANIFEST, name: 1.into(), language: 0x0409, code_page: 0, data: b"<assembly/>".to_vec() });
+    resources.insert(Resource { type_id: "CUSTOM".into(), name: "BLOB".into(), language: 0, code_page: 1252, data: vec![1, 2, 3] });
+    resources.insert(Resource { type_id: ResourceId::RCDATA, name: "BLOB".into(), language: 0, code_page: 0, data: vec![4; 9] });
+    resources.set_string(101, 0x0409, "Hello");
+    resources.set_string(102, 0x0409, "World");
+    resources.set_string(102, 0x0409, "");
+    assert_eq!(resources.string(101, 0x0409).as_deref(), Some("Hello"));
+    assert_eq!(resources.string(102, 0x0409), None);
+
+    let ico = [
+        0, 0, 1, 0, 1, 0,                                   // ICONDIR: 1 image
+        16, 16, 0, 0, 1, 0, 32, 0, 4, 0, 0, 0, 22, 0, 0, 0, // ICONDIRENTRY: 16x16x32, 4 bytes @ 22
+        0xAA, 0xBB, 0xCC, 0xDD,
+    ];
+    resources.set_icon(1.into(), 0x0409, &ico[..]).unwrap();
+    resources.set_icon(1.into(), 0x0409, &ico[..]).unwrap(); // replaces the old icon
+    assert_eq!(resources.iter().filter(|r| r.type_id == ResourceId::ICON).count(), 1);
+    assert_eq!(resources.get(&ResourceId::GROUP_ICON, &1.into(), 0x0409).unwrap().data[18..20], [1, 0]);
+
+    let rva = RVA::new(0x3000);
+    let bytes = resources.to_bytes(rva).unwrap();
+    assert_eq!(bytes[12..16], [1, 0, 5, 0]); // "CUSTOM" + RCDATA, STRING, GROUP_ICON, ICON, MANIFEST
+    let parsed = Resources::parse(&bytes[..], |r, size| Ok(bytes[(r - rva) as usize ..][.. size as usize].to_vec())).unwrap();
+    assert_eq!(parsed.resources().len(), resources.resources().len());
+    for r in resources.iter() { assert_eq!(parsed.get(&r.type_id, &r.name, r.language), Some(r)) }
+    assert_eq!(parsed.resources()[0].type_id, ResourceId::Name("CUSTOM".into()));
+    assert_eq!(parsed.to_bytes(rva).unwrap(), bytes);
+}
@@ -0,0 +1,461 @@
+use std::convert::*;
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+
+
+
+/// ⨯ {
+///     [offset](Self::offset),
+///     [key](Self::key),
+///     [entries](Self::entries)
+/// }<br>
+/// MSVC's undocumented "Rich" header: which compilers/linkers (and how many objects from each) went into an image.<br>
+/// <br>
+///
+/// Found between the [`mz::Stub`](crate::mz::Stub) program and the [`pe::Header`](crate::pe::Header), XOR-masked with [`key`](Self::key):
+///
+/// | Masked        | Unmasked      | Desc  |
+/// | --------------| --------------| ------|
+/// | `DanS ^ key`  | `"DanS"`      | Start marker, at [`offset`](Self::offset)
+/// | `0 ^ key` × 3 | `0` × 3       | Padding
+/// | `id ^ key`, `count ^ key` | [`RichEntry`] | Repeated for each [`entries`](Self::entries)
+/// | `"Rich"`      | `"Rich"`      | End marker (unmasked)
+/// | `key`         |               | Also a checksum of the MZ header, stub and entries
+///
+/// ## References
+/// *   <https://bytepointer.com/articles/the_microsoft_rich_header.htm>
+/// *   <http://www.ntcore.com/files/richsign.htm>
+/// *   <https://github.com/dishather/richprint>
+#[derive(Clone, Debug, Default)]
+pub struct RichHeader {
+    /// File offset of the `"DanS"` start marker
+    pub offset:             u32,
+    /// The XOR mask / checksum following the `"Rich"` end marker
+    pub key:                u32,
+    /// Decoded (product id, build number, use count) entries
+    pub entries:            Vec<RichEntry>,
+    checksum:               u32,
+}
+
+/// ⨯ {
+///     [product_id](Self::product_id),
+///     [build](Self::build),
+///     [count](Self::count)
+/// }<br>
+/// A single [`RichHeader`] entry: `count` objects were produced by tool `product_id` at build `build`.<br>
+/// <br>
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RichEntry {
+    /// Which tool (e.g. `0x0105` = `Utc1900_CPP`, the VS2015+ C++ compiler) - see [`product_name`](Self::product_name)
+    pub product_id:         u16,
+    /// The tool's build number (e.g. `30159` for VS2019 16.11)
+    pub build:              u16,
+    /// How many objects (or for `Import0`, imported functions) came from this tool
+    pub count:              u32,
+}
+
+impl RichHeader {
+    /// Find and decode the Rich header in `image_prefix` (the file's bytes from offset `0` up to [`mz::Header::pe_header_start`](crate::mz::Header::pe_header_start).)
+    ///
+    /// Returns [`None`] if there is no `"Rich"` end marker (e.g. the image was produced by a non-Microsoft linker, or stripped.)
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if a `"Rich"` marker was found without a matching `"DanS"` marker
+    pub fn find_in(image_prefix: &[u8]) -> io::Result<Option<Self>> {
+        const MZ_HEADER_SIZE : usize = 0x40;
+        let dword = |o: usize| u32::from_le_bytes(image_prefix[o..o+4].try_into().unwrap());
+
+        let rich = match (MZ_HEADER_SIZE .. image_prefix.len().saturating_sub(7)).step_by(4).rev().find(|o| &image_prefix[*o..*o+4] == b"Rich") {
+            None => return Ok(None),
+            Some(o) => o,
+        };
+        let key = dword(rich + 4);
+
+        let dans = (MZ_HEADER_SIZE .. rich).step_by(4).rev().find(|o| dword(*o) ^ key == u32::from_le_bytes(*b"DanS"))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "pe::RichHeader: found \"Rich\" marker without \"DanS\" marker"))?;
+
+        let entries_start = (dans + 16).min(rich);
+        let entries = image_prefix[entries_start .. rich].chunks_exact(8).map(|e| {
+            let id      = u32::from_le_bytes(e[0..4].try_into().unwrap()) ^ key;
+            let count   = u32::from_le_bytes(e[4..8].try_into().unwrap()) ^ key;
+            RichEntry { product_id: (id >> 16) as u16, build: id as u16, count }
+        }).collect::<Vec<_>>();
+
+        let mut checksum = dans as u32;
+        for (i, b) in image_prefix[..dans].iter().copied().enumerate() {
+            if (0x3C .. 0x40).contains(&i) { continue } // mz::Header::pe_header_start
+            checksum = checksum.wrapping_add(u32::from(b).rotate_left(i as u32));
+        }
+        for e in entries.iter() {
+            checksum = checksum.wrapping_add(e.comp_id().rotate_left(e.count));
+        }
+
+        Ok(Some(Self { offset: dans as u32, key, entries, checksum }))
+    }
+
+    /// The checksum computed from the MZ header, stub and [`entries`](Self::entries)
+    pub fn computed_checksum(&self) -> u32 { self.checksum }
+
+    /// `true` if [`key`](Self::key) matches [`computed_checksum`](Self::computed_checksum).
+    /// A mismatch suggests the MZ header, stub, or Rich header was edited (or forged) after linking.
+    pub fn is_checksum_valid(&self) -> bool { self.key == self.checksum }
+
+    /// File offset one past the end of the header (after the `"Rich"` marker and [`key`](Self::key))
+    pub fn end(&self) -> u32 { self.offset + 16 + 8 * self.entries.len() as u32 + 8 }
+}
+
+impl RichEntry {
+    /// `product_id << 16 | build`, as stored (masked) in the header
+    pub fn comp_id(&self) -> u32 { u32::from(self.product_id) << 16 | u32::from(self.build) }
+
+    /// Microsoft's internal name for [`product_id`](Self::product_id) (e.g. `"Utc1900_CPP"`, `"Linker1400"`, `"Masm1400"`), if known
+    pub fn product_name(&self) -> Option<&'static str> {
+        PRODUCTS.iter().find(|p| p.0 == self.product_id).map(|p| p.1)
+    }
+
+    /// The Visual Studio release that shipped this tool (e.g. `"Visual Studio 2019"`), if known
+    pub fn toolchain(&self) -> Option<&'static str> {
+        let toolchain = PRODUCTS.iter().find(|p| p.0 == self.product_id)?.2?;
+        if toolchain != "Visual Studio 2015+" { return Some(toolchain) }
+        // VS2015 through VS2022 all share the same product ids (MSVC 14.x) - tell them apart by build number
+        Some(match self.build {
+            0     ..= 24999 => "Visual Studio 2015",
+            25000 ..= 27499 => "Visual Studio 2017",
+            27500 ..= 30699 => "Visual Studio 2019",
+            _               => "Visual Studio 2022",
+        })
+    }
+}
+
+impl Debug for RichEntry {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.product_name() {
+            Some(name)  => write!(fmt, "RichEntry {{ {} (0x{:04x}) build {} × {} }}", name, self.product_id, self.build, self.count),
+            None        => write!(fmt, "RichEntry {{ 0x{:04x} build {} × {} }}", self.product_id, self.build, self.count),
+        }
+    }
+}
+
+
+
+/// (`product_id`, name, toolchain)
+///
+/// Names per <https://github.com/dishather/richprint/blob/master/comp_id.txt>
+const PRODUCTS : &[(u16, &str, Option<&str>)] = &[
+    (0x0000, "Unknown",                 None),
+    (0x0001, "Import0",                 None),
+    (0x0002, "Linker510",               Some("Visual Studio 97")),
+    (0x0003, "Cvtomf510",               Some("Visual Studio 97")),
+    (0x0004, "Linker600",               Some("Visual Studio 6.0")),
+    (0x0005, "Cvtomf600",               Some("Visual Studio 6.0")),
+    (0x0006, "Cvtres500",               Some("Visual Studio 97")),
+    (0x0007, "Utc11_Basic",             Some("Visual Studio 97")),
+    (0x0008, "Utc11_C",                 Some("Visual Studio 97")),
+    (0x0009, "Utc12_Basic",             Some("Visual Studio 6.0")),
+    (0x000a, "Utc12_C",                 Some("Visual Studio 6.0")),
+    (0x000b, "Utc12_CPP",               Some("Visual Studio 6.0")),
+    (0x000c, "AliasObj60",              Some("Visual Studio 6.0")),
+    (0x000d, "VisualBasic60",           Some("Visual Studio 6.0")),
+    (0x000e, "Masm613",                 Some("Visual Studio 6.0")),
+    (0x000f, "Masm710",                 Some("Visual Studio .NET 2003")),
+    (0x0010, "Linker511",               Some("Visual Studio 97")),
+    (0x0011, "Cvtomf511",               Some("Visual Studio 97")),
+    (0x0012, "Masm614",                 Some("Visual Studio 6.0")),
+    (0x0013, "Linker512",               Some("Visual Studio 97")),
+    (0x0014, "Cvtomf512",               Some("Visual Studio 97")),
+    (0x0015, "Utc12_C_Std",             Some("Visual Studio 6.0")),
+    (0x0016, "Utc12_CPP_Std",           Some("Visual Studio 6.0")),
+    (0x0017, "Utc12_C_Book",            Some("Visual Studio 6.0")),
+    (0x0018, "Utc12_CPP_Book",          Some("Visual Studio 6.0")),
+    (0x0019, "Implib700",               Some("Visual Studio .NET 2002")),
+    (0x001a, "Cvtomf700",               Some("Visual Studio .NET 2002")),
+    (0x001b, "Utc13_Basic",             Some("Visual Studio .NET 2002")),
+    (0x001c, "Utc13_C",                 Some("Visual Studio .NET 2002")),
+    (0x001d, "Utc13_CPP",               Some("Visual Studio .NET 2002")),
+    (0x001e, "Linker610",               Some("Visual Studio 6.0")),
+    (0x001f, "Cvtomf610",               Some("Visual Studio 6.0")),
+    (0x0020, "Linker601",               Some("Visual Studio 6.0")),
+    (0x0021, "Cvtomf601",               Some("Visual Studio 6.0")),
+    (0x0022, "Utc12_1_Basic",           Some("Visual Studio 6.0")),
+    (0x0023, "Utc12_1_C",               Some("Visual Studio 6.0")),
+    (0x0024, "Utc12_1_CPP",             Some("Visual Studio 6.0")),
+    (0x0025, "Linker620",               Some("Visual Studio 6.0")),
+    (0x0026, "Cvtomf620",               Some("Visual Studio 6.0")),
+    (0x0027, "AliasObj70",              Some("Visual Studio .NET 2002")),
+    (0x0028, "Linker621",               Some("Visual Studio 6.0")),
+    (0x0029, "Cvtomf621",               Some("Visual Studio 6.0")),
+    (0x002a, "Masm615",                 Some("Visual Studio 6.0")),
+    (0x002b, "Utc13_LTCG_C",            Some("Visual Studio .NET 2002")),
+    (0x002c, "Utc13_LTCG_CPP",          Some("Visual Studio .NET 2002")),
+    (0x002d, "Masm620",                 Some("Visual Studio 6.0")),
+    (0x002e, "ILAsm100",                None),
+    (0x002f, "Utc12_2_Basic",           Some("Visual Studio 6.0")),
+    (0x0030, "Utc12_2_C",               Some("Visual Studio 6.0")),
+    (0x0031, "Utc12_2_CPP",             Some("Visual Studio 6.0")),
+    (0x0032, "Utc12_2_C_Std",           Some("Visual Studio 6.0")),
+    (0x0033, "Utc12_2_CPP_Std",         Some("Visual Studio 6.0")),
+    (0x0034, "Utc12_2_C_Book",          Some("Visual Studio 6.0")),
+    (0x0035, "Utc12_2_CPP_Book",        Some("Visual Studio 6.0")),
+    (0x0036, "Implib622",               Some("Visual Studio 6.0")),
+    (0x0037, "Cvtomf622",               Some("Visual Studio 6.0")),
+    (0x0038, "Cvtres501",               Some("Visual Studio 97")),
+    (0x0039, "Utc13_C_Std",             Some("Visual Studio .NET 2002")),
+    (0x003a, "Utc13_CPP_Std",           Some("Visual Studio .NET 2002")),
+    (0x003b, "Cvtpgd1300",              Some("Visual Studio .NET 2002")),
+    (0x003c, "Linker622",               Some("Visual Studio 6.0")),
+    (0x003d, "Linker700",               Some("Visual Studio .NET 2002")),
+    (0x003e, "Export622",               Some("Visual Studio 6.0")),
+    (0x003f, "Export700",               Some("Visual Studio .NET 2002")),
+    (0x0040, "Masm700",                 Some("Visual Studio .NET 2002")),
+    (0x0041, "Utc13_POGO_I_C",          Some("Visual Studio .NET 2002")),
+    (0x0042, "Utc13_POGO_I_CPP",        Some("Visual Studio .NET 2002")),
+    (0x0043, "Utc13_POGO_O_C",          Some("Visual Studio .NET 2002")),
+    (0x0044, "Utc13_POGO_O_CPP",        Some("Visual Studio .NET 2002")),
+    (0x0045, "Cvtres700",               Some("Visual Studio .NET 2002")),
+    (0x0046, "Cvtres710p",              None),
+    (0x0047, "Linker710p",              None),
+    (0x0048, "Cvtomf710p",              None),
+    (0x0049, "Export710p",              None),
+    (0x004a, "Implib710p",              None),
+    (0x004b, "Masm710p",                None),
+    (0x004c, "Utc1310p_C",              None),
+    (0x004d, "Utc1310p_CPP",            None),
+    (0x004e, "Utc1310p_C_Std",          None),
+    (0x004f, "Utc1310p_CPP_Std",        None),
+    (0x0050, "Utc1310p_LTCG_C",         None),
+    (0x0051, "Utc1310p_LTCG_CPP",       None),
+    (0x0052, "Utc1310p_POGO_I_C",       None),
+    (0x0053, "Utc1310p_POGO_I_CPP",     None),
+    (0x0054, "Utc1310p_POGO_O_C",       None),
+    (0x0055, "Utc1310p_POGO_O_CPP",     None),
+    (0x0056, "Linker624",               Some("Visual Studio 6.0")),
+    (0x0057, "Cvtomf624",               Some("Visual Studio 6.0")),
+    (0x0058, "Export624",               Some("Visual Studio 6.0")),
+    (0x0059, "Implib624",               Some("Visual Studio 6.0")),
+    (0x005a, "Linker710",               Some("Visual Studio .NET 2003")),
+    (0x005b, "Cvtomf710",               Some("Visual Studio .NET 2003")),
+    (0x005c, "Export710",               Some("Visual Studio .NET 2003")),
+    (0x005d, "Implib710",               Some("Visual Studio .NET 2003")),
+    (0x005e, "Cvtres710",               Some("Visual Studio .NET 2003")),
+    (0x005f, "Utc1310_C",               Some("Visual Studio .NET 2003")),
+    (0x0060, "Utc1310_CPP",             Some("Visual Studio .NET 2003")),
+    (0x0061, "Utc1310_C_Std",           Some("Visual Studio .NET 2003")),
+    (0x0062, "Utc1310_CPP_Std",         Some("Visual Studio .NET 2003")),
+    (0x0063, "Utc1310_LTCG_C",          Some("Visual Studio .NET 2003")),
+    (0x0064, "Utc1310_LTCG_CPP",        Some("Visual Studio .NET 2003")),
+    (0x0065, "Utc1310_POGO_I_C",        Some("Visual Studio .NET 2003")),
+    (0x0066, "Utc1310_POGO_I_CPP",      Some("Visual Studio .NET 2003")),
+    (0x0067, "Utc1310_POGO_O_C",        Some("Visual Studio .NET 2003")),
+    (0x0068, "Utc1310_POGO_O_CPP",      Some("Visual Studio .NET 2003")),
+    (0x0069, "AliasObj710",             Some("Visual Studio .NET 2003")),
+    (0x006a, "AliasObj710p",            None),
+    (0x006b, "Cvtpgd1310",              Some("Visual Studio .NET 2003")),
+    (0x006c, "Cvtpgd1310p",             None),
+    (0x006d, "Utc1400_C",               Some("Visual Studio 2005")),
+    (0x006e, "Utc1400_CPP",             Some("Visual Studio 2005")),
+    (0x006f, "Utc1400_C_Std",           Some("Visual Studio 2005")),
+    (0x0070, "Utc1400_CPP_Std",         Some("Visual Studio 2005")),
+    (0x0071, "Utc1400_LTCG_C",          Some("Visual Studio 2005")),
+    (0x0072, "Utc1400_LTCG_CPP",        Some("Visual Studio 2005")),
+    (0x0073, "Utc1400_POGO_I_C",        Some("Visual Studio 2005")),
+    (0x0074, "Utc1400_POGO_I_CPP",      Some("Visual Studio 2005")),
+    (0x0075, "Utc1400_POGO_O_C",        Some("Visual Studio 2005")),
+    (0x0076, "Utc1400_POGO_O_CPP",      Some("Visual Studio 2005")),
+    (0x0077, "Cvtpgd1400",              Some("Visual Studio 2005")),
+    (0x0078, "Linker800",               Some("Visual Studio 2005")),
+    (0x0079, "Cvtomf800",               Some("Visual Studio 2005")),
+    (0x007a, "Export800",               Some("Visual Studio 2005")),
+    (0x007b, "Implib800",               Some("Visual Studio 2005")),
+    (0x007c, "Cvtres800",               Some("Visual Studio 2005")),
+    (0x007d, "Masm800",                 Some("Visual Studio 2005")),
+    (0x007e, "AliasObj800",             Some("Visual Studio 2005")),
+    (0x007f, "PhoenixPrerelease",       None),
+    (0x0080, "Utc1400_CVTCIL_C",        Some("Visual Studio 2005")),
+    (0x0081, "Utc1400_CVTCIL_CPP",      Some("Visual Studio 2005")),
+    (0x0082, "Utc1400_LTCG_MSIL",       Some("Visual Studio 2005")),
+    (0x0083, "Utc1500_C",               Some("Visual Studio 2008")),
+    (0x0084, "Utc1500_CPP",             Some("Visual Studio 2008")),
+    (0x0085, "Utc1500_C_Std",           Some("Visual Studio 2008")),
+    (0x0086, "Utc1500_CPP_Std",         Some("Visual Studio 2008")),
+    (0x0087, "Utc1500_CVTCIL_C",        Some("Visual Studio 2008")),
+    (0x0088, "Utc1500_CVTCIL_CPP",      Some("Visual Studio 2008")),
+    (0x0089, "Utc1500_LTCG_C",          Some("Visual Studio 2008")),
+    (0x008a, "Utc1500_LTCG_CPP",        Some("Visual Studio 2008")),
+    (0x008b, "Utc1500_LTCG_MSIL",       Some("Visual Studio 2008")),
+    (0x008c, "Utc1500_POGO_I_C",        Some("Visual Studio 2008")),
+    (0x008d, "Utc1500_POGO_I_CPP",      Some("Visual Studio 2008")),
+    (0x008e, "Utc1500_POGO_O_C",        Some("Visual Studio 2008")),
+    (0x008f, "Utc1500_POGO_O_CPP",      Some("Visual Studio 2008")),
+    (0x0090, "Cvtpgd1500",              Some("Visual Studio 2008")),
+    (0x0091, "Linker900",               Some("Visual Studio 2008")),
+    (0x0092, "Export900",               Some("Visual Studio 2008")),
+    (0x0093, "Implib900",               Some("Visual Studio 2008")),
+    (0x0094, "Cvtres900",               Some("Visual Studio 2008")),
+    (0x0095, "Masm900",                 Some("Visual Studio 2008")),
+    (0x0096, "AliasObj900",             Some("Visual Studio 2008")),
+    (0x0097, "Resource",                None),
+    (0x0098, "AliasObj1000",            Some("Visual Studio 2010")),
+    (0x0099, "Cvtpgd1600",              Some("Visual Studio 2010")),
+    (0x009a, "Cvtres1000",              Some("Visual Studio 2010")),
+    (0x009b, "Export1000",              Some("Visual Studio 2010")),
+    (0x009c, "Implib1000",              Some("Visual Studio 2010")),
+    (0x009d, "Linker1000",              Some("Visual Studio 2010")),
+    (0x009e, "Masm1000",                Some("Visual Studio 2010")),
+    (0x009f, "Phx1600_C",               Some("Visual Studio 2010")),
+    (0x00a0, "Phx1600_CPP",             Some("Visual Studio 2010")),
+    (0x00a1, "Phx1600_CVTCIL_C",        Some("Visual Studio 2010")),
+    (0x00a2, "Phx1600_CVTCIL_CPP",      Some("Visual Studio 2010")),
+    (0x00a3, "Phx1600_LTCG_C",          Some("Visual Studio 2010")),
+    (0x00a4, "Phx1600_LTCG_CPP",        Some("Visual Studio 2010")),
+    (0x00a5, "Phx1600_LTCG_MSIL",       Some("Visual Studio 2010")),
+    (0x00a6, "Phx1600_POGO_I_C",        Some("Visual Studio 2010")),
+    (0x00a7, "Phx1600_POGO_I_CPP",      Some("Visual Studio 2010")),
+    (0x00a8, "Phx1600_POGO_O_C",        Some("Visual Studio 2010")),
+    (0x00a9, "Phx1600_POGO_O_CPP",      Some("Visual Studio 2010")),
+    (0x00aa, "Utc1600_C",               Some("Visual Studio 2010")),
+    (0x00ab, "Utc1600_CPP",             Some("Visual Studio 2010")),
+    (0x00ac, "Utc1600_CVTCIL_C",        Some("Visual Studio 2010")),
+    (0x00ad, "Utc1600_CVTCIL_CPP",      Some("Visual Studio 2010")),
+    (0x00ae, "Utc1600_LTCG_C",          Some("Visual Studio 2010")),
+    (0x00af, "Utc1600_LTCG_CPP",        Some("Visual Studio 2010")),
+    (0x00b0, "Utc1600_LTCG_MSIL",       Some("Visual Studio 2010")),
+    (0x00b1, "Utc1600_POGO_I_C",        Some("Visual Studio 2010")),
+    (0x00b2, "Utc1600_POGO_I_CPP",      Some("Visual Studio 2010")),
+    (0x00b3, "Utc1600_POGO_O_C",        Some("Visual Studio 2010")),
+    (0x00b4, "Utc1600_POGO_O_CPP",      Some("Visual Studio 2010")),
+    (0x00b5, "AliasObj1010",            Some("Visual Studio 2010")),
+    (0x00b6, "Cvtpgd1610",              Some("Visual Studio 2010")),
+    (0x00b7, "Cvtres1010",              Some("Visual Studio 2010")),
+    (0x00b8, "Export1010",              Some("Visual Studio 2010")),
+    (0x00b9, "Implib1010",              Some("Visual Studio 2010")),
+    (0x00ba, "Linker1010",              Some("Visual Studio 2010")),
+    (0x00bb, "Masm1010",                Some("Visual Studio 2010")),
+    (0x00bc, "Utc1610_C",               Some("Visual Studio 2010")),
+    (0x00bd, "Utc1610_CPP",             Some("Visual Studio 2010")),
+    (0x00be, "Utc1610_CVTCIL_C",        Some("Visual Studio 2010")),
+    (0x00bf, "Utc1610_CVTCIL_CPP",      Some("Visual Studio 2010")),
+    (0x00c0, "Utc1610_LTCG_C",          Some("Visual Studio 2010")),
+    (0x00c1, "Utc1610_LTCG_CPP",        Some("Visual Studio 2010")),
+    (0x00c2, "Utc1610_LTCG_MSIL",       Some("Visual Studio 2010")),
+    (0x00c3, "Utc1610_POGO_I_C",        Some("Visual Studio 2010")),
+    (0x00c4, "Utc1610_POGO_I_CPP",      Some("Visual Studio 2010")),
+    (0x00c5, "Utc1610_POGO_O_C",        Some("Visual Studio 2010")),
+    (0x00c6, "Utc1610_POGO_O_CPP",      Some("Visual Studio 2010")),
+    (0x00c7, "AliasObj1100",            Some("Visual Studio 2012")),
+    (0x00c8, "Cvtpgd1700",              Some("Visual Studio 2012")),
+    (0x00c9, "Cvtres1100",              Some("Visual Studio 2012")),
+    (0x00ca, "Export1100",              Some("Visual Studio 2012")),
+    (0x00cb, "Implib1100",              Some("Visual Studio 2012")),
+    (0x00cc, "Linker1100",              Some("Visual Studio 2012")),
+    (0x00cd, "Masm1100",                Some("Visual Studio 2012")),
+    (0x00ce, "Utc1700_C",               Some("Visual Studio 2012")),
+    (0x00cf, "Utc1700_CPP",             Some("Visual Studio 2012")),
+    (0x00d0, "Utc1700_CVTCIL_C",        Some("Visual Studio 2012")),
+    (0x00d1, "Utc1700_CVTCIL_CPP",      Some("Visual Studio 2012")),
+    (0x00d2, "Utc1700_LTCG_C",          Some("Visual Studio 2012")),
+    (0x00d3, "Utc1700_LTCG_CPP",        Some("Visual Studio 2012")),
+    (0x00d4, "Utc1700_LTCG_MSIL",       Some("Visual Studio 2012")),
+    (0x00d5, "Utc1700_POGO_I_C",        Some("Visual Studio 2012")),
+    (0x00d6, "Utc1700_POGO_I_CPP",      Some("Visual Studio 2012")),
+    (0x00d7, "Utc1700_POGO_O_C",        Some("Visual Studio 2012")),
+    (0x00d8, "Utc1700_POGO_O_CPP",      Some("Visual Studio 2012")),
+    (0x00d9, "AliasObj1200",            Some("Visual Studio 2013")),
+    (0x00da, "Cvtpgd1800",              Some("Visual Studio 2013")),
+    (0x00db, "Cvtres1200",              Some("Visual Studio 2013")),
+    (0x00dc, "Export1200",              Some("Visual Studio 2013")),
+    (0x00dd, "Implib1200",              Some("Visual Studio 2013")),
+    (0x00de, "Linker1200",              Some("Visual Studio 2013")),
+    (0x00df, "Masm1200",                Some("Visual Studio 2013")),
+    (0x00e0, "Utc1800_C",               Some("Visual Studio 2013")),
+    (0x00e1, "Utc1800_CPP",             Some("Visual Studio 2013")),
+    (0x00e2, "Utc1800_CVTCIL_C",        Some("Visual Studio 2013")),
+    (0x00e3, "Utc1800_CVTCIL_CPP",      Some("Visual Studio 2013")),
+    (0x00e4, "Utc1800_LTCG_C",          Some("Visual Studio 2013")),
+    (0x00e5, "Utc1800_LTCG_CPP",        Some("Visual Studio 2013")),
+    (0x00e6, "Utc1800_LTCG_MSIL",       Some("Visual Studio 2013")),
+    (0x00e7, "Utc1800_POGO_I_C",        Some("Visual Studio 2013")),
+    (0x00e8, "Utc1800_POGO_I_CPP",      Some("Visual Studio 2013")),
+    (0x00e9, "Utc1800_POGO_O_C",        Some("Visual Studio 2013")),
+    (0x00ea, "Utc1800_POGO_O_CPP",      Some("Visual Studio 2013")),
+    (0x00eb, "AliasObj1210",            Some("Visual Studio 2013")),
+    (0x00ec, "Cvtpgd1810",              Some("Visual Studio 2013")),
+    (0x00ed, "Cvtres1210",              Some("Visual Studio 2013")),
+    (0x00ee, "Export1210",              Some("Visual Studio 2013")),
+    (0x00ef, "Implib1210",              Some("Visual Studio 2013")),
+    (0x00f0, "Linker1210",              Some("Visual Studio 2013")),
+    (0x00f1, "Masm1210",                Some("Visual Studio 2013")),
+    (0x00f2, "Utc1810_C",               Some("Visual Studio 2013")),
+    (0x00f3, "Utc1810_CPP",             Some("Visual Studio 2013")),
+    (0x00f4, "Utc1810_CVTCIL_C",        Some("Visual Studio 2013")),
+    (0x00f5, "Utc1810_CVTCIL_CPP",      Some("Visual Studio 2013")),
+    (0x00f6, "Utc1810_LTCG_C",          Some("Visual Studio 2013")),
+    (0x00f7, "Utc1810_LTCG_CPP",        Some("Visual Studio 2013")),
+    (0x00f8, "Utc1810_LTCG_MSIL",       Some("Visual Studio 2013")),
+    (0x00f9, "Utc1810_POGO_I_C",        Some("Visual Studio 2013")),
+    (0x00fa, "Utc1810_POGO_I_CPP",      Some("Visual Studio 2013")),
+    (0x00fb, "Utc1810_POGO_O_C",        Some("Visual Studio 2013")),
+    (0x00fc, "Utc1810_POGO_O_CPP",      Some("Visual Studio 2013")),
+    (0x00fd, "AliasObj1400",            Some("Visual Studio 2015+")),
+    (0x00fe, "Cvtpgd1900",              Some("Visual Studio 2015+")),
+    (0x00ff, "Cvtres1400",              Some("Visual Studio 2015+")),
+    (0x0100, "Export1400",              Some("Visual Studio 2015+")),
+    (0x0101, "Implib1400",              Some("Visual Studio 2015+")),
+    (0x0102, "Linker1400",              Some("Visual Studio 2015+")),
+    (0x0103, "Masm1400",                Some("Visual Studio 2015+")),
+    (0x0104, "Utc1900_C",               Some("Visual Studio 2015+")),
+    (0x0105, "Utc1900_CPP",             Some("Visual Studio 2015+")),
+    (0x0106, "Utc1900_CVTCIL_C",        Some("Visual Studio 2015+")),
+    (0x0107, "Utc1900_CVTCIL_CPP",      Some("Visual Studio 2015+")),
+    (0x0108, "Utc1900_LTCG_C",          Some("Visual Studio 2015+")),
+    (0x0109, "Utc1900_LTCG_CPP",        Some("Visual Studio 2015+")),
+    (0x010a, "Utc1900_LTCG_MSIL",       Some("Visual Studio 2015+")),
+    (0x010b, "Utc1900_POGO_I_C",        Some("Visual Studio 2015+")),
+    (0x010c, "Utc1900_POGO_I_CPP",      Some("Visual Studio 2015+")),
+    (0x010d, "Utc1900_POGO_O_C",        Some("Visual Studio 2015+")),
+    (0x010e, "Utc1900_POGO_O_CPP",      Some("Visual Studio 2015+")),
+];
+
+
+
+#[test] fn find_in() {
+    let key = 0x1234_5678;
+    let mut image = vec![0u8; 0x80];
+    image[0] = b'M';
+    image[1] = b'Z';
+    let mut dword = |v: u32| image.extend_from_slice(&v.to_le_bytes());
+    dword(u32::from_le_bytes(*b"DanS") ^ key);
+    dword(key); dword(key); dword(key);
+    dword((0x0105 << 16 | 30159) ^ key); dword(42 ^ key);
+    dword((0x0102 << 16 | 27045) ^ key); dword(1 ^ key);
+    dword(u32::from_le_bytes(*b"Rich"));
+    dword(key);
+    image.resize(image.len() + 8, 0);
+
+    let rich = RichHeader::find_in(&image).unwrap().unwrap();
+    assert_eq!(rich.offset, 0x80);
+    assert_eq!(rich.key, key);
+    assert_eq!(rich.end(), 0x80 + 0x28);
+    assert_eq!(rich.entries, [
+        RichEntry { product_id: 0x0105, build: 30159, count: 42 },
+        RichEntry { product_id: 0x0102, build: 27045, count:  1 },
+    ]);
+    assert_eq!(rich.entries[0].product_name(), Some("Utc1900_CPP"));
+    assert_eq!(rich.entries[0].toolchain(), Some("Visual Studio 2019"));
+    assert_eq!(rich.entries[1].toolchain(), Some("Visual Studio 2017"));
+    assert!(!rich.is_checksum_valid());
+
+    let expected = 0x80u32
+        .wrapping_add(u32::from(b'M'))
+        .wrapping_add(u32::from(b'Z').rotate_left(1))
+        .wrapping_add((0x0105u32 << 16 | 30159).rotate_left(42))
+        .wrapping_add((0x0102u32 << 16 | 27045).rotate_left(1));
+    assert_eq!(rich.computed_checksum(), expected);
+
+    assert!(RichHeader::find_in(&image[..0x80]).unwrap().is_none());
+    image[0x80] ^= 0xFF;
+    assert!(RichHeader::find_in(&image).is_err());
+}
+
+#[test] fn products_sorted() {
+    for w in PRODUCTS.windows(2) { assert!(w[0].0 < w[1].0) }
+}
@@ -38,6 +38,7 @@ impl FromMemory for RVA {
     type Raw    = u32le;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(Self(raw.to_le())) }
+    fn to_raw(&self) -> Self::Raw { u32le::new(self.0) }
 }
 
 // RVA +- offset
@@ -0,0 +1,35 @@
+/// ⨯ {
+///     [debug](Self::debug),
+///     [certificates](Self::certificates),
+///     [rich_header](Self::rich_header),
+///     [overlay](Self::overlay),
+///     [timestamps](Self::timestamps)
+/// }<br>
+/// What [`pe::Image::strip`](crate::pe::Image::strip) removes<br>
+/// <br>
+///
+/// The [`Default`] strips everything except [`timestamps`](Self::timestamps).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StripOptions {
+    /// Remove the [`pe::DebugDirectory`](crate::pe::DebugDirectory) entries and their data (e.g. PDB paths), and any COFF symbol table
+    pub debug:          bool,
+    /// Remove the attribute certificate table (Authenticode signatures), zeroing [`pe::DataDirectories::security`](crate::pe::DataDirectories::security)
+    pub certificates:   bool,
+    /// Zero MSVC's undocumented [`pe::RichHeader`](crate::pe::RichHeader)
+    pub rich_header:    bool,
+    /// Remove any data trailing the last section (including any certificate table)
+    pub overlay:        bool,
+    /// Zero the link timestamp of the [`pe::FileHeader`](crate::pe::FileHeader), export directory, resource directories, and any remaining debug directory entries
+    pub timestamps:     bool,
+}
+
+impl StripOptions {
+    /// Strip nothing
+    pub const NONE : StripOptions = StripOptions { debug: false, certificates: false, rich_header: false, overlay: false, timestamps: false };
+    /// Strip everything
+    pub const ALL  : StripOptions = StripOptions { debug: true,  certificates: true,  rich_header: true,  overlay: true,  timestamps: true  };
+}
+
+impl Default for StripOptions {
+    fn default() -> Self { StripOptions { debug: true, certificates: true, rich_header: true, overlay: true, timestamps: false } }
+}
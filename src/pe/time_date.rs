@@ -28,6 +28,11 @@ pub struct TimeDate(u32le);
 
 impl TimeDate {
     pub const UNIX_EPOCH : TimeDate = TimeDate(u32le::new(0));
+
+    /// Seconds since [`UNIX_EPOCH`](Self::UNIX_EPOCH)
+    pub const fn from_secs(secs: u32) -> Self { TimeDate(u32le::new(secs)) }
+    /// Seconds since [`UNIX_EPOCH`](Self::UNIX_EPOCH)
+    pub fn to_secs(self) -> u32 { self.0.to_le() }
 }
 
 impl From<TimeDate> for SystemTime {
@@ -40,6 +45,7 @@ impl FromMemory for TimeDate {
     type Raw    = Self;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
 }
 
 impl Debug for TimeDate {
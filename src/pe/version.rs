@@ -54,4 +54,5 @@ impl<C: FromMemory> FromMemory for MajorMinorVersion<C> {
         let minor = C::from_raw(minor)?;
         Ok(Self { major, minor })
     }
+    fn to_raw(&self) -> Self::Raw { [self.major.to_raw(), self.minor.to_raw()] }
 }
@@ -0,0 +1,291 @@
+use crate::*;
+
+use std::convert::*;
+use std::io;
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [file_version_ms](Self::file_version_ms),
+    ///     [file_version_ls](Self::file_version_ls),
+    ///     [product_version_ms](Self::product_version_ms),
+    ///     [product_version_ls](Self::product_version_ls),
+    ///     ...
+    /// }<br>
+    /// The fixed, language independent part of a [`pe::VersionInfo`]<br>
+    /// <br>
+    ///
+    /// ## References
+    /// *   <https://learn.microsoft.com/en-us/windows/win32/api/verrsrc/ns-verrsrc-vs_fixedfileinfo>
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct FixedFileInfo {
+        /// [`FixedFileInfo::SIGNATURE`]
+        pub signature:          u32,
+        /// `0x00010000`
+        pub struc_version:      u32,
+        pub file_version_ms:    u32,
+        pub file_version_ls:    u32,
+        pub product_version_ms: u32,
+        pub product_version_ls: u32,
+        /// Which bits of [`file_flags`](Self::file_flags) are valid, typically `0x3F`
+        pub file_flags_mask:    u32,
+        /// `VS_FF_*`: e.g. `0x1` for `VS_FF_DEBUG`, `0x2` for `VS_FF_PRERELEASE`
+        pub file_flags:         u32,
+        /// `VOS_*`: typically `0x00040004` for `VOS_NT_WINDOWS32`
+        pub file_os:            u32,
+        /// `VFT_*`: `0x1` for `VFT_APP`, `0x2` for `VFT_DLL`
+        pub file_type:          u32,
+        pub file_subtype:       u32,
+        pub file_date_ms:       u32,
+        pub file_date_ls:       u32,
+    }
+}
+
+impl FixedFileInfo {
+    /// `VS_FFI_SIGNATURE`
+    pub const SIGNATURE : u32 = 0xFEEF04BD;
+
+    /// A `VFT_APP` for `VOS_NT_WINDOWS32` with the given versions (e.g. `[1, 2, 3, 4]` for `1.2.3.4`) and no flags set
+    pub fn new(file_version: [u16; 4], product_version: [u16; 4]) -> Self {
+        let ms = |v: [u16; 4]| u32::from(v[0]) << 16 | u32::from(v[1]);
+        let ls = |v: [u16; 4]| u32::from(v[2]) << 16 | u32::from(v[3]);
+        Self {
+            signature:          Self::SIGNATURE,
+            struc_version:      0x0001_0000,
+            file_version_ms:    ms(file_version),
+            file_version_ls:    ls(file_version),
+            product_version_ms: ms(product_version),
+            product_version_ls: ls(product_version),
+            file_flags_mask:    0x3F,
+            file_flags:         0,
+            file_os:            0x0004_0004,
+            file_type:          0x1,
+            file_subtype:       0,
+            file_date_ms:       0,
+            file_date_ls:       0,
+        }
+    }
+}
+
+/// ⨯ { [language](Self::language), [code_page](Self::code_page), [strings](Self::strings) }<br>
+/// A `StringTable` of a [`pe::VersionInfo`]: e.g. `"CompanyName"`, `"FileDescription"`, `"FileVersion"`, `"ProductName"`, ...<br>
+/// <br>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VersionStrings {
+    /// A `LANGID`, e.g. `0x0409` for en-US
+    pub language:   u16,
+    /// e.g. `1200` for UTF-16
+    pub code_page:  u16,
+    /// (key, value) pairs
+    pub strings:    Vec<(String, String)>,
+}
+
+/// ⨯ { [fixed](Self::fixed), [string_tables](Self::string_tables) }<br>
+/// A `VS_VERSIONINFO` block, stored as a [`pe::ResourceId::VERSION`] resource (named 1)<br>
+/// <br>
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_format_exe::*;
+/// let info = pe::VersionInfo {
+///     fixed:          pe::FixedFileInfo::new([1, 2, 3, 4], [1, 2, 0, 0]),
+///     string_tables:  vec![pe::VersionStrings {
+///         language:   0x0409,
+///         code_page:  1200,
+///         strings:    vec![("FileVersion".into(), "1.2.3.4".into()), ("ProductName".into(), "Example".into())],
+///     }],
+/// };
+/// let mut resources = pe::Resources::new();
+/// resources.insert(pe::Resource { type_id: pe::ResourceId::VERSION, name: 1.into(), language: 0x0409, code_page: 0, data: info.to_bytes().unwrap() });
+/// ```
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo>
+/// *   <https://learn.microsoft.com/en-us/windows/win32/menurc/stringfileinfo>
+/// *   <https://learn.microsoft.com/en-us/windows/win32/menurc/varfileinfo>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VersionInfo {
+    pub fixed:          FixedFileInfo,
+    /// Also used to generate the `VarFileInfo\Translation` list
+    pub string_tables:  Vec<VersionStrings>,
+}
+
+impl VersionInfo {
+    /// Parse a `VS_VERSIONINFO` block, e.g. an existing [`pe::ResourceId::VERSION`] resource to edit and re-serialize with [`to_bytes`](Self::to_bytes).
+    ///
+    /// `VarFileInfo` is skipped: [`to_bytes`](Self::to_bytes) regenerates it from [`string_tables`](Self::string_tables).
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidData`] if the root block isn't `VS_VERSION_INFO`, lacks a valid [`FixedFileInfo`], or a `StringTable` key isn't 8 hex digits
+    /// *   [`io::ErrorKind::InvalidData`] if a block key isn't `\0`-terminated
+    /// *   [`io::ErrorKind::UnexpectedEof`] if a block extends past its parent
+    pub fn parse(data: &[u8]) -> io::Result<Self> {
+        let root = Node::parse_all(data)?.into_iter().next().ok_or_else(eof)?;
+        if root.key != "VS_VERSION_INFO" { return Err(invalid("root block isn't VS_VERSION_INFO")) }
+        let fixed = FixedFileInfo::from_io(&mut &root.value[..])?;
+        if fixed.signature != FixedFileInfo::SIGNATURE { return Err(invalid("FixedFileInfo::signature isn't VS_FFI_SIGNATURE")) }
+
+        let mut string_tables = Vec::new();
+        for file_info in Node::parse_all(root.children)?.into_iter().filter(|n| n.key == "StringFileInfo") {
+            for table in Node::parse_all(file_info.children)? {
+                let hex = |s: &str| u16::from_str_radix(s, 16).map_err(|_| invalid("StringTable key isn't 8 hex digits"));
+                if table.key.len() != 8 || !table.key.is_ascii() { return Err(invalid("StringTable key isn't 8 hex digits")) }
+                let language    = hex(&table.key[.. 4])?;
+                let code_page   = hex(&table.key[4 ..])?;
+                let strings = Node::parse_all(table.children)?.into_iter().map(|s| (s.key, utf16_lossy(s.value))).collect();
+                string_tables.push(VersionStrings { language, code_page, strings });
+            }
+        }
+        Ok(Self { fixed, string_tables })
+    }
+
+    /// Serialize the `VS_VERSIONINFO` block.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if a block would exceed 64 KiB
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        node(&mut out, "VS_VERSION_INFO", 0, &self.fixed.to_bytes()[..], std::mem::size_of::<<FixedFileInfo as FromMemory>::Raw>() as u16, |out| {
+            if !self.string_tables.is_empty() {
+                node(out, "StringFileInfo", 1, &[], 0, |out| {
+                    for table in self.string_tables.iter() {
+                        node(out, &format!("{:04X}{:04X}", table.language, table.code_page), 1, &[], 0, |out| {
+                            for (key, value) in table.strings.iter() {
+                                let value = utf16z(value);
+                                let len = u16::try_from(value.len() / 2).map_err(|_| too_big())?;
+                                node(out, key, 1, &value[..], len, |_| Ok(()))?;
+                            }
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                node(out, "VarFileInfo", 1, &[], 0, |out| {
+                    let translations = self.string_tables.iter().flat_map(|t| [t.language.to_le_bytes(), t.code_page.to_le_bytes()].concat()).collect::<Vec<u8>>();
+                    let len = u16::try_from(translations.len()).map_err(|_| too_big())?;
+                    node(out, "Translation", 0, &translations[..], len, |_| Ok(()))
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+}
+
+fn too_big() -> io::Error { io::Error::new(io::ErrorKind::InvalidInput, "pe::VersionInfo: block exceeds 64 KiB") }
+fn eof() -> io::Error { io::Error::new(io::ErrorKind::UnexpectedEof, "pe::VersionInfo: block extends past its parent") }
+fn invalid(msg: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, format!("pe::VersionInfo: {}", msg)) }
+fn align4(n: usize) -> usize { (n + 3) & !3 }
+
+fn utf16z(s: &str) -> Vec<u8> {
+    s.encode_utf16().chain(Some(0)).flat_map(u16::to_le_bytes).collect()
+}
+
+/// Decode UTF-16 up to the first `\0` (if any)
+fn utf16_lossy(data: &[u8]) -> String {
+    let units = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).take_while(|u| *u != 0).collect::<Vec<_>>();
+    String::from_utf16_lossy(&units[..])
+}
+
+/// A parsed `{ wLength, wValueLength, wType, szKey, Padding, Value, Padding, Children }` block
+struct Node<'a> {
+    key:        String,
+    value:      &'a [u8],
+    children:   &'a [u8],
+}
+
+impl<'a> Node<'a> {
+    /// Parse the 4-byte aligned sibling blocks of `data` (which must itself start 4-byte aligned)
+    fn parse_all(data: &'a [u8]) -> io::Result<Vec<Self>> {
+        let mut nodes = Vec::new();
+        let mut offset = 0;
+        while offset + 6 <= data.len() {
+            let word = |o: usize| usize::from(u16::from_le_bytes([data[offset + o], data[offset + o + 1]]));
+            let (len, value_length, ty) = (word(0), word(2), word(4));
+            if len == 0 { break } // trailing padding
+            if len < 6 { return Err(invalid("block shorter than its header")) }
+            let block = data.get(offset .. offset + len).ok_or_else(eof)?;
+
+            let key_len = block[6 ..].chunks_exact(2).position(|c| c == [0, 0]).ok_or_else(|| invalid("block key isn't \\0-terminated"))?;
+            let key = utf16_lossy(&block[6 .. 6 + 2 * key_len]);
+            let value_start = align4(6 + 2 * key_len + 2).min(block.len());
+            let value_size  = if ty == 1 { 2 * value_length } else { value_length }; // text values are measured in WCHARs
+            let value_end   = (value_start + value_size).min(block.len());
+            let children    = &block[align4(value_end).min(block.len()) ..];
+            nodes.push(Node { key, value: &block[value_start .. value_end], children });
+            offset = align4(offset + len);
+        }
+        Ok(nodes)
+    }
+}
+
+/// Write a `{ wLength, wValueLength, wType, szKey, Padding, Value, Padding, Children }` block, 4-byte aligning `out` first
+fn node(out: &mut Vec<u8>, key: &str, ty: u16, value: &[u8], value_length: u16, children: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> io::Result<()> {
+    out.resize((out.len() + 3) & !3, 0);
+    let start = out.len();
+    out.extend_from_slice(&[0, 0]);
+    out.extend_from_slice(&value_length.to_le_bytes());
+    out.extend_from_slice(&ty.to_le_bytes());
+    out.extend_from_slice(&utf16z(key)[..]);
+    if !value.is_empty() {
+        out.resize((out.len() + 3) & !3, 0);
+        out.extend_from_slice(value);
+    }
+    children(out)?;
+    let len = u16::try_from(out.len() - start).map_err(|_| too_big())?;
+    out[start .. start + 2].copy_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+
+
+#[test] fn layout() {
+    assert_eq!(52, std::mem::size_of::<<FixedFileInfo as FromMemory>::Raw>());
+}
+
+#[test] fn version_info() {
+    let info = VersionInfo {
+        fixed:          FixedFileInfo::new([1, 2, 3, 4], [1, 2, 0, 0]),
+        string_tables:  vec![VersionStrings { language: 0x0409, code_page: 1200, strings: vec![("FileVersion".into(), "1.2.3.4".into())] }],
+    };
+    let bytes = info.to_bytes().unwrap();
+    assert_eq!(usize::from(u16::from_le_bytes([bytes[0], bytes[1]])), bytes.len());
+    assert_eq!(bytes[2..4], [52, 0]);
+    assert_eq!(bytes[6..8], [b'V', 0]);
+    assert_eq!(bytes[40..44], 0xFEEF04BDu32.to_le_bytes()); // 6 byte header + 32 byte key + 2 byte padding
+    assert_eq!(bytes[48..52], 0x0001_0002u32.to_le_bytes());
+    assert_eq!(bytes[52..56], 0x0003_0004u32.to_le_bytes());
+
+    let find = |needle: &str| { let needle = utf16z(needle); bytes.windows(needle.len()).position(|w| w == &needle[..]).unwrap() };
+    assert!(find("StringFileInfo") < find("040904B0"));
+    assert!(find("040904B0") < find("1.2.3.4"));
+    let translation = find("Translation") + 24 + 2; // key + padding
+    assert_eq!(bytes[translation .. translation + 4], [0x09, 0x04, 0xB0, 0x04]);
+    assert_eq!(bytes.len(), translation + 4);
+}
+
+#[test] fn parse() {
+    let info = VersionInfo {
+        fixed:          FixedFileInfo::new([1, 2, 3, 4], [1, 2, 0, 0]),
+        string_tables:  vec![
+            VersionStrings { language: 0x0409, code_page: 1200, strings: vec![("FileVersion".into(), "1.2.3.4".into()), ("Comments".into(), "".into())] },
+            VersionStrings { language: 0x0411, code_page: 932,  strings: vec![("ProductName".into(), "\u{4F8B}".into())] },
+        ],
+    };
+    assert_eq!(VersionInfo::parse(&info.to_bytes().unwrap()[..]).unwrap(), info);
+
+    // Edit a string without rebuilding the rest of the block
+    let mut edited = VersionInfo::parse(&info.to_bytes().unwrap()[..]).unwrap();
+    edited.string_tables[0].strings[0].1 = "1.2.3.5".into();
+    let edited = VersionInfo::parse(&edited.to_bytes().unwrap()[..]).unwrap();
+    assert_eq!(edited.string_tables[0].strings[0], ("FileVersion".into(), "1.2.3.5".into()));
+    assert_eq!(edited.string_tables[1], info.string_tables[1]);
+
+    let bytes = info.to_bytes().unwrap();
+    assert_eq!(VersionInfo::parse(&bytes[.. 40]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    let mut bad = bytes.clone();
+    bad[40] ^= 1; // FixedFileInfo::signature
+    assert_eq!(VersionInfo::parse(&bad[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+use bytemuck::*;
+
+use std::fmt::{self, Debug, Formatter};
+
+
+
+from_memory_struct! {
+    /// {
+    ///     [length](Self::length),
+    ///     [revision](Self::revision),
+    ///     [certificate_type](Self::certificate_type)
+    /// }<br>
+    /// Header of a single entry in the attribute certificate table referenced by [`pe::DataDirectories::security`].<br>
+    /// <br>
+    ///
+    /// Unlike most [`pe::DataDirectory`]s, [`pe::DataDirectories::security`] is a *file offset*, not an [`pe::RVA`].
+    /// Entries are 8-byte aligned, and [`length`](Self::length) includes this header.
+    ///
+    /// ## References
+    /// *   [PE Format: The Attribute Certificate Table](https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#the-attribute-certificate-table-image-only)
+    /// *   <https://learn.microsoft.com/en-us/windows/win32/api/wintrust/ns-wintrust-win_certificate>
+    /// *   `WIN_CERTIFICATE` in `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\WinTrust.h`
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct WinCertificate {
+        /// Length of this header + the certificate data that follows, in bytes.
+        pub length:                     u32,
+        /// `0x0100` (`WIN_CERT_REVISION_1_0`) or `0x0200` (`WIN_CERT_REVISION_2_0`, current)
+        pub revision:                   u16,
+        /// What kind of certificate data follows (typically [`CertificateType::PKCS_SIGNED_DATA`])
+        pub certificate_type:           CertificateType,
+    }
+}
+
+/// ∑
+/// [X509](Self::X509) |
+/// [PKCS_SIGNED_DATA](Self::PKCS_SIGNED_DATA) |
+/// [TS_STACK_SIGNED](Self::TS_STACK_SIGNED) |
+/// ...<br>
+/// [`pe::WinCertificate::certificate_type`]<br>
+/// <br>
+///
+/// ## References
+/// *   <https://learn.microsoft.com/en-us/windows/win32/api/wintrust/ns-wintrust-win_certificate>
+/// *   `WIN_CERT_TYPE_*` in `C:\Program Files (x86)\Windows Kits\10\Include\10.0.19041.0\um\WinTrust.h`
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, PartialEq, Eq)] // TODO: PartialOrd/Ord/Hash
+#[derive(Pod, Zeroable)]
+pub struct CertificateType(u16le);
+
+impl CertificateType {
+    pub const fn new(value: u16) -> Self {
+        Self(u16le::new(value))
+    }
+
+    #[doc = "X.509 Certificate (not supported by Windows)"                      ] pub const X509                : CertificateType = CertificateType::new(0x0001);
+    #[doc = "PKCS#7 SignedData structure (Authenticode)"                        ] pub const PKCS_SIGNED_DATA    : CertificateType = CertificateType::new(0x0002);
+    #[doc = "Reserved"                                                          ] pub const RESERVED_1          : CertificateType = CertificateType::new(0x0003);
+    #[doc = "Terminal Server Protocol Stack Certificate signing (not supported)" ] pub const TS_STACK_SIGNED     : CertificateType = CertificateType::new(0x0004);
+}
+
+impl Debug for CertificateType {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::X509              => write!(fmt, "CertificateType::X509"),
+            Self::PKCS_SIGNED_DATA  => write!(fmt, "CertificateType::PKCS_SIGNED_DATA"),
+            Self::RESERVED_1        => write!(fmt, "CertificateType::RESERVED_1"),
+            Self::TS_STACK_SIGNED   => write!(fmt, "CertificateType::TS_STACK_SIGNED"),
+            other                   => write!(fmt, "CertificateType(0x{:04x})", other.0.to_le()),
+        }
+    }
+}
+
+impl FromMemory for CertificateType {
+    type Raw    = Self;
+    type Error  = std::io::Error;
+    fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
+}
+
+
+
+#[test] fn layout() {
+    use std::mem::*;
+
+    assert_eq!(size_of::<<WinCertificate as FromMemory>::Raw>(), 8);
+}
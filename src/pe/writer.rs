@@ -0,0 +1,324 @@
+use crate::*;
+use super::*;
+
+use bytemuck::*;
+
+use std::convert::*;
+use std::io::{self, Write};
+use std::num::NonZeroU32;
+
+
+
+/// Builds a PE32 or PE32+ image from a [`pe::FileHeader`], [`pe::OptionalHeader`], and sections<br>
+/// <br>
+///
+/// Layout fields are computed by [`write`](Self::write), overwriting whatever was set:
+/// *   [`FileHeader::nsections`], [`optional_header_size`](FileHeader::optional_header_size), [`symbols`](FileHeader::symbols), [`nsymbols`](FileHeader::nsymbols)
+/// *   [`OptionalHeader32::magic`], `size_of_code`, `size_of_initialized_data`, `size_of_uninitialized_data`, `base_of_code`, `base_of_data`,
+///     `size_of_image`, `size_of_headers`, `checksum`, and `number_of_rva_and_sizes`
+/// *   Every [`pe::SectionHeader`] except for its name and characteristics
+///
+/// Everything else - entry point, image base, subsystem, [`DataDirectories`], etc. - is written as-is.
+/// Set the alignments before adding sections, as section [`RVA`]s are assigned by [`add_section`](Self::add_section).
+///
+/// ### Example
+/// ```
+/// # use maulingmonkey_format_exe::*;
+/// let mut dll = pe::Writer::new_pe32_plus(pe::Machine::AMD64);
+/// dll.file_header.characteristics |= pe::FileCharacteristics::DLL;
+/// let rva = dll.add_section(".rdata", pe::SectionCharacteristics::CNT_INITIALIZED_DATA | pe::SectionCharacteristics::MEM_READ, b"Hello, world!".to_vec()).unwrap();
+/// assert_eq!(rva, pe::RVA::new(0x1000));
+/// let mut data = Vec::new();
+/// dll.write(&mut data).unwrap();
+/// assert!(data.starts_with(b"MZ"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Writer {
+    /// [`machine`](FileHeader::machine), [`link_time_date`](FileHeader::link_time_date), and [`characteristics`](FileHeader::characteristics) are written as-is
+    pub file_header:        FileHeader,
+    /// A [`OptionalHeader32`] (PE32) or [`OptionalHeader64`] (PE32+)
+    pub optional_header:    OptionalHeader,
+    /// The 16-bit program following the [`mz::Header`] (default: [`mz::Stub::STANDARD`])
+    pub dos_stub:           Vec<u8>,
+    sections:               Vec<(SectionHeader, Vec<u8>)>,
+}
+
+impl Writer {
+    /// Create a 32-bit image for `machine` with `link.exe`-like defaults: no sections, a console subsystem, and ASLR + DEP enabled.
+    pub fn new_pe32(machine: Machine) -> Self {
+        Self::new(machine, FileCharacteristics::EXECUTABLE_IMAGE | FileCharacteristics::MACHINE_32BIT, OptionalHeader32 {
+            magic:                      Self::PE32_MAGIC,
+            linker_version:             MajorMinorVersion { major: 14, minor: 0 },
+            image_base:                 0x0040_0000,
+            section_alignment:          0x1000,
+            file_alignment:             0x200,
+            operating_system_version:   MajorMinorVersion { major: 6, minor: 0 },
+            subsystem_version:          MajorMinorVersion { major: 6, minor: 0 },
+            subsystem:                  Subsystem::WINDOWS_CUI,
+            dll_characteristics:        DllCharacteristics::DYNAMIC_BASE | DllCharacteristics::NX_COMPAT | DllCharacteristics::TERMINAL_SERVER_AWARE,
+            size_of_stack_reserve:      0x10_0000,
+            size_of_stack_commit:       0x1000,
+            size_of_heap_reserve:       0x10_0000,
+            size_of_heap_commit:        0x1000,
+            number_of_rva_and_sizes:    16,
+            .. Default::default()
+        }.into())
+    }
+
+    /// Create a 64-bit image for `machine` with `link.exe`-like defaults: no sections, a console subsystem, and (high entropy) ASLR + DEP enabled.
+    pub fn new_pe32_plus(machine: Machine) -> Self {
+        Self::new(machine, FileCharacteristics::EXECUTABLE_IMAGE | FileCharacteristics::LARGE_ADDRESS_AWARE, OptionalHeader64 {
+            magic:                      Self::PE32_PLUS_MAGIC,
+            linker_version:             MajorMinorVersion { major: 14, minor: 0 },
+            image_base:                 0x1_4000_0000,
+            section_alignment:          0x1000,
+            file_alignment:             0x200,
+            operating_system_version:   MajorMinorVersion { major: 6, minor: 0 },
+            subsystem_version:          MajorMinorVersion { major: 6, minor: 0 },
+            subsystem:                  Subsystem::WINDOWS_CUI,
+            dll_characteristics:        DllCharacteristics::HIGH_ENTROPY_VA | DllCharacteristics::DYNAMIC_BASE | DllCharacteristics::NX_COMPAT | DllCharacteristics::TERMINAL_SERVER_AWARE,
+            size_of_stack_reserve:      0x10_0000,
+            size_of_stack_commit:       0x1000,
+            size_of_heap_reserve:       0x10_0000,
+            size_of_heap_commit:        0x1000,
+            number_of_rva_and_sizes:    16,
+            .. Default::default()
+        }.into())
+    }
+
+    const PE32_MAGIC        : u16 = 0x010b;
+    const PE32_PLUS_MAGIC   : u16 = 0x020b;
+
+    fn new(machine: Machine, characteristics: FileCharacteristics, optional_header: OptionalHeader) -> Self {
+        Self {
+            file_header:        FileHeader { machine, characteristics, .. Default::default() },
+            optional_header,
+            dos_stub:           mz::Stub::STANDARD.to_vec(),
+            sections:           Vec::new(),
+        }
+    }
+
+    /// Add a section named `name` (up to 8 bytes) containing `data`, returning the [`RVA`] it will be loaded at.
+    ///
+    /// The [`virtual_size`](SectionHeader::virtual_size) is `data.len()`, or 1 if `data` is empty (so the next section doesn't share its [`RVA`].)
+    /// Use [`add_section_with_virtual_size`](Self::add_section_with_virtual_size) for uninitialized (`.bss`) data.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `name` is longer than 8 bytes, or the image would exceed 4 GiB
+    pub fn add_section(&mut self, name: &str, characteristics: SectionCharacteristics, data: Vec<u8>) -> io::Result<RVA> {
+        let virtual_size = u32::try_from(data.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pe::Writer: image exceeds 4 GiB"))?;
+        self.add_section_with_virtual_size(name, characteristics, data, virtual_size.max(1))
+    }
+
+    /// Add a section named `name` (up to 8 bytes) containing `data`, zero filled to `virtual_size` bytes when loaded, returning the [`RVA`] it will be loaded at.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if `name` is longer than 8 bytes, or the image would exceed 4 GiB
+    /// *   [`io::ErrorKind::InvalidInput`] if `virtual_size` is 0, or less than `data.len()`
+    pub fn add_section_with_virtual_size(&mut self, name: &str, characteristics: SectionCharacteristics, data: Vec<u8>, virtual_size: u32) -> io::Result<RVA> {
+        let too_big = || io::Error::new(io::ErrorKind::InvalidInput, "pe::Writer: image exceeds 4 GiB");
+        if virtual_size == 0 || (virtual_size as usize) < data.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Writer: section virtual size 0x{:X} is 0 or smaller than its 0x{:X} bytes of data", virtual_size, data.len())));
+        }
+        if name.len() > 8 { return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Writer: section name {:?} exceeds 8 bytes", name))) }
+        let mut short = [0u8; 8];
+        short[..name.len()].copy_from_slice(name.as_bytes());
+
+        let section_alignment = self.optional_header.section_alignment().max(1);
+        let next = match self.sections.last() {
+            Some((last, _)) => u64::from(last.virtual_address) + u64::from(last.virtual_size),
+            None            => u64::from(self.headers_size(self.sections.len() + 1)),
+        };
+        let virtual_address = u32::try_from(align(next, section_alignment)).map_err(|_| too_big())?;
+        virtual_address.checked_add(virtual_size).ok_or_else(too_big)?;
+
+        let header = SectionHeader {
+            name: cast(short),
+            virtual_size,
+            virtual_address: RVA::new(virtual_address),
+            characteristics,
+            .. Default::default()
+        };
+        self.sections.push((header, data));
+        Ok(RVA::new(virtual_address))
+    }
+
+    #[allow(missing_docs)] pub fn data_directory_mut(&mut self) -> &mut DataDirectories { self.optional_header.data_directory_mut() }
+
+    /// Lay out and write the image, computing sizes and the checksum.
+    ///
+    /// ### Errors
+    /// *   [`io::ErrorKind::InvalidInput`] if the headers overlap the first section (too many sections for [`section_alignment`](OptionalHeader32::section_alignment))
+    /// *   [`io::ErrorKind::InvalidInput`] if an alignment isn't a power of two, or the image would exceed 4 GiB
+    /// *   [`io::Error`] forwarded from [`Write::write_all`]
+    pub fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.to_bytes()?[..])
+    }
+
+    /// Lay out the image as per [`write`](Self::write), returning the bytes instead.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("pe::Writer: {}", msg));
+        let file_alignment      = self.optional_header.file_alignment();
+        let section_alignment   = self.optional_header.section_alignment();
+        if !file_alignment.is_power_of_two() || !section_alignment.is_power_of_two() { return Err(invalid("alignments must be powers of two")) }
+
+        let nsections = u16::try_from(self.sections.len()).map_err(|_| invalid("too many sections"))?;
+        let pe_header_start = self.pe_header_start();
+        let headers_size = self.headers_size(self.sections.len());
+        if let Some((first, _)) = self.sections.first() {
+            if u64::from(headers_size) > u64::from(first.virtual_address) { return Err(invalid("headers overlap the first section")) }
+        }
+        let size_of_headers = align(headers_size.into(), file_alignment);
+
+        let mut file_header = self.file_header;
+        file_header.nsections               = nsections;
+        file_header.symbols                 = 0;
+        file_header.nsymbols                = 0;
+        file_header.optional_header_size    = self.optional_header_size();
+
+        let mut sections = Vec::with_capacity(self.sections.len());
+        let mut offset = size_of_headers;
+        let (mut base_of_code, mut base_of_data) = (None, None);
+        for (header, data) in self.sections.iter() {
+            let mut header = *header;
+            let raw_size = if data.is_empty() { 0 } else { align(data.len() as u64, file_alignment) };
+            header.size_of_raw_data     = u32::try_from(raw_size).map_err(|_| invalid("image exceeds 4 GiB"))?;
+            header.pointer_to_raw_data  = if raw_size == 0 { None } else { NonZeroU32::new(u32::try_from(offset).map_err(|_| invalid("image exceeds 4 GiB"))?) };
+            offset += raw_size;
+
+            if header.characteristics.contains(SectionCharacteristics::CNT_CODE) {
+                base_of_code.get_or_insert(header.virtual_address);
+            } else {
+                base_of_data.get_or_insert(header.virtual_address);
+            }
+            sections.push((header, data));
+        }
+
+        let mut optional_header = self.optional_header;
+        let headers = sections.iter().map(|(header, _)| *header).collect::<Vec<_>>();
+        optional_header.update_sizes(&headers[..], u32::try_from(size_of_headers).map_err(|_| invalid("image exceeds 4 GiB"))?).map_err(|_| invalid("image exceeds 4 GiB"))?;
+        match &mut optional_header {
+            OptionalHeader::OptionalHeader32(oh) => {
+                oh.magic                        = Self::PE32_MAGIC;
+                oh.base_of_code                 = base_of_code.map_or(0, u32::from);
+                oh.base_of_data                 = base_of_data.map_or(0, u32::from);
+                oh.checksum                     = 0;
+                oh.number_of_rva_and_sizes      = 16;
+            },
+            OptionalHeader::OptionalHeader64(oh) => {
+                oh.magic                        = Self::PE32_PLUS_MAGIC;
+                oh.base_of_code                 = base_of_code.map_or(0, u32::from);
+                oh.checksum                     = 0;
+                oh.number_of_rva_and_sizes      = 16;
+            },
+        }
+
+        let mut image = Vec::with_capacity(offset as usize);
+        image.extend_from_slice(&mz_header(pe_header_start)[..]);
+        image.extend_from_slice(&self.dos_stub[..]);
+        image.resize(pe_header_start as usize, 0);
+        image.extend_from_slice(&Header { signature: cast(*b"PE\0\0"), file_header, optional_header: Some(optional_header) }.to_bytes()[..]);
+        for (header, _) in sections.iter() { image.extend_from_slice(&header.to_bytes()[..]) }
+        image.resize(size_of_headers as usize, 0);
+        for (header, data) in sections.iter() {
+            image.extend_from_slice(&data[..]);
+            image.resize(image.len() + (header.size_of_raw_data as usize - data.len().min(header.size_of_raw_data as usize)), 0);
+        }
+
+        update_checksum(&mut image[..], pe_header_start);
+        Ok(image)
+    }
+
+    fn pe_header_start(&self) -> u32 {
+        (std::mem::size_of::<<mz::Header as FromMemory>::Raw>() + self.dos_stub.len() + 7) as u32 & !7
+    }
+
+    fn optional_header_size(&self) -> u16 {
+        match self.optional_header {
+            OptionalHeader::OptionalHeader32(_) => std::mem::size_of::<<OptionalHeader32 as FromMemory>::Raw>() as u16,
+            OptionalHeader::OptionalHeader64(_) => std::mem::size_of::<<OptionalHeader64 as FromMemory>::Raw>() as u16,
+        }
+    }
+
+    fn headers_size(&self, nsections: usize) -> u32 {
+        let file_header     = std::mem::size_of::<<FileHeader    as FromMemory>::Raw>();
+        let section_header  = std::mem::size_of::<<SectionHeader as FromMemory>::Raw>();
+        (self.pe_header_start() as usize + 4 + file_header + usize::from(self.optional_header_size()) + section_header * nsections) as u32
+    }
+}
+
+/// Round `value` up to a multiple of `alignment` (a power of two)
+pub(crate) fn align(value: u64, alignment: u32) -> u64 {
+    let mask = u64::from(alignment.max(1)) - 1;
+    (value + mask) & !mask
+}
+
+/// Recompute and store [`OptionalHeader32::checksum`] / [`OptionalHeader64::checksum`] of an in-memory image
+pub(crate) fn update_checksum(image: &mut [u8], pe_header_start: u32) {
+    let checksum_offset = u64::from(pe_header_start) + 4 + std::mem::size_of::<<FileHeader as FromMemory>::Raw>() as u64 + OPTIONAL_HEADER_CHECKSUM_OFFSET;
+    let mut checksum = Checksum::new(checksum_offset);
+    checksum.update(image);
+    let checksum = checksum.finish();
+    image[checksum_offset as usize .. checksum_offset as usize + 4].copy_from_slice(&checksum.to_le_bytes());
+}
+
+/// The `mz::Header` emitted by `link.exe`: 3 pages, a 4 paragraph header, maximal allocation, and `SS:SP = 0000:00B8`
+fn mz_header(pe_header_start: u32) -> [u8; 64] {
+    let mut h = [0u8; 64];
+    h[..0x1A].copy_from_slice(b"MZ\x90\x00\x03\x00\x00\x00\x04\x00\x00\x00\xFF\xFF\x00\x00\xB8\x00\x00\x00\x00\x00\x00\x00\x40\x00");
+    h[0x3C..].copy_from_slice(&pe_header_start.to_le_bytes());
+    h
+}
+
+
+
+#[test] fn roundtrip() {
+    let mut w = Writer::new_pe32(Machine::I386);
+    w.file_header.characteristics |= FileCharacteristics::DLL;
+    let text = w.add_section(".text", SectionCharacteristics::CNT_CODE | SectionCharacteristics::MEM_EXECUTE | SectionCharacteristics::MEM_READ, vec![0xC3; 3]).unwrap();
+    let data = w.add_section(".data", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 0x1234]).unwrap();
+    assert_eq!((text, data), (RVA::new(0x1000), RVA::new(0x2000)));
+    w.data_directory_mut().resource = DataDirectory { virtual_address: data, size: 0x1234 };
+    assert!(w.add_section(".toolong!", SectionCharacteristics::NONE, Vec::new()).is_err());
+
+    let path = std::env::temp_dir().join(format!("maulingmonkey-format-exe-writer-{}.dll", std::process::id()));
+    w.write(&mut std::fs::File::create(&path).unwrap()).unwrap();
+    let pe = pe::Reader::open(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    pe.verify_checksum().unwrap();
+    let fh = &pe.pe_header().file_header;
+    assert_eq!(fh.nsections, 2);
+    match pe.pe_header().optional_header.unwrap() {
+        OptionalHeader::OptionalHeader32(oh) => {
+            assert_eq!(oh.size_of_headers, 0x200);
+            assert_eq!(oh.size_of_image, 0x4000);
+            assert_eq!(oh.size_of_code, 0x200);
+            assert_eq!(oh.size_of_initialized_data, 0x1400);
+            assert_eq!((oh.base_of_code, oh.base_of_data), (0x1000, 0x2000));
+        },
+        other => panic!("expected PE32, got {:?}", other),
+    }
+    assert_eq!(pe.data_directory().resource.size, 0x1234);
+    let sections = pe.pe_section_headers();
+    assert_eq!(sections[1].name.to_bytes(), b".data");
+    assert_eq!(sections[1].pointer_to_raw_data.map(u32::from), Some(0x400));
+    assert_eq!(pe.read_pe_section_data(&sections[0]).unwrap()[..4], [0xC3, 0xC3, 0xC3, 0]);
+    assert_eq!(pe.read_dos_stub().unwrap().kind(), mz::StubKind::Standard);
+}
+
+#[test] fn virtual_sizes() {
+    let mut w = Writer::new_pe32_plus(Machine::AMD64);
+    let empty = w.add_section(".empty", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, Vec::new()).unwrap();
+    let bss = w.add_section_with_virtual_size(".bss", SectionCharacteristics::CNT_UNINITIALIZED_DATA | SectionCharacteristics::MEM_READ | SectionCharacteristics::MEM_WRITE, Vec::new(), 0x2345).unwrap();
+    let data = w.add_section_with_virtual_size(".data", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![1; 4], 0x10).unwrap();
+    let after = w.add_section(".rdata", SectionCharacteristics::CNT_INITIALIZED_DATA | SectionCharacteristics::MEM_READ, vec![2; 4]).unwrap();
+    assert_eq!((empty, bss, data, after), (RVA::new(0x1000), RVA::new(0x2000), RVA::new(0x5000), RVA::new(0x6000)));
+    assert_eq!(w.add_section_with_virtual_size(".zero", SectionCharacteristics::NONE, Vec::new(), 0).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(w.add_section_with_virtual_size(".small", SectionCharacteristics::NONE, vec![0; 4], 3).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+    let pe = pe::Reader::read(crate::io::MemoryReadAt(w.to_bytes().unwrap())).unwrap();
+    let sections = pe.pe_section_headers();
+    assert_eq!(sections.iter().map(|s| s.virtual_size).collect::<Vec<_>>(), [1, 0x2345, 0x10, 4]);
+    assert_eq!((sections[1].pointer_to_raw_data, sections[1].size_of_raw_data), (None, 0));
+    assert_eq!(sections[2].size_of_raw_data, 0x200);
+}
@@ -29,4 +29,5 @@ impl<const N: usize> FromMemory for Reserved<N> {
     type Raw    = Self;
     type Error  = std::io::Error;
     fn from_raw(raw: Self::Raw) -> Result<Self, Self::Error> { Ok(raw) }
+    fn to_raw(&self) -> Self::Raw { *self }
 }